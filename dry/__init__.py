@@ -1,15 +1,20 @@
 from logging import NullHandler, getLogger
 
 from .exceptions import BridgeError, DryError, PanicError, WebviewError
+from .integrity import write_manifest
 from .interface import Webview, WindowState
+from .portal import ProtocolRequest, cancelled
 
 __all__ = [
     'BridgeError',
     'DryError',
     'PanicError',
+    'ProtocolRequest',
     'Webview',
     'WebviewError',
     'WindowState',
+    'cancelled',
+    'write_manifest',
 ]
 
 # Dry writes to no stream of its own. Records go to `dry` and its children,
@@ -0,0 +1,123 @@
+"""
+The integrity manifest a Root can be checked against.
+
+A frontend shipped beside the Python code sits on a disk its users control,
+where an update can stop halfway and a file can be edited in place. The
+manifest is the SHA-256 of every file in the Root, written once at build time
+by `write_manifest`. A Webview given it with `integrity=` serves nothing that
+does not match, and logs what it refused, so a broken install fails loudly
+instead of running half of last version's JavaScript.
+
+The manifest is JSON, one digest per file by the name the Root serves it
+under:
+
+    {"sha256": {"index.html": "e3b0c442...", "assets/app.js": "5f70bf18..."}}
+"""
+
+from collections.abc import Iterator, Mapping
+from hashlib import sha256
+from importlib.resources.abc import Traversable
+from json import dumps, loads
+from os import PathLike
+from pathlib import Path
+from re import compile as compile_pattern
+from zipfile import Path as ZipPath
+from zipfile import is_zipfile
+
+StrPath = str | PathLike[str]
+
+# What a digest in the manifest may look like: SHA-256, as hex in either case,
+# as the Root reads it.
+_DIGEST = compile_pattern(r'^[0-9a-fA-F]{64}$')
+
+
+def write_manifest(
+    root: StrPath | Traversable | Mapping[str, StrPath | Traversable],
+    manifest: StrPath,
+) -> Path:
+    """
+    Write the integrity manifest of a Root, and return where it was written.
+
+    `root` is what the Webview's `root=` is given: a directory, a zip archive,
+    a package's resources, or a mapping of URL prefixes to any of those. Run
+    it as the last step of a build, and ship the manifest with the build:
+
+        write_manifest('frontend/dist', 'frontend/dist.manifest.json')
+
+    Every file is hashed, dotfiles and precompressed siblings included, since
+    any of them may be served. Keep the manifest outside the Root, or it names
+    itself with a digest it can no longer have once written.
+
+    Args:
+        root: The Root the manifest describes.
+        manifest: Where to write it.
+    """
+    locations = root if isinstance(root, Mapping) else {'/': root}
+    digests: dict[str, str] = {}
+    for prefix, location in locations.items():
+        top = '/'.join(segment for segment in prefix.split('/') if segment)
+        for name, file in _files(_traversable(location), top):
+            digests[name] = sha256(file.read_bytes()).hexdigest()
+
+    path = Path(manifest).expanduser()
+    path.write_text(
+        dumps({'sha256': dict(sorted(digests.items()))}, indent=2) + '\n',
+        encoding='utf-8',
+    )
+    return path
+
+
+def read_manifest(manifest: StrPath) -> dict[str, str]:
+    """
+    The digests an integrity manifest holds, by file name, checked as the Root
+    checks them. A digest may be written in either case, and is read back in
+    lowercase, as `write_manifest` writes it.
+    """
+    path = Path(manifest).expanduser()
+    try:
+        content = loads(path.read_text(encoding='utf-8'))
+    except ValueError as error:
+        raise ValueError(f'{path} is not an integrity manifest: {error}') from None
+    digests = content.get('sha256') if isinstance(content, dict) else None
+    if not isinstance(digests, dict):
+        raise ValueError(
+            f'{path} is not an integrity manifest: it holds no "sha256" object.'
+        )
+    for name, digest in digests.items():  # pyright: ignore[reportUnknownVariableType]
+        if not isinstance(digest, str) or not _DIGEST.match(digest):
+            raise ValueError(
+                f'{path} is not an integrity manifest: the digest of {name!r} is '
+                f'not 64 hex digits.'
+            )
+    return {name: digest.lower() for name, digest in digests.items()}  # pyright: ignore[reportUnknownVariableType]
+
+
+def _traversable(location: StrPath | Traversable) -> Traversable:
+    """
+    A location of a Root as something that can be walked: a directory as
+    itself, a zip archive from its top.
+    """
+    if not isinstance(location, str | PathLike):
+        return location
+    path = Path(location).expanduser()
+    if path.is_file() and is_zipfile(path):
+        return ZipPath(path)
+    if not path.is_dir():
+        raise NotADirectoryError(f'root must be a directory or a zip archive: {path}')
+    return path
+
+
+def _files(directory: Traversable, name: str) -> Iterator[tuple[str, Traversable]]:
+    """
+    Every file beneath a directory, by the name the Root serves it under. A
+    directory that is a symlink is not followed, as the Root's watcher does
+    not follow one, so a link back up the tree cannot loop.
+    """
+    for child in sorted(directory.iterdir(), key=lambda child: child.name):
+        child_name = f'{name}/{child.name}' if name else child.name
+        if child.is_dir():
+            if isinstance(child, Path) and child.is_symlink():
+                continue
+            yield from _files(child, child_name)
+        elif child.is_file():
+            yield child_name, child
@@ -1,19 +1,34 @@
+from collections.abc import Mapping
 from hashlib import sha256
+from importlib.resources.abc import Traversable
+from math import isfinite
 from os import PathLike, environ
 from pathlib import Path
 from re import compile as compile_pattern
 from sys import argv, executable, platform
-from typing import Any, Callable, NamedTuple
+from typing import Any, Callable, Literal, NamedTuple
+from zipfile import Path as ZipPath
+from zipfile import is_zipfile
 
 from . import dry, portal
 from .exceptions import WebviewError
-from .portal import CloseHook, Listener
+from .integrity import read_manifest
+from .portal import CloseHook, Listener, ProtocolHandler
 
 StrPath = str | PathLike[str]
 
-# The prefix the Rust side reads a Root off. Everything after it is the absolute
-# path of the directory to serve.
-_ROOT_URL_PREFIX = 'localfile://'
+# A built-in conversion for a value outside the Bridge contract, done in Rust.
+Codec = Literal['datetime', 'decimal', 'uuid', 'path', 'enum', 'dataclass']
+
+# Every codec, in the order a value is offered to them.
+_CODECS: tuple[Codec, ...] = (
+    'datetime',
+    'decimal',
+    'uuid',
+    'path',
+    'enum',
+    'dataclass',
+)
 
 # What an App id may look like. One path segment, starting with a letter or a
 # digit, so it is a legal directory name on every platform Dry supports.
@@ -22,6 +37,87 @@ _APP_ID = compile_pattern(r'^[A-Za-z0-9][A-Za-z0-9._-]*$')
 # Anything that is not safe inside a derived App id.
 _NOT_IN_SLUG = compile_pattern(r'[^A-Za-z0-9]+')
 
+# What a custom protocol's scheme may look like, as RFC 3986 spells a scheme,
+# lowercase since the Webview compares them that way.
+_SCHEME = compile_pattern(r'^[a-z][a-z0-9+.-]*$')
+
+# What a Root's origin may look like. The scheme becomes part of a host name on
+# Windows, so it is held to what a host label may hold.
+_ORIGIN = compile_pattern(
+    r'^(?P<scheme>[a-z][a-z0-9-]*)://'
+    r'(?P<host>[a-z0-9]([a-z0-9-]*[a-z0-9])?(\.[a-z0-9]([a-z0-9-]*[a-z0-9])?)*)$'
+)
+
+# Schemes the Webview, or Dry itself, already answers.
+_RESERVED_SCHEMES = frozenset(
+    {
+        'about',
+        'blob',
+        'data',
+        'file',
+        'ftp',
+        'http',
+        'https',
+        'javascript',
+        'localfile',
+        'ws',
+        'wss',
+    }
+)
+
+
+def _header_dict(setting: str, headers: dict[str, str] | None) -> dict[str, str] | None:
+    """
+    A copy of headers a setting names, checked to be str names and values.
+    """
+    if headers is None:
+        return None
+    if not isinstance(headers, dict):  # pyright: ignore[reportUnnecessaryIsInstance]
+        raise TypeError(f'{setting} must be a dict, got {type(headers).__name__}.')
+    for name, value in headers.items():
+        if not isinstance(name, str) or not isinstance(value, str):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'{setting} maps str header names to str values, got '
+                f'{name!r}: {value!r}.'
+            )
+    return dict(headers)
+
+
+def _pattern_list(setting: str, patterns: list[str] | None) -> list[str] | None:
+    """
+    A copy of the glob patterns a setting names, checked to be str.
+    """
+    if patterns is None:
+        return None
+    if isinstance(patterns, str) or not isinstance(patterns, list | tuple):  # pyright: ignore[reportUnnecessaryIsInstance]
+        raise TypeError(
+            f'{setting} must be a list of glob patterns, got '
+            f'{type(patterns).__name__}.'
+        )
+    for pattern in patterns:
+        if not isinstance(pattern, str):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(f'{setting} holds glob patterns as str, got {pattern!r}.')
+    return list(patterns)
+
+
+def _seconds(setting: str, seconds: float | None) -> float | None:
+    """
+    A timeout a setting names, checked to be a positive number of seconds, or
+    None for no limit.
+    """
+    if seconds is None:
+        return None
+    if isinstance(seconds, bool) or not isinstance(seconds, int | float):  # pyright: ignore[reportUnnecessaryIsInstance]
+        raise TypeError(
+            f'{setting} must be a number of seconds or None, got '
+            f'{type(seconds).__name__}.'
+        )
+    if not isfinite(seconds) or seconds <= 0:
+        raise ValueError(
+            f'{setting} must be a positive number of seconds, got {seconds}.'
+        )
+    return float(seconds)
+
 
 def _user_data_directory() -> Path:
     """
@@ -64,6 +160,66 @@ def _derive_app_id() -> str:
     return f'dry.{slug}.{digest}'
 
 
+def _locate_resources(resources: Traversable) -> tuple[Path, str]:
+    """
+    Where a package's resources are on disk, as a Root is served from them:
+    the directory itself, or the zip archive holding them and the directory
+    inside it.
+
+    A package installed normally hands back a `Path`; one imported from a zip,
+    by `zipimport` or from a `.pyz`, hands back a `zipfile.Path` into it. Those
+    are the two a Root can serve. A loader that keeps its files somewhere else
+    again has no place on disk to serve them from, and says so.
+    """
+    if isinstance(resources, Path):
+        return resources.resolve(), ''
+    if isinstance(resources, ZipPath):
+        archive = resources.root.filename
+        if archive is not None:
+            return Path(archive).resolve(), resources.at
+    raise TypeError(
+        f'root cannot serve resources kept by {type(resources).__name__}: only '
+        f'a directory on disk or a directory inside a zip archive can be served.'
+    )
+
+
+def _mount(prefix: str, location: Path | Traversable) -> dict[str, str]:
+    """
+    One location of a Root as the Rust side reads it: the URL prefix it is
+    served beneath, and where on disk it is.
+    """
+    path, within = _locate_resources(location)
+    return {'prefix': prefix, 'path': str(path), 'within': within}
+
+
+def _root_location(root: StrPath | Traversable) -> Path | Traversable:
+    """
+    A directory, zip archive or directory of package resources a Root may
+    serve, checked to be one.
+    """
+    if not isinstance(root, str | PathLike):
+        if not isinstance(root, Traversable):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'root must be a str, an os.PathLike or an importlib.resources '
+                f'Traversable, got {type(root).__name__}.'
+            )
+        if not root.is_dir():
+            raise NotADirectoryError(
+                f'root must be a directory of package resources: {root}'
+            )
+        _locate_resources(root)
+        return root
+    path = Path(root).expanduser()
+    if not path.exists():
+        raise FileNotFoundError(f'root does not exist: {path}')
+    if not path.is_dir() and not is_zipfile(path):
+        raise NotADirectoryError(
+            f'root must be a directory or a zip archive, not a file: {path}. '
+            f'To render a single file, read it and set webview.html instead.'
+        )
+    return path.resolve()
+
+
 class WindowState(NamedTuple):
     """
     What the window is doing, in one reading.
@@ -122,7 +278,9 @@ class Webview:
     The Webview renders exactly one Content, declared as exactly one of three
     mutually exclusive modes: an HTML string (`html`), a URL (`url`), or a Root
     (`root`) — a local directory served to the Webview so that relative assets
-    resolve. Declaring more than one, or none, raises.
+    resolve. Declaring more than one, or none, raises. Only a Root is served by
+    Dry, so the settings that shape how it is served, from `root_origin` to
+    `stream_threshold`, have no effect on `html` or `url`.
 
     The Bridge to that frontend carries two shapes, both ways. A Call returns a
     value: the frontend Calls a name in `api` and awaits what Python returns. An
@@ -167,8 +325,52 @@ class Webview:
         icon_path: Path to the window icon (.ico format).
         html: An HTML string to render.
         url: A URL to load.
-        root: A local directory to serve, starting at its index.html.
+        root: A local directory or zip archive to serve, starting at its
+            index.html, or a package's resources as `importlib.resources`
+            hands them back. A mapping of URL prefixes to any of those serves
+            each beneath its prefix, the longest prefix winning.
+        root_origin: The scheme and host a Root is served under, as
+            `'localfile://localhost'`. Local storage and cookies belong to
+            it, so choose it once.
+        secure_origin: Whether the Root is served under `https` where the
+            webview maps its scheme onto one, which is Windows.
+        root_transport: How the Root reaches the page: `'protocol'`, over an
+            internal protocol at `root_origin`, or `'loopback'`, from an HTTP
+            server Dry starts on `127.0.0.1` for a frontend that needs an
+            `http` origin.
+        cache_control: How long the Webview may keep a file from the Root
+            without asking for it again: glob patterns mapped to a
+            Cache-Control value, or to `'immutable'` for hashed asset names.
+            The first pattern matching a file decides it; anything else is
+            `no-cache`.
+        headers: Headers every response from the Root carries, such as a
+            Content-Security-Policy.
+        header_overrides: Glob patterns mapped to headers the files they name
+            carry instead. An empty value leaves a header off.
+        include: Glob patterns a file from the Root must match one of to be
+            served. Every file, when not given.
+        exclude: Glob patterns no file served from the Root may match.
+        dotfiles: Whether a file from the Root whose path has a segment
+            starting with `.` may be served. Off, so `.env` is never sent.
+        integrity: An integrity manifest `dry.write_manifest` wrote for the
+            Root. Any file it does not vouch for is refused and logged.
+        mime_types: File extensions, such as `'.glb'`, mapped to the content
+            type a file from the Root is served with, over the built-in table.
+        spa_fallback: Whether a path in the Root with no file and no
+            extension is answered with its index.html, for a frontend router
+            using the History API. A missing asset is still a 404.
+        live_reload: Watch the Root's directories while the Webview runs:
+            `'reload'` reloads the page when its files change, `'event'` only
+            emits `window:root-changed` with the paths that changed.
+        stream_threshold: How many bytes a file from the Root may hold before
+            a request for it is answered a chunk of that size at a time.
+        protocols: Custom URL schemes, such as `app`, mapped to the Python
+            callable that answers a request for them.
         api: The names the frontend may Call, mapped to Python callables.
+        call_timeout: How many seconds a Call may run before it is rejected
+            with a `TimeoutError`. No limit, when not given.
+        call_timeouts: Api entries mapped to a timeout of their own, over
+            `call_timeout`. `None` lifts the limit for that entry.
         dev_tools: Whether to enable the developer tools.
         app_id: A stable reverse-domain identifier, such as
             `com.example.myapp`, deciding where cookies, local storage and
@@ -178,6 +380,15 @@ class Webview:
         default: Called with any value outside the Bridge contract, exactly as
             `json.dumps(default=...)` calls it, and must return something
             inside the contract. Raises if it does not.
+        codecs: The built-in conversions to apply before `default`: `True`
+            for all of them, or a list naming some of `'datetime'`,
+            `'decimal'`, `'uuid'`, `'path'`, `'enum'` and `'dataclass'`.
+        object_hook: Called with every object the frontend sends, as a dict,
+            exactly as `json.loads(object_hook=...)` calls it, and what it
+            returns stands in that object's place.
+        big_integers: Whether an int past ±2**53 crosses as a JavaScript
+            BigInt, and a BigInt from the frontend arrives as an int, rather
+            than raising.
 
     Example:
         >>> wv = Webview(title='My App', html='<h1>Hello World</h1>')
@@ -190,16 +401,36 @@ class Webview:
     __slots__ = (
         '_api',
         '_app_id',
+        '_big_integers',
+        '_cache_control',
+        '_call_timeout',
+        '_call_timeouts',
+        '_codecs',
         '_decorations',
         '_default',
         '_dev_tools',
+        '_dotfiles',
+        '_exclude',
+        '_header_overrides',
+        '_headers',
         '_html',
         '_icon_path',
+        '_include',
+        '_integrity',
+        '_live_reload',
+        '_mime_types',
         '_min_size',
+        '_object_hook',
         '_on_close',
+        '_protocols',
         '_root',
+        '_root_origin',
+        '_root_transport',
         '_running',
+        '_secure_origin',
         '_size',
+        '_spa_fallback',
+        '_stream_threshold',
         '_title',
         '_url',
         '_user_data_folder',
@@ -215,19 +446,39 @@ class Webview:
         icon_path: StrPath | None = None,
         html: str | None = None,
         url: str | None = None,
-        root: StrPath | None = None,
+        root: StrPath | Traversable | Mapping[str, StrPath | Traversable] | None = None,
+        root_origin: str = 'localfile://localhost',
+        secure_origin: bool = False,
+        root_transport: Literal['protocol', 'loopback'] = 'protocol',
+        cache_control: dict[str, str] | None = None,
+        headers: dict[str, str] | None = None,
+        header_overrides: dict[str, dict[str, str]] | None = None,
+        include: list[str] | None = None,
+        exclude: list[str] | None = None,
+        dotfiles: bool = False,
+        integrity: StrPath | None = None,
+        mime_types: dict[str, str] | None = None,
+        spa_fallback: bool = False,
+        stream_threshold: int = 8 * 1024 * 1024,
+        live_reload: Literal['reload', 'event'] | None = None,
+        protocols: dict[str, ProtocolHandler] | None = None,
         api: dict[str, Callable[..., Any]] | None = None,
+        call_timeout: float | None = None,
+        call_timeouts: dict[str, float | None] | None = None,
         dev_tools: bool = False,
         app_id: str | None = None,
         user_data_folder: StrPath | None = None,
         default: Callable[[Any], Any] | None = None,
+        codecs: bool | list[Codec] = False,
+        object_hook: Callable[[dict[str, Any]], Any] | None = None,
+        big_integers: bool = False,
         on_close: CloseHook | None = None,
     ) -> None:
         self._running = False
 
         self._html: str | None = None
         self._url: str | None = None
-        self._root: Path | None = None
+        self._root: Path | Traversable | dict[str, Path | Traversable] | None = None
         self._user_data_folder: str | None = None
 
         self.title = title
@@ -241,12 +492,32 @@ class Webview:
             self.url = url
         if root is not None:
             self.root = root
+        self.root_origin = root_origin
+        self.secure_origin = secure_origin
+        self.root_transport = root_transport
+        self.cache_control = cache_control
+        self.headers = headers
+        self.header_overrides = header_overrides
+        self.include = include
+        self.exclude = exclude
+        self.dotfiles = dotfiles
+        self.integrity = integrity
+        self.mime_types = mime_types
+        self.spa_fallback = spa_fallback
+        self.stream_threshold = stream_threshold
+        self.live_reload = live_reload
+        self.protocols = protocols
         self.api = api
+        self.call_timeout = call_timeout
+        self.call_timeouts = call_timeouts
         self.dev_tools = dev_tools
         self.app_id = app_id if app_id is not None else _derive_app_id()
         if user_data_folder is not None:
             self.user_data_folder = user_data_folder
         self.default = default
+        self.codecs = codecs
+        self.object_hook = object_hook
+        self.big_integers = big_integers
         self.on_close = on_close
 
     def _refuse_late_assignment(self, setting: str) -> None:
@@ -562,28 +833,542 @@ class Webview:
         self._url = url
 
     @property
-    def root(self) -> Path | None:
+    def root(self) -> Path | Traversable | dict[str, Path | Traversable] | None:
         """
-        The directory served to the Webview, if that is its Content.
+        The directory or zip archive served to the Webview, if that is its
+        Content.
+
+        An archive is served as the directory it would unpack to, without
+        being unpacked: `index.html` at its top is the first page, and every
+        rule a directory is served by applies to its entries. Any file
+        `zipfile` recognises is accepted, including an archive appended to an
+        executable, so a frontend can ship inside the one file that runs it.
+
+        A package's own resources can be served wherever pip put them, by
+        assigning what `importlib.resources` hands back:
+
+            wv.root = files('myapp') / 'frontend' / 'dist'
+
+        That is a directory for a package installed normally, and a directory
+        inside a zip for one imported from an archive; both are served the
+        same way, and the Traversable reads back as it was assigned.
+
+        Several locations can be served under one origin, each beneath a URL
+        prefix:
+
+            wv.root = {'/': 'dist', '/themes': themes_dir}
+
+        A path is answered by the mount with the longest prefix it falls
+        under, and only by that one, and nothing under one prefix can reach
+        another's files.
         """
         return self._root
 
     @root.setter
-    def root(self, root: StrPath | None) -> None:
+    def root(
+        self,
+        root: StrPath | Traversable | Mapping[str, StrPath | Traversable] | None,
+    ) -> None:
         self._refuse_late_assignment('root')
         if root is None:
             self._root = None
             return
-        directory = Path(root).expanduser()
-        if not directory.exists():
-            raise FileNotFoundError(f'root does not exist: {directory}')
-        if not directory.is_dir():
-            raise NotADirectoryError(
-                f'root must be a directory, not a file: {directory}. To render a '
-                f'single file, read it and set webview.html instead.'
-            )
+        if isinstance(root, Mapping):
+            self._set_root_mounts(root)  # pyright: ignore[reportUnknownArgumentType]
+            return
+        location = _root_location(root)
         self._refuse_second_mode('root')
-        self._root = directory.resolve()
+        self._root = location
+
+    def _set_root_mounts(self, mounts: Mapping[str, StrPath | Traversable]) -> None:
+        """
+        Set the Root to several locations, each beneath a URL prefix.
+        """
+        locations: dict[str, Path | Traversable] = {}
+        for prefix, location in mounts.items():
+            if not isinstance(prefix, str) or not prefix.startswith('/'):  # pyright: ignore[reportUnnecessaryIsInstance]
+                raise ValueError(
+                    f"root maps URL prefixes starting with '/' to what is served "
+                    f'beneath them, got {prefix!r}.'
+                )
+            if '/' + prefix.strip('/') in {'/' + seen.strip('/') for seen in locations}:
+                raise ValueError(f'root mounts {prefix!r} more than once.')
+            locations[prefix] = _root_location(location)
+        if not locations:
+            raise ValueError('root maps no URL prefix to anything to serve.')
+        self._refuse_second_mode('root')
+        self._root = locations
+
+    @property
+    def root_origin(self) -> str:
+        """
+        The scheme and host the Root is served under.
+
+        Everything the page keeps — local storage, IndexedDB, cookies, a
+        service worker's registration — belongs to its origin, so moving the
+        Root to another one leaves all of it behind with the old. Choose it
+        before the first release and keep it:
+
+            wv.root_origin = 'myapp://app'
+
+        The scheme is a lowercase letter followed by lowercase letters, digits
+        or `-`, and may not be one the Webview answers already or one in
+        `protocols`. The host is one or more dot-separated labels of lowercase
+        letters, digits and `-`. On Windows the webview serves it as
+        `http://myapp.app/`, the scheme folded into the host.
+        """
+        return self._root_origin
+
+    @root_origin.setter
+    def root_origin(self, root_origin: str) -> None:
+        self._refuse_late_assignment('root_origin')
+        if not isinstance(root_origin, str):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'root_origin must be a str, got {type(root_origin).__name__}.'
+            )
+        match = _ORIGIN.match(root_origin)
+        if match is None:
+            raise ValueError(
+                f'root_origin must be a scheme and a host, such as '
+                f"'myapp://app', got {root_origin!r}."
+            )
+        if match['scheme'] in _RESERVED_SCHEMES - {'localfile'}:
+            raise ValueError(
+                f'The scheme {match["scheme"]!r} is answered by the Webview '
+                f'already and cannot serve the Root.'
+            )
+        self._root_origin = root_origin
+
+    @property
+    def secure_origin(self) -> bool:
+        """
+        Whether the Root is served under `https` where the webview lets it be.
+
+        Service workers, `crypto.subtle` and the async clipboard only exist in
+        a secure context. On Windows a Root is served as `http://` by default,
+        which is not one; this makes it `https://`. WebKitGTK on Linux already
+        treats the Root's scheme as secure, and on macOS WKWebView decides for
+        itself, so this changes nothing there.
+
+        It is part of the origin on Windows, so turning it on later leaves the
+        page's stored data behind, as changing `root_origin` does.
+        """
+        return self._secure_origin
+
+    @secure_origin.setter
+    def secure_origin(self, secure_origin: bool) -> None:
+        self._refuse_late_assignment('secure_origin')
+        self._secure_origin = secure_origin
+
+    @property
+    def root_transport(self) -> Literal['protocol', 'loopback']:
+        """
+        How the Root reaches the page.
+
+        `'protocol'` serves it over an internal protocol at `root_origin`,
+        with no server, no port and nothing another process can reach. Some
+        frontends will not run there: an OAuth provider that only redirects to
+        `http://127.0.0.1`, a WASM loader that fetches by URL, a service
+        worker. `'loopback'` serves the same Root from an HTTP server Dry starts
+        on `127.0.0.1`, at a port the system picks, and opens the page there:
+
+            wv.root_transport = 'loopback'
+
+        The server only answers the Webview. It is let in with a token drawn
+        for the session, held in a cookie from then on, and anything else on
+        the machine that connects is refused with a `403`. The port changes
+        every run, and with it the origin, so a page served this way keeps
+        nothing in local storage from one run to the next; `root_origin` and
+        `secure_origin` have no effect.
+        """
+        return self._root_transport
+
+    @root_transport.setter
+    def root_transport(self, root_transport: Literal['protocol', 'loopback']) -> None:
+        self._refuse_late_assignment('root_transport')
+        if root_transport not in ('protocol', 'loopback'):
+            raise ValueError(
+                f"root_transport must be 'protocol' or 'loopback', got "
+                f'{root_transport!r}.'
+            )
+        self._root_transport = root_transport
+
+    @property
+    def cache_control(self) -> dict[str, str] | None:
+        """
+        The cache policy for files served from the Root, if there is one.
+
+        Glob patterns mapped to the `Cache-Control` a matching file is served
+        with, in order, the first match deciding:
+
+            wv.cache_control = {
+                'assets/*': 'immutable',
+                '*.html': 'no-cache',
+            }
+
+        A pattern without a `/` names a file anywhere in the Root; one with a
+        `/` is anchored at its top. `'immutable'` stands for a year of caching
+        with no revalidation, which is right for a file whose name carries a
+        hash of its content and wrong for anything else. A file no pattern
+        names is `no-cache`: the Webview keeps a copy and asks before using it,
+        and the answer is a `304` unless the file has changed.
+        """
+        return self._cache_control
+
+    @cache_control.setter
+    def cache_control(self, cache_control: dict[str, str] | None) -> None:
+        self._refuse_late_assignment('cache_control')
+        if cache_control is None:
+            self._cache_control = None
+            return
+        if not isinstance(cache_control, dict):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'cache_control must be a dict, got {type(cache_control).__name__}.'
+            )
+        for pattern, policy in cache_control.items():
+            if not isinstance(pattern, str) or not isinstance(policy, str):  # pyright: ignore[reportUnnecessaryIsInstance]
+                raise TypeError(
+                    f'cache_control maps str patterns to str policies, got '
+                    f'{pattern!r}: {policy!r}.'
+                )
+        self._cache_control = dict(cache_control)
+
+    @property
+    def headers(self) -> dict[str, str] | None:
+        """
+        The headers every response from the Root carries, if there are any.
+
+        What a static host would be configured to send, and a Root has no
+        host to configure:
+
+            wv.headers = {
+                'Content-Security-Policy': "default-src 'self'",
+                'X-Content-Type-Options': 'nosniff',
+            }
+
+        They are on every response, a `404` included. The headers Dry works
+        out for each file — `Content-Type`, `Content-Length`, `ETag` and the
+        rest — cannot be set, and `Cache-Control` is `cache_control`.
+        """
+        return self._headers
+
+    @headers.setter
+    def headers(self, headers: dict[str, str] | None) -> None:
+        self._refuse_late_assignment('headers')
+        self._headers = _header_dict('headers', headers)
+
+    @property
+    def header_overrides(self) -> dict[str, dict[str, str]] | None:
+        """
+        Headers for the files a glob pattern names, over the defaults.
+
+        Patterns are read as `cache_control` reads them. Each header an
+        override sets replaces the default for the files it matches, the first
+        matching pattern that sets a header deciding it, and an empty value
+        leaves the header off:
+
+            wv.header_overrides = {
+                'wasm/**': {
+                    'Cross-Origin-Opener-Policy': 'same-origin',
+                    'Cross-Origin-Embedder-Policy': 'require-corp',
+                },
+                'embed.html': {'Content-Security-Policy': ''},
+            }
+        """
+        return self._header_overrides
+
+    @header_overrides.setter
+    def header_overrides(
+        self, header_overrides: dict[str, dict[str, str]] | None
+    ) -> None:
+        self._refuse_late_assignment('header_overrides')
+        if header_overrides is None:
+            self._header_overrides = None
+            return
+        if not isinstance(header_overrides, dict):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'header_overrides must be a dict, got '
+                f'{type(header_overrides).__name__}.'
+            )
+        overrides: dict[str, dict[str, str]] = {}
+        for pattern, headers in header_overrides.items():
+            if not isinstance(pattern, str):  # pyright: ignore[reportUnnecessaryIsInstance]
+                raise TypeError(
+                    f'header_overrides maps str patterns to headers, got '
+                    f'{pattern!r}.'
+                )
+            overrides[pattern] = _header_dict(
+                f'header_overrides[{pattern!r}]', headers
+            ) or {}
+        self._header_overrides = overrides
+
+    @property
+    def include(self) -> list[str] | None:
+        """
+        The glob patterns a file from the Root must match one of to be served.
+
+        A build folder collects more than the frontend asks for. Naming what
+        ships keeps everything else out of reach, whatever ends up beside it:
+
+            wv.include = ['index.html', 'assets/**', '*.woff2']
+
+        Patterns are read as `cache_control` reads them, against the file a
+        request resolves to, so `/` is judged as `index.html`. A file held
+        back is a `404`, as if it were not there. Every file may be served
+        when this is not set.
+        """
+        return self._include
+
+    @include.setter
+    def include(self, include: list[str] | None) -> None:
+        self._refuse_late_assignment('include')
+        self._include = _pattern_list('include', include)
+
+    @property
+    def exclude(self) -> list[str] | None:
+        """
+        The glob patterns no file served from the Root may match.
+
+            wv.exclude = ['*.map', 'drafts/**']
+
+        Exclusion wins over `include`. A file held back is a `404`.
+        """
+        return self._exclude
+
+    @exclude.setter
+    def exclude(self, exclude: list[str] | None) -> None:
+        self._refuse_late_assignment('exclude')
+        self._exclude = _pattern_list('exclude', exclude)
+
+    @property
+    def dotfiles(self) -> bool:
+        """
+        Whether a file from the Root whose path has a segment starting with `.`
+        may be served.
+
+        Off, so `.env`, `.git/config` and `.DS_Store` are a `404` even when a
+        build leaves them in the Root. Turn it on for `.well-known/`, and
+        `exclude` what should still stay hidden.
+        """
+        return self._dotfiles
+
+    @dotfiles.setter
+    def dotfiles(self, dotfiles: bool) -> None:
+        self._refuse_late_assignment('dotfiles')
+        self._dotfiles = dotfiles
+
+    @property
+    def integrity(self) -> Path | None:
+        """
+        The integrity manifest the Root is checked against.
+
+        A build writes it with `dry.write_manifest`, and the app ships it
+        beside the Root:
+
+            wv.integrity = Path(__file__).parent / 'dist.manifest.json'
+
+        Every file served must then be named in it, with the SHA-256 its
+        content has. One that is not, or that no longer hashes to its digest —
+        an update that stopped halfway, a file edited in place — is answered
+        with a `500` and logged on `dry.webview` as an error.
+
+        A file of up to 1 MiB is hashed each time it is served. A larger one is
+        hashed the first time, then trusted while its size and modification
+        time stay the same, so a rewrite that keeps both goes unnoticed until
+        the next run. This catches a broken install, not a determined edit.
+
+        The manifest is read when it is assigned, and again at `run()`.
+        """
+        return self._integrity
+
+    @integrity.setter
+    def integrity(self, integrity: StrPath | None) -> None:
+        self._refuse_late_assignment('integrity')
+        if integrity is None:
+            self._integrity = None
+            return
+        if not isinstance(integrity, str | PathLike):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'integrity must be a str or an os.PathLike, got '
+                f'{type(integrity).__name__}.'
+            )
+        path = Path(integrity).expanduser().resolve()
+        read_manifest(path)
+        self._integrity = path
+
+    @property
+    def mime_types(self) -> dict[str, str] | None:
+        """
+        Content types by file extension, over the built-in table.
+
+        The built-in table covers the formats a web frontend ships, from
+        scripts and fonts to `.glb` models; anything it does not know is
+        `application/octet-stream`, which the Webview will not run or render:
+
+            wv.mime_types = {
+                '.usd': 'model/vnd.usd',
+                '.ts': 'video/mp2t',
+            }
+
+        An extension may have dots of its own, `'.d.ts'`, and the longest one
+        a file name ends in decides it. Case does not matter.
+        """
+        return self._mime_types
+
+    @mime_types.setter
+    def mime_types(self, mime_types: dict[str, str] | None) -> None:
+        self._refuse_late_assignment('mime_types')
+        if mime_types is None:
+            self._mime_types = None
+            return
+        if not isinstance(mime_types, dict):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'mime_types must be a dict, got {type(mime_types).__name__}.'
+            )
+        for extension, content_type in mime_types.items():
+            if not isinstance(extension, str) or not isinstance(content_type, str):  # pyright: ignore[reportUnnecessaryIsInstance]
+                raise TypeError(
+                    f'mime_types maps str extensions to str content types, got '
+                    f'{extension!r}: {content_type!r}.'
+                )
+            if not extension.startswith('.') or len(extension) < 2:
+                raise ValueError(
+                    f"mime_types keys are extensions starting with '.', such as "
+                    f"'.glb'. Got: {extension!r}."
+                )
+        self._mime_types = dict(mime_types)
+
+    @property
+    def spa_fallback(self) -> bool:
+        """
+        Whether a route in the Root is answered with its index.html.
+
+        A single-page app whose router uses the History API puts paths such as
+        `/settings/profile` in the address bar, and a reload asks the Root for
+        that path. With this on, a path the Root has no file for, and whose
+        last segment has no extension, is answered with the index, so the
+        router draws the page. A missing `app.js` or `theme.css` is still a
+        404: a script tag handed an HTML page would hide the broken reference.
+        """
+        return self._spa_fallback
+
+    @spa_fallback.setter
+    def spa_fallback(self, spa_fallback: bool) -> None:
+        self._refuse_late_assignment('spa_fallback')
+        self._spa_fallback = spa_fallback
+
+    @property
+    def stream_threshold(self) -> int:
+        """
+        How large a file from the Root may be before it is streamed, in bytes.
+
+        A `<video>` opens a file with a request for `bytes=0-`, all of it. A
+        file larger than this is answered with this many bytes instead, and the
+        element asks for the next run when it needs it, so a 2 GB recording is
+        never read whole. A request with no range at all, such as a plain
+        `fetch`, is still answered with the whole file, as HTTP requires.
+
+        Every file is read off the thread that draws the window, whatever its
+        size, by one of four workers.
+        """
+        return self._stream_threshold
+
+    @stream_threshold.setter
+    def stream_threshold(self, stream_threshold: int) -> None:
+        self._refuse_late_assignment('stream_threshold')
+        if isinstance(stream_threshold, bool) or not isinstance(stream_threshold, int):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'stream_threshold must be an int, got '
+                f'{type(stream_threshold).__name__}.'
+            )
+        if stream_threshold < 1:
+            raise ValueError(
+                f'stream_threshold must be at least 1 byte, got {stream_threshold}.'
+            )
+        self._stream_threshold = stream_threshold
+
+    @property
+    def live_reload(self) -> Literal['reload', 'event'] | None:
+        """
+        What an edit to the Root does while the Webview runs, if anything.
+
+        A development setting. The directories the Root serves are scanned a
+        few times a second, and once a burst of changes has been quiet for a
+        moment — a bundler rewriting its output — Dry emits
+        `window:root-changed` to both sides, with the paths that changed as
+        the page would ask for them:
+
+            wv.live_reload = 'event'
+
+            window.dry.on('window:root-changed', (paths) => {
+                if (paths.every((path) => path.endsWith('.css'))) swapStyles();
+                else location.reload();
+            });
+
+        With `'reload'` the page is also reloaded, so an edit shows without
+        the window being closed and the app run again. A Root served from a
+        zip archive is read once and not watched. Leave this off in a shipped
+        application: it costs a walk of the Root every quarter second.
+        """
+        return self._live_reload
+
+    @live_reload.setter
+    def live_reload(self, live_reload: Literal['reload', 'event'] | None) -> None:
+        self._refuse_late_assignment('live_reload')
+        if live_reload not in (None, 'reload', 'event'):
+            raise ValueError(
+                f"live_reload must be 'reload', 'event' or None, got {live_reload!r}."
+            )
+        self._live_reload = live_reload
+
+    @property
+    def protocols(self) -> dict[str, ProtocolHandler] | None:
+        """
+        Custom URL schemes, mapped to the callable that answers them.
+
+        A handler is called with a `ProtocolRequest` for every request the
+        page makes to its scheme, and returns a `(status, headers, body)`
+        tuple, the body as bytes or str:
+
+            def thumbnail(request: ProtocolRequest):
+                png = render(request.path)
+                return 200, {'Content-Type': 'image/png'}, png
+
+            wv.protocols = {'app': thumbnail}
+
+        It runs where a Call runs, a coroutine on the portal's loop and a plain
+        function on its thread pool, so a slow one holds up only its own
+        request. One that raises answers with a `500` naming the exception.
+        """
+        return self._protocols
+
+    @protocols.setter
+    def protocols(self, protocols: dict[str, ProtocolHandler] | None) -> None:
+        self._refuse_late_assignment('protocols')
+        if protocols is None:
+            self._protocols = None
+            return
+        if not isinstance(protocols, dict):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'protocols must be a dict, got {type(protocols).__name__}.'
+            )
+        for scheme, handler in protocols.items():
+            if not isinstance(scheme, str) or not _SCHEME.match(scheme):  # pyright: ignore[reportUnnecessaryIsInstance]
+                raise ValueError(
+                    f'{scheme!r} is not a URL scheme: a lowercase letter, then '
+                    f'lowercase letters, digits, "+", "-" or ".".'
+                )
+            if scheme in _RESERVED_SCHEMES:
+                raise ValueError(
+                    f'The scheme {scheme!r} is answered by the Webview already '
+                    f'and cannot be given a handler.'
+                )
+            if not callable(handler):
+                raise TypeError(
+                    f'The handler for the protocol {scheme!r} is not callable, '
+                    f'got {type(handler).__name__}.'
+                )
+        self._protocols = dict(protocols)
 
     @property
     def api(self) -> dict[str, Callable[..., Any]] | None:
@@ -602,6 +1387,59 @@ class Webview:
         self._refuse_late_assignment('api')
         self._api = api
 
+    @property
+    def call_timeout(self) -> float | None:
+        """
+        How many seconds a Call may run before its Promise is rejected with a
+        `TimeoutError`, or None for no limit, which is the default.
+
+        A Call that never answers otherwise leaves the frontend waiting for
+        good. Once the time is up, the Call is cancelled as a Call the frontend
+        cancels is: a coroutine at its next `await`, a callable in the thread
+        pool through `dry.cancelled()`. What it answers after that is dropped,
+        and logged at warning on `dry.bridge`.
+        """
+        return self._call_timeout
+
+    @call_timeout.setter
+    def call_timeout(self, call_timeout: float | None) -> None:
+        self._refuse_late_assignment('call_timeout')
+        self._call_timeout = _seconds('call_timeout', call_timeout)
+
+    @property
+    def call_timeouts(self) -> dict[str, float | None] | None:
+        """
+        Api entries given a timeout of their own, over `call_timeout`.
+
+            wv.call_timeout = 10
+            wv.call_timeouts = {'export': 300, 'watch': None}
+
+        `None` lifts the limit for an entry, for one that is meant to run for
+        as long as the window is open. A name the Api does not have is refused
+        by `run()`.
+        """
+        return self._call_timeouts
+
+    @call_timeouts.setter
+    def call_timeouts(self, call_timeouts: dict[str, float | None] | None) -> None:
+        self._refuse_late_assignment('call_timeouts')
+        if call_timeouts is None:
+            self._call_timeouts = None
+            return
+        if not isinstance(call_timeouts, dict):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'call_timeouts must be a dict, got {type(call_timeouts).__name__}.'
+            )
+        for name in call_timeouts:
+            if not isinstance(name, str):  # pyright: ignore[reportUnnecessaryIsInstance]
+                raise TypeError(
+                    f'call_timeouts maps Api entry names as str, got {name!r}.'
+                )
+        self._call_timeouts = {
+            name: _seconds(f'call_timeouts[{name!r}]', seconds)
+            for name, seconds in call_timeouts.items()
+        }
+
     @property
     def dev_tools(self) -> bool:
         """
@@ -628,6 +1466,102 @@ class Webview:
             raise TypeError(f'default must be callable, got {type(default).__name__}.')
         self._default = default
 
+    @property
+    def codecs(self) -> tuple[Codec, ...]:
+        """
+        The built-in conversions applied to a value outside the Bridge
+        contract, before the `default` hook is asked about it.
+
+        Each converts in Rust, with no call into Python per value:
+
+            wv.codecs = True                      # all of them
+            wv.codecs = ['datetime', 'decimal']   # only these
+
+        `'datetime'` writes a `datetime`, `date` or `time` as ISO 8601,
+        `'decimal'` a `Decimal` as its string, every digit kept, `'uuid'` and
+        `'path'` a `UUID` or a path as its string, `'enum'` an `Enum` member as
+        its value, and `'dataclass'` a dataclass instance as an object of its
+        fields. Anything they leave still reaches `default`. Off unless asked
+        for, since each is a choice the contract does not make for you.
+        """
+        return self._codecs
+
+    @codecs.setter
+    def codecs(self, codecs: bool | list[Codec]) -> None:
+        self._refuse_late_assignment('codecs')
+        if isinstance(codecs, bool):
+            self._codecs = _CODECS if codecs else ()
+            return
+        if isinstance(codecs, str) or not isinstance(codecs, list | tuple):  # pyright: ignore[reportUnnecessaryIsInstance]
+            raise TypeError(
+                f'codecs must be a bool or a list of codec names, got '
+                f'{type(codecs).__name__}.'
+            )
+        for codec in codecs:
+            if codec not in _CODECS:
+                raise ValueError(
+                    f'{codec!r} is not a codec: expected one of '
+                    f'{", ".join(map(repr, _CODECS))}.'
+                )
+        self._codecs = tuple(codec for codec in _CODECS if codec in codecs)
+
+    @property
+    def object_hook(self) -> Callable[[dict[str, Any]], Any] | None:
+        """
+        The hook called with every object the frontend sends, the inbound
+        counterpart to `default`.
+
+        It has the signature `json.loads` gives it: called with each JSON
+        object once it is a dict, innermost first, and whatever it returns
+        stands in that object's place, so a Call's arguments and an Event's
+        value arrive as your own types:
+
+            def object_hook(value):
+                if value.get('__type') == 'Point':
+                    return Point(value['x'], value['y'])
+                return value
+
+        Return the dict unchanged for anything you do not convert. An
+        exception from it rejects the Call, or drops the Event, with the
+        reason logged. Dry's own window events are not passed through it.
+        """
+        return self._object_hook
+
+    @object_hook.setter
+    def object_hook(self, object_hook: Callable[[dict[str, Any]], Any] | None) -> None:
+        self._refuse_late_assignment('object_hook')
+        if object_hook is not None and not callable(object_hook):  # pyright: ignore[reportUnnecessaryComparison]
+            raise TypeError(
+                f'object_hook must be callable, got {type(object_hook).__name__}.'
+            )
+        self._object_hook = object_hook
+
+    @property
+    def big_integers(self) -> bool:
+        """
+        Whether an int past ±2**53 crosses the Bridge as a JavaScript BigInt.
+
+        A JSON number is a double on the frontend, so by default such an int
+        raises rather than arriving with digits missing. Switched on, it
+        crosses as a BigInt, and a BigInt the frontend sends arrives as an
+        int, in Call arguments, return values and Events alike:
+
+            wv.big_integers = True
+
+            const row = await window.dry.api.fetch_row(9007199254740993n);
+            row.id;  // 9007199254740993n
+
+        An int a JSON number carries still crosses as a number, so a value
+        that may grow past ±2**53, such as a database id, is best read with
+        `BigInt(value)` on the frontend, which takes either.
+        """
+        return self._big_integers
+
+    @big_integers.setter
+    def big_integers(self, big_integers: bool) -> None:
+        self._refuse_late_assignment('big_integers')
+        self._big_integers = big_integers
+
     @property
     def app_id(self) -> str:
         """
@@ -665,12 +1599,13 @@ class Webview:
         self._refuse_late_assignment('user_data_folder')
         self._user_data_folder = str(Path(user_data_folder).expanduser())
 
-    def _content(self) -> tuple[str | None, str | None]:
+    def _content(self) -> tuple[str | None, str | None, dict[str, Any] | None]:
         """
-        Resolve the declared Content into the html and url the Rust side reads.
+        Resolve the declared Content into the html, url and Root the Rust side
+        reads, exactly one of which is set.
 
-        A Root travels as a url under the internal protocol, carrying the
-        directory to serve.
+        A Root travels as the directory to serve and the settings it is served
+        with, which only mean anything for a Root.
         """
         declared = [
             mode
@@ -696,9 +1631,41 @@ class Webview:
             )
 
         if self._root is not None:
-            return None, f'{_ROOT_URL_PREFIX}{self._root.as_posix()}'
+            mounts = (
+                self._root if isinstance(self._root, dict) else {'/': self._root}
+            )
+            return (
+                None,
+                None,
+                {
+                    'transport': self._root_transport,
+                    'origin': self._root_origin,
+                    'secure_origin': self._secure_origin,
+                    'mounts': [
+                        _mount(prefix, location) for prefix, location in mounts.items()
+                    ],
+                    'cache_control': list((self._cache_control or {}).items()),
+                    'headers': list((self._headers or {}).items()),
+                    'header_overrides': [
+                        (pattern, list(headers.items()))
+                        for pattern, headers in (self._header_overrides or {}).items()
+                    ],
+                    'mime_types': list((self._mime_types or {}).items()),
+                    'spa_fallback': self._spa_fallback,
+                    'stream_threshold': self._stream_threshold,
+                    'live_reload': self._live_reload,
+                    'include': self._include or [],
+                    'exclude': self._exclude or [],
+                    'dotfiles': self._dotfiles,
+                    'integrity': (
+                        list(read_manifest(self._integrity).items())
+                        if self._integrity is not None
+                        else None
+                    ),
+                },
+            )
 
-        return self._html, self._url
+        return self._html, self._url, None
 
     @property
     def on_close(self) -> CloseHook | None:
@@ -801,7 +1768,7 @@ class Webview:
         whose callables share state must make that state thread-safe. See
         ADR-0001.
         """
-        html, url = self._content()
+        html, url, root = self._content()
 
         user_data_folder = self.user_data_folder
         Path(user_data_folder).mkdir(parents=True, exist_ok=True)
@@ -823,10 +1790,17 @@ class Webview:
                     'icon_path': self._icon_path,
                     'html': html,
                     'url': url,
+                    'root': root,
+                    'protocols': self._protocols,
                     'api': self._api,
+                    'call_timeout': self._call_timeout,
+                    'call_timeouts': self._call_timeouts or {},
                     'dev_tools': self._dev_tools,
                     'user_data_folder': user_data_folder,
                     'default': self._default,
+                    'codecs': list(self._codecs),
+                    'object_hook': self._object_hook,
+                    'big_integers': self._big_integers,
                 }
             )
         finally:
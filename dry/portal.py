@@ -7,12 +7,26 @@ still for its whole duration: no repaint, no input, no second Call. The portal
 takes every Call off that thread. A coroutine callback is scheduled onto an
 asyncio loop Dry owns on a daemon thread, and a plain callback goes to a thread
 pool. Both answer through the same Completion, which carries the reply back
-across the Bridge.
+across the Bridge. A callback that is a generator, of either kind, streams
+through it instead: each value it yields crosses as it is yielded, and the
+reply that ends the Call comes when the generator does.
+
+A Call the frontend gives up on is cancelled here, by its call id. A coroutine
+is cancelled outright. A thread cannot be interrupted, so a thread-pool
+callable is told instead, through `cancelled()`, and is expected to ask. A Call
+that outlives its timeout is answered with a `TimeoutError` and then cancelled
+the same way.
 
 An Event listener takes the same road, minus the Completion: it is a Bridge
 message that returns nothing, so it is handed over and never answered. What it
 returns is dropped, and what it raises is logged.
 
+So does a protocol handler, the callable answering `app://` requests the page
+makes. It is not a Bridge message — the page's `fetch` reached it, not
+`window.dry` — but it is user code the window must not wait on, and its answer
+goes back through a Responder the same way a Call's goes back through a
+Completion.
+
 Two consequences, recorded in ADR-0001. Dry owns the process, so an application
 cannot make `asyncio.run(main())` its entry point — the developer's async code
 lives inside callbacks, on the loop this module runs. And callbacks now run
@@ -31,23 +45,29 @@ does not need, at the cost of the zero-dependency promise.
 
 from asyncio import (
     AbstractEventLoop,
+    CancelledError,
+    TimerHandle,
     all_tasks,
     current_task,
     gather,
+    get_running_loop,
     new_event_loop,
     run_coroutine_threadsafe,
     set_event_loop,
 )
 from atexit import _run_exitfuncs  # pyright: ignore[reportPrivateUsage]
+from collections.abc import AsyncGenerator, Coroutine, Generator, Mapping
 from concurrent.futures import Future, ThreadPoolExecutor, wait
+from contextvars import ContextVar
 from inspect import isawaitable, iscoroutinefunction
 from logging import getLogger
-from threading import Lock, Thread
-from typing import Any, Awaitable, Callable, Protocol
+from threading import Event, Lock, Thread
+from typing import Any, Awaitable, Callable, NamedTuple, Protocol
 
 from .signature import mismatch
 
 _LOGGER = getLogger('dry.bridge')
+_WEBVIEW_LOGGER = getLogger('dry.webview')
 
 # How long a closing portal waits, at each step, before giving up on it.
 _SHUTDOWN_TIMEOUT = 5.0
@@ -60,12 +80,72 @@ class Completion(Protocol):
     Exactly one of these two lands, exactly once. `resolve` carries a value
     that must be inside the Bridge contract, and raises if it is not, leaving
     the Call unanswered so that the failure can be rejected instead.
+
+    `stream` carries one value a generator yielded, before either of them, and
+    raises as `resolve` does. `expire` rejects as `reject` does, for a Call
+    that ran out of time, and has whatever is answered after it dropped with a
+    warning. `id` is the call id the frontend cancels by.
     """
 
+    @property
+    def id(self) -> str: ...
+
     def resolve(self, value: object, /) -> None: ...
 
+    def stream(self, value: object, /) -> None: ...
+
     def reject(self, error: BaseException, /) -> None: ...
 
+    def expire(self, error: BaseException, /) -> None: ...
+
+
+class Responder(Protocol):
+    """
+    How a protocol request is answered.
+
+    Exactly one of these two lands, exactly once. `respond` raises for a
+    status or a header HTTP cannot carry, leaving the request unanswered so
+    that the failure can answer it instead.
+    """
+
+    def respond(
+        self, status: int, headers: list[tuple[str, str]], body: bytes, /
+    ) -> None: ...
+
+    def fail(self, error: BaseException, /) -> None: ...
+
+
+class ProtocolRequest(NamedTuple):
+    """
+    One request the page made of a protocol the application answers.
+
+    `url` is the whole of it, `app://thumbnails/42.png?size=64`, and `path`
+    the part after the host, `/42.png`. Header names are lowercase, and a
+    header sent twice arrives once, its values joined with commas.
+    """
+
+    method: str
+    url: str
+    path: str
+    headers: dict[str, str]
+    body: bytes
+
+
+ProtocolResponse = tuple[int, Mapping[str, str], bytes | bytearray | memoryview | str]
+"""
+What a protocol handler returns: a status, the response headers, and the body.
+A `str` body is sent as UTF-8.
+"""
+
+ProtocolHandler = Callable[[ProtocolRequest], object]
+"""
+What an application registers for a scheme of its own.
+
+Takes a `ProtocolRequest` and returns a `ProtocolResponse`. A coroutine
+function works too, awaited on Dry's loop. A handler that raises, or returns
+something that is not a response, answers `500` with the exception's name and
+message, and the exception is logged.
+"""
 
 CloseHook = Callable[[], object]
 """
@@ -87,6 +167,22 @@ an Event has no return path, so whatever a listener returns is dropped. A
 coroutine function works too, awaited on Dry's loop.
 """
 
+
+class _Call:
+    """
+    One Call in flight, as a cancellation finds it: whether it has been
+    cancelled, the step it is on, which is the one to cancel, and the timer
+    that expires it, to be stopped once it is answered in time.
+    """
+
+    __slots__ = ('cancelled', 'deadline', 'step')
+
+    def __init__(self) -> None:
+        self.cancelled = Event()
+        self.deadline: TimerHandle | None = None
+        self.step: Future[Any] | None = None
+
+
 _lock = Lock()
 _loop: AbstractEventLoop | None = None
 _thread: Thread | None = None
@@ -99,6 +195,13 @@ _hook: CloseHook | None = None
 # writing its file and one that is cut off mid-write.
 _pending: set[Future[Any]] = set()
 
+# Every Call in flight, by its call id, for a cancellation to find.
+_calls: dict[str, _Call] = {}
+
+# Whether the Call the current thread or task is running for has been
+# cancelled. Unset outside a Call.
+_cancellation: ContextVar[Event | None] = ContextVar('dry_cancellation', default=None)
+
 # Every Python listener, by the name of the Event it is registered for. The
 # lists are kept in registration order and never mutated in place once handed
 # out, so a listener registering or unregistering another from inside its own
@@ -111,15 +214,19 @@ def dispatch(
     function: Callable[..., object],
     arguments: tuple[object, ...],
     completion: Completion,
+    timeout: float | None = None,
 ) -> None:
     """
-    Runs one Call off the event-loop thread and answers it when it finishes.
+    Runs one Call off the event-loop thread and answers it when it finishes,
+    or when `timeout` seconds have passed, whichever comes first.
 
     Returns as soon as the work is handed over, so the caller — the GUI thread
     — is free again. A coroutine function is scheduled onto the loop; anything
     else goes to the thread pool, and if what it returns turns out to be
     awaitable it finishes on the loop too, which is what makes a callable
-    object with an `async def __call__` work like an `async def`.
+    object with an `async def __call__` work like an `async def`. What it
+    returns may be a generator instead, and is then streamed: an async one on
+    the loop, a plain one in the pool, since each step may block.
 
     A Call whose arguments contradict what the callback declared is refused
     here, before any of it runs: see `dry/signature.py`.
@@ -130,6 +237,11 @@ def dispatch(
         return
 
     loop, executor = _running()
+    call = _Call()
+    with _lock:
+        _calls[completion.id] = call
+    if timeout is not None:
+        _ = loop.call_soon_threadsafe(_arm, name, completion, timeout, call)
 
     if iscoroutinefunction(function):
         try:
@@ -140,7 +252,124 @@ def dispatch(
         _on_loop(name, coroutine, loop, completion)
         return
 
-    _answer(name, executor.submit(function, *arguments), loop, completion)
+    step = executor.submit(_within, call.cancelled, function, *arguments)
+    _answer(name, step, loop, completion)
+
+
+def cancel(call_id: str) -> None:
+    """
+    Cancels the Call the frontend gave up on, if it is still running.
+
+    Called by Rust on the thread that owns the window, once the frontend has
+    already rejected the Call's Promise, so nothing here answers anybody: the
+    point is to stop the work. A coroutine, or an async generator, is
+    cancelled, and its `finally` blocks run. A plain callable already running
+    in the pool cannot be stopped from outside; `cancelled()` turns true for
+    it, and a generator is closed before its next step. Cancelling a Call that
+    has finished, or was never made, does nothing.
+    """
+    with _lock:
+        call = _calls.get(call_id)
+    if call is not None:
+        _stop(call)
+
+
+def _stop(call: _Call) -> None:
+    call.cancelled.set()
+    if call.step is not None:
+        _ = call.step.cancel()
+
+
+def _arm(name: str, completion: Completion, timeout: float, call: _Call) -> None:
+    """
+    Starts a Call's timer, on the loop, unless the Call is already answered.
+    """
+    with _lock:
+        if completion.id not in _calls:
+            return
+        call.deadline = get_running_loop().call_later(
+            timeout, _expire, name, completion, timeout
+        )
+
+
+def _expire(name: str, completion: Completion, timeout: float) -> None:
+    """
+    Answers a Call still running when its timer runs out, then stops it.
+
+    The answer comes first: stopping a coroutine answers the Call on the spot
+    with its cancellation, and the frontend is owed the `TimeoutError`. The
+    Call stays on file until the callable does stop, so what it answers then
+    is dropped by the Completion, with a warning, and not taken for a fault.
+    """
+    with _lock:
+        call = _calls.get(completion.id)
+    if call is None:
+        return
+    _LOGGER.warning("The Call to '%s' timed out after %s seconds.", name, timeout)
+    error = TimeoutError(f"The Call to '{name}' timed out after {timeout} seconds.")
+    try:
+        completion.expire(error)
+    except BaseException:
+        _LOGGER.exception("The Call to '%s' could not be answered.", name)
+    _stop(call)
+
+
+def cancelled() -> bool:
+    """
+    Whether the frontend has cancelled the Call this code is running for.
+
+    A long callable in the thread pool cannot be interrupted, so it asks this
+    between units of work and returns early once it is true:
+
+        def export(rows: list[dict[str, object]]) -> int:
+            for count, row in enumerate(rows):
+                if dry.cancelled():
+                    return count
+                write(row)
+            return len(rows)
+
+    A coroutine need not ask: it is cancelled at its next `await`. Outside a
+    Call, this is always False.
+    """
+    event = _cancellation.get()
+    return event is not None and event.is_set()
+
+
+def serve(
+    scheme: str,
+    handler: ProtocolHandler,
+    fields: tuple[str, str, str, list[tuple[str, str]], bytes],
+    responder: Responder,
+) -> None:
+    """
+    Runs one protocol request's handler off the event-loop thread, and answers
+    the request when it finishes.
+
+    Called by Rust on the thread that owns the window, and returns as soon as
+    the handler is handed over, making the same choice `dispatch` makes: a
+    coroutine function onto the loop, anything else into the pool. A request
+    arriving once the portal is closed is answered with the refusal, so the
+    page's `fetch` fails instead of hanging.
+    """
+    method, url, path, headers, body = fields
+    request = ProtocolRequest(method, url, path, dict(headers), body)
+
+    try:
+        loop, executor = _running()
+    except RuntimeError as error:
+        _handler_failed(scheme, url, error, responder)
+        return
+
+    if iscoroutinefunction(handler):
+        try:
+            coroutine = handler(request)
+        except BaseException as error:
+            _handler_failed(scheme, url, error, responder)
+            return
+        _handle_on_loop(scheme, url, coroutine, loop, responder)
+        return
+
+    _handled(scheme, url, executor.submit(handler, request), loop, responder)
 
 
 def listen(name: str, listener: Listener) -> None:
@@ -417,8 +646,9 @@ def _on_loop(
     with the reason instead: a rejection the frontend can see beats a Promise
     that never settles.
     """
+    cancelled = _cancellation_of(completion)
     try:
-        scheduled = run_coroutine_threadsafe(_awaited(awaitable), loop)
+        scheduled = run_coroutine_threadsafe(_awaited(awaitable, cancelled), loop)
     except BaseException as error:
         _reject(name, error, completion)
         return
@@ -426,10 +656,41 @@ def _on_loop(
     _answer(name, scheduled, loop, completion)
 
 
-async def _awaited(awaitable: Awaitable[object]) -> object:
+async def _awaited(
+    awaitable: Awaitable[object], cancelled: Event | None = None
+) -> object:
+    """
+    Awaits on the loop, in a task of its own, which is where a Call's
+    cancellation is set for `cancelled()` to read.
+    """
+    _ = _cancellation.set(cancelled)
     return await awaitable
 
 
+def _within(
+    cancelled: Event, function: Callable[..., object], /, *arguments: object
+) -> object:
+    """
+    Runs a callable on a pool thread with its Call's cancellation set, and
+    takes it away again, since the thread goes on to run other Calls.
+    """
+    token = _cancellation.set(cancelled)
+    try:
+        return function(*arguments)
+    finally:
+        _cancellation.reset(token)
+
+
+def _cancellation_of(completion: Completion) -> Event:
+    """
+    The cancellation of a Call in flight, or one that is never set for a Call
+    no longer tracked.
+    """
+    with _lock:
+        call = _calls.get(completion.id)
+    return call.cancelled if call is not None else Event()
+
+
 def _answer(
     name: str,
     future: Future[Any],
@@ -451,18 +712,107 @@ def _answer(
             _reject(name, error, completion)
             return
 
+        # A pool thread that ran on past its cancellation, having never asked.
+        if _cancellation_of(completion).is_set():
+            _close(value)
+            error = CancelledError('The frontend cancelled the Call.')
+            _reject(name, error, completion)
+            return
+
         if isawaitable(value):
             _on_loop(name, value, loop, completion)
             return
 
+        if isinstance(value, Generator | AsyncGenerator):
+            _stream(name, value, loop, completion)  # pyright: ignore[reportUnknownArgumentType]
+            return
+
         _resolve(name, value, completion)
 
     with _lock:
         _pending.add(future)
+        call = _calls.get(completion.id)
+        if call is not None:
+            call.step = future
+
+    # Cancelled between two steps, with no step yet to cancel.
+    if call is not None and call.cancelled.is_set():
+        _ = future.cancel()
 
     future.add_done_callback(answered)
 
 
+def _close(value: object) -> None:
+    """
+    Closes what a cancelled step returned and nobody will now run: a
+    coroutine or a generator left unstarted would otherwise warn that it was
+    never awaited, or hold what it opened until it is collected.
+    """
+    if isinstance(value, Coroutine | Generator):
+        value.close()
+
+
+def _stream(
+    name: str,
+    generator: Generator[object, None, object] | AsyncGenerator[object, None],
+    loop: AbstractEventLoop,
+    completion: Completion,
+) -> None:
+    """
+    Runs a generator to its end, off the event-loop thread, and answers the
+    Call with what it returns once every value it yielded has been sent.
+    """
+    if isinstance(generator, AsyncGenerator):
+        _on_loop(name, _streamed_async(generator, completion), loop, completion)
+        return
+
+    try:
+        _, executor = _running()
+    except RuntimeError as error:
+        generator.close()
+        _reject(name, error, completion)
+        return
+    cancelled = _cancellation_of(completion)
+    step = executor.submit(_within, cancelled, _streamed, generator, completion)
+    _answer(name, step, loop, completion)
+
+
+def _streamed(
+    generator: Generator[object, None, object], completion: Completion
+) -> object:
+    """
+    Sends each value a generator yields, in order, and returns what it
+    returned. A value outside the Bridge contract ends the generator, and the
+    Call is rejected with the refusal; so does a cancellation, between two
+    steps.
+    """
+    try:
+        while True:
+            if cancelled():
+                raise CancelledError('The frontend cancelled the Call.')
+            try:
+                value = next(generator)
+            except StopIteration as stop:
+                return stop.value
+            completion.stream(value)
+    finally:
+        generator.close()
+
+
+async def _streamed_async(
+    generator: AsyncGenerator[object, None], completion: Completion
+) -> None:
+    """
+    Sends each value an async generator yields, in order. An async generator
+    returns nothing, so neither does this.
+    """
+    try:
+        async for value in generator:
+            completion.stream(value)
+    finally:
+        await generator.aclose()
+
+
 def _resolve(name: str, value: object, completion: Completion) -> None:
     """
     Answers a Call with the value its callable returned.
@@ -481,6 +831,8 @@ def _resolve(name: str, value: object, completion: Completion) -> None:
             exc_info=error,
         )
         _answer_with(name, error, completion)
+        return
+    _end(completion)
 
 
 def _reject(name: str, error: BaseException, completion: Completion) -> None:
@@ -489,7 +841,11 @@ def _reject(name: str, error: BaseException, completion: Completion) -> None:
     travels with it, so JavaScript can tell a ValueError from a
     PermissionError.
     """
-    _LOGGER.error("The Call to '%s' raised.", name, exc_info=error)
+    if _cancellation_of(completion).is_set():
+        # The frontend asked for this, and has already rejected the Promise.
+        _LOGGER.debug("The Call to '%s' was cancelled.", name)
+    else:
+        _LOGGER.error("The Call to '%s' raised.", name, exc_info=error)
     _answer_with(name, error, completion)
 
 
@@ -498,6 +854,23 @@ def _answer_with(name: str, error: BaseException, completion: Completion) -> Non
         completion.reject(error)
     except BaseException:
         _LOGGER.exception("The Call to '%s' could not be answered.", name)
+    _end(completion)
+
+
+def _end(completion: Completion) -> None:
+    """
+    Forgets an answered Call, so a cancellation arriving after it does
+    nothing.
+    """
+    with _lock:
+        call = _calls.pop(completion.id, None)
+    if call is not None and call.deadline is not None and _loop is not None:
+        # Off the loop, where a timer cannot be touched directly. A loop
+        # already closed has no timer left to stop.
+        try:
+            _ = _loop.call_soon_threadsafe(call.deadline.cancel)
+        except RuntimeError:
+            pass
 
 
 def _hand_over(
@@ -574,3 +947,116 @@ def _listener_failed(name: str, error: BaseException) -> None:
     _LOGGER.error(
         "A listener for the Event '%s' raised.", name, exc_info=error
     )
+
+
+def _handle_on_loop(
+    scheme: str,
+    url: str,
+    awaitable: Awaitable[object],
+    loop: AbstractEventLoop,
+    responder: Responder,
+) -> None:
+    try:
+        scheduled = run_coroutine_threadsafe(_awaited(awaitable), loop)
+    except BaseException as error:
+        _handler_failed(scheme, url, error, responder)
+        return
+
+    _handled(scheme, url, scheduled, loop, responder)
+
+
+def _handled(
+    scheme: str,
+    url: str,
+    future: Future[Any],
+    loop: AbstractEventLoop,
+    responder: Responder,
+) -> None:
+    """
+    Watches one step of a protocol handler and answers its request, keeping
+    the step on the list a closing portal waits for.
+    """
+
+    def finished(future: Future[Any]) -> None:
+        with _lock:
+            _pending.discard(future)
+
+        try:
+            value = future.result()
+        except BaseException as error:
+            _handler_failed(scheme, url, error, responder)
+            return
+
+        if isawaitable(value):
+            _handle_on_loop(scheme, url, value, loop, responder)
+            return
+
+        try:
+            responder.respond(*_response(scheme, value))
+        except BaseException as error:
+            _handler_failed(scheme, url, error, responder)
+
+    with _lock:
+        _pending.add(future)
+
+    future.add_done_callback(finished)
+
+
+def _response(
+    scheme: str, value: object
+) -> tuple[int, list[tuple[str, str]], bytes]:
+    """
+    What a handler returned, as the Responder takes it, or the TypeError that
+    says what a handler returns.
+    """
+    try:
+        status, headers, body = value  # pyright: ignore[reportGeneralTypeIssues]
+    except (TypeError, ValueError):
+        raise TypeError(
+            f"The handler for '{scheme}' must return (status, headers, body), "
+            f'got {type(value).__name__}.'
+        ) from None
+
+    if isinstance(status, bool) or not isinstance(status, int):
+        raise TypeError(
+            f"The handler for '{scheme}' returned a status that is not an int: "
+            f'{status!r}.'
+        )
+    if not isinstance(headers, Mapping):
+        raise TypeError(
+            f"The handler for '{scheme}' returned headers that are not a mapping: "
+            f'{type(headers).__name__}.'
+        )
+    pairs: list[tuple[str, str]] = []
+    for name, header in headers.items():  # pyright: ignore[reportUnknownVariableType]
+        if not isinstance(name, str) or not isinstance(header, str):
+            raise TypeError(
+                f"The handler for '{scheme}' returned a header that is not "
+                f'str: str, got {name!r}: {header!r}.'
+            )
+        pairs.append((name, header))
+
+    if isinstance(body, str):
+        return status, pairs, body.encode()
+    if isinstance(body, bytes | bytearray | memoryview):
+        return status, pairs, bytes(body)
+    raise TypeError(
+        f"The handler for '{scheme}' returned a body that is not bytes or str: "
+        f'{type(body).__name__}.'
+    )
+
+
+def _handler_failed(
+    scheme: str, url: str, error: BaseException, responder: Responder
+) -> None:
+    """
+    Records a protocol handler that raised, with its traceback, and answers
+    its request with the failure.
+    """
+    _WEBVIEW_LOGGER.error(
+        "The handler for '%s' raised on %s.", scheme, url, exc_info=error
+    )
+    try:
+        responder.fail(error)
+    except BaseException:
+        _WEBVIEW_LOGGER.exception('The request for %s could not be answered.', url)
@@ -21,7 +21,7 @@ The check is deliberately shallow, and deliberately timid.
 Shallow: arity and the top level of each argument, and no more. `list[int]`
 asks whether an array arrived, not what is in it. Turning a dictionary into a
 dataclass is a validation framework, which is not what this library is; the
-`default=` hook is where a project that wants one puts it.
+`object_hook=` hook is where a project that wants one puts it.
 
 Timid: an annotation this module cannot resolve, or can resolve but cannot
 express in the JSON data model the Bridge contract is made of, leaves its
@@ -42,10 +42,10 @@ from annotationlib import Format
 
 _LOGGER = getLogger('dry.bridge')
 
-# What a value crossing the Bridge can be. The JSON data model of ADR-0002,
-# split one step finer than JSON itself: a JSON number reaches Python as an int
-# or a float, and the difference is exactly what a callback declaring `int` is
-# asking about.
+# What a value crossing the Bridge can be. The JSON data model of ADR-0002 and
+# bytes, split one step finer than JSON itself: a JSON number reaches Python as
+# an int or a float, and the difference is exactly what a callback declaring
+# `int` is asking about.
 _NULL = 'null'
 _BOOLEAN = 'boolean'
 _INTEGER = 'integer'
@@ -53,9 +53,11 @@ _FLOAT = 'float'
 _STRING = 'string'
 _ARRAY = 'array'
 _OBJECT = 'object'
+_BYTES = 'bytes'
 
 # How each of those is named back to the developer. JSON's own vocabulary,
 # because the frontend wrote JSON: an int and a float both arrived as `number`.
+# Bytes are what JSON has no word for, so they take JavaScript's.
 _REPORTED = {
     _NULL: 'null',
     _BOOLEAN: 'boolean',
@@ -64,6 +66,7 @@ _REPORTED = {
     _STRING: 'string',
     _ARRAY: 'array',
     _OBJECT: 'object',
+    _BYTES: 'Uint8Array',
 }
 
 # The annotations this module is willing to check, and what each one accepts.
@@ -86,6 +89,7 @@ _ACCEPTED: dict[object, frozenset[str]] = {
     str: frozenset({_STRING}),
     list: frozenset({_ARRAY}),
     dict: frozenset({_OBJECT}),
+    bytes: frozenset({_BYTES}),
 }
 
 # The parameters a Call could ever fill. A Call carries positional arguments
@@ -301,6 +305,8 @@ def _classify(value: object) -> str | None:
         return _ARRAY
     if isinstance(value, dict):
         return _OBJECT
+    if isinstance(value, bytes):
+        return _BYTES
     return None
 
 
@@ -6,18 +6,33 @@
 //! loop and thread pool, in `dry/portal.py` — which answers it whenever it
 //! finishes. The thread that took the message is the thread that draws the
 //! window, and it gets back to drawing immediately.
+//!
+//! Nor does anything here keep time. A Call's timeout goes to the portal with
+//! it, and the portal answers a Call that runs out of it with a `TimeoutError`
+//! through `Completion::expire`; whatever the callable answers later is
+//! dropped here.
 
 use pyo3::{
   Bound, Py, PyAny, PyErr, PyResult, Python, pyclass, pymethods, types::PyAnyMethods,
 };
-use std::{collections::HashMap, error::Error, sync::Mutex};
+use serde_json::Error as JsonError;
+use std::{
+  collections::HashMap,
+  error::Error,
+  sync::{
+    Mutex, RwLock,
+    atomic::{AtomicBool, Ordering},
+  },
+};
 
 use crate::{
+  binary,
   errors::py_error_message,
   events::{AppEvent, send_to_event_loop},
   logs,
   types::{
-    Call, CallResult, arguments_to_python, call_result_script, from_python, parse_call,
+    Call, CallResult, CallYield, arguments_to_python, call_result_script,
+    call_yield_script, from_python, parse_call,
   },
 };
 
@@ -29,6 +44,43 @@ mod tests;
 /// The module that owns the asyncio loop and the thread pool.
 const PORTAL: &str = "dry.portal";
 
+/// What the frontend puts in front of a cancellation on the wire: the call id
+/// of a Call whose Promise it has already rejected, as a JSON string.
+pub const CANCEL_PREFIX: &str = "dry_cancel:";
+
+static TIMEOUTS: RwLock<Option<Timeouts>> = RwLock::new(None);
+
+/// How long a Call may run before it is answered with a `TimeoutError`, in
+/// seconds: the Webview-wide `call_timeout`, and the Api entries
+/// `call_timeouts` gives a limit of their own. `None` is no limit.
+pub struct Timeouts {
+  default: Option<f64>,
+  entries: HashMap<String, Option<f64>>,
+}
+
+impl Timeouts {
+  pub fn new(default: Option<f64>, entries: HashMap<String, Option<f64>>) -> Self {
+    Timeouts { default, entries }
+  }
+
+  /// The limit for a Call to one Api entry.
+  fn of(&self, function: &str) -> Option<f64> {
+    self.entries.get(function).copied().unwrap_or(self.default)
+  }
+}
+
+/// Installs the timeouts every Call from here on runs under.
+pub fn set_timeouts(timeouts: Timeouts) {
+  if let Ok(mut slot) = TIMEOUTS.write() {
+    *slot = Some(timeouts);
+  }
+}
+
+fn timeout_of(function: &str) -> Option<f64> {
+  let timeouts = TIMEOUTS.read().ok()?;
+  timeouts.as_ref()?.of(function)
+}
+
 /// One Call's right to an answer, handed to Python along with the callable.
 ///
 /// A Promise settles once, so a Completion answers once: the call id is taken
@@ -36,27 +88,59 @@ const PORTAL: &str = "dry.portal";
 /// survives a `resolve` that fails, though — a return value outside the Bridge
 /// contract is refused before the id is taken, so the portal can turn round
 /// and reject the Call with that refusal.
+///
+/// A Call whose callable is a generator is answered in pieces: each value it
+/// yields is streamed ahead of the answer, which still comes once, and ends
+/// the frontend's iteration.
+///
+/// `id` stays readable after the Call is answered: it is what the portal files
+/// the Call under, for a cancellation to find.
+///
+/// A Call that timed out was answered by `expire`, and the callable's own
+/// answer, when it finally comes, finds the id gone like any second answer.
+/// That one is not the portal's doing, though, so it is dropped out loud.
 #[pyclass(frozen)]
 pub struct Completion {
   call_id: Mutex<Option<String>>,
+  #[pyo3(get)]
+  id: String,
+  expired: AtomicBool,
 }
 
 impl Completion {
   pub fn new(call_id: String) -> Self {
     Completion {
+      id: call_id.clone(),
       call_id: Mutex::new(Some(call_id)),
+      expired: AtomicBool::new(false),
     }
   }
 
   /// Takes the right to answer, or `None` if this Call is already answered.
   fn claim(&self) -> Option<String> {
-    self.call_id.lock().ok().and_then(|mut slot| slot.take())
+    let claimed = self.call_id.lock().ok().and_then(|mut slot| slot.take());
+    if claimed.is_none() && self.expired.load(Ordering::Relaxed) {
+      logs::warning(
+        logs::BRIDGE,
+        format!(
+          "The Call '{}' was answered after it timed out. The answer is dropped.",
+          self.id
+        ),
+      );
+    }
+    claimed
+  }
+
+  /// The right to answer, left where it is: a yielded value is sent while
+  /// the Call is still owed its answer.
+  fn owed(&self) -> Option<String> {
+    self.call_id.lock().ok().and_then(|slot| slot.clone())
   }
 
   /// A reply that cannot be sent leaves a Promise pending, and there is
   /// nothing further to do about it but say so.
-  fn send(&self, result: CallResult) {
-    let script = match call_result_script(&result) {
+  fn send(&self, script: Result<String, JsonError>) {
+    let script = match script {
       Ok(script) => script,
       Err(err) => {
         logs::error(
@@ -80,13 +164,26 @@ impl Completion {
   /// Answers with the value the callable returned. Raises if that value is
   /// outside the Bridge contract, leaving the Call unanswered.
   fn resolve(&self, value: &Bound<'_, PyAny>) -> PyResult<()> {
-    let result = from_python(value)?;
+    let mut result = from_python(value)?;
     if let Some(call_id) = self.claim() {
-      self.send(CallResult {
+      binary::set_aside_within(&mut result);
+      self.send(call_result_script(&CallResult {
         call_id,
         result,
         error: None,
-      });
+      }));
+    }
+    Ok(())
+  }
+
+  /// Sends one value a streaming Call yielded, ahead of the answer that ends
+  /// it. Raises if that value is outside the Bridge contract, as `resolve`
+  /// does; a Call already answered sends nothing.
+  fn stream(&self, value: &Bound<'_, PyAny>) -> PyResult<()> {
+    let mut value = from_python(value)?;
+    if let Some(call_id) = self.owed() {
+      binary::set_aside_within(&mut value);
+      self.send(call_yield_script(&CallYield { call_id, value }));
     }
     Ok(())
   }
@@ -96,9 +193,17 @@ impl Completion {
   fn reject(&self, error: &Bound<'_, PyAny>) {
     if let Some(call_id) = self.claim() {
       let message = py_error_message(error.py(), &PyErr::from_value(error.clone()));
-      self.send(CallResult::failed(call_id, message));
+      self.send(call_result_script(&CallResult::failed(call_id, message)));
     }
   }
+
+  /// Answers a Call that ran out of time with the `TimeoutError` the portal
+  /// made for it, as `reject` does. The callable may still be running, and
+  /// what it answers when it stops is dropped with a warning.
+  fn expire(&self, error: &Bound<'_, PyAny>) {
+    self.expired.store(true, Ordering::Relaxed);
+    self.reject(error);
+  }
 }
 
 /// Reads one message off the Bridge and starts the Call it carries.
@@ -122,6 +227,35 @@ pub fn handle_api_requests(
   Ok(())
 }
 
+/// Reads one cancellation off the Bridge and hands it to the portal, which
+/// stops the Call if it is still running. The frontend has settled the Promise
+/// already, so there is no one to tell when this fails but the log.
+pub fn handle_cancel_request(body: &str) {
+  let call_id = match serde_json::from_str::<String>(body) {
+    Ok(call_id) => call_id,
+    Err(err) => {
+      logs::error(
+        logs::BRIDGE,
+        format!("The cancellation could not be read: {err}"),
+      );
+      return;
+    },
+  };
+
+  let cancelled = Python::attach(|py| {
+    py.import(PORTAL)
+      .and_then(|portal| portal.call_method1("cancel", (&call_id,)))
+      .map(|_| ())
+      .map_err(|err| py_error_message(py, &err))
+  });
+  if let Err(reason) = cancelled {
+    logs::error(
+      logs::BRIDGE,
+      format!("The Call '{call_id}' could not be cancelled: {reason}"),
+    );
+  }
+}
+
 /// Hands a Call to the portal and returns. The GIL is held for the lookup and
 /// the argument conversion only; the callable itself runs on a thread of the
 /// portal's choosing, long after this has returned.
@@ -134,9 +268,10 @@ fn dispatch_call(call: &Call, api: &HashMap<String, Py<PyAny>>) -> Result<(), St
     let start = || -> PyResult<()> {
       let arguments = arguments_to_python(py, &call.arguments)?;
       let completion = Py::new(py, Completion::new(call.call_id.clone()))?;
+      let timeout = timeout_of(&call.function);
       py.import(PORTAL)?.call_method1(
         "dispatch",
-        (&call.function, function, arguments, completion),
+        (&call.function, function, arguments, completion, timeout),
       )?;
       Ok(())
     };
@@ -12,11 +12,12 @@ use pyo3::{
   types::{PyAnyMethods, PyDict, PyDictMethods, PyModule},
 };
 use std::{
+  collections::HashMap,
   ffi::{CStr, CString},
   sync::atomic::{AtomicUsize, Ordering},
 };
 
-use super::Completion;
+use super::{Completion, Timeouts};
 
 /// Dry's own logger, kept quiet: a test that deliberately fails a Call would
 /// otherwise print the record through logging's last-resort handler.
@@ -72,6 +73,22 @@ fn a_value_outside_the_bridge_contract_leaves_the_call_unanswered() {
   });
 }
 
+#[test]
+fn a_yielded_value_leaves_the_call_owed_its_answer() {
+  Python::attach(|py| {
+    silence_logs(py);
+    let completion = Completion::new("call-5".to_string());
+    completion
+      .stream(&evaluate(py, c"[1, 2, 3]"))
+      .expect("a value inside the Bridge contract should be accepted");
+    assert!(
+      completion.stream(&evaluate(py, c"{1, 2}")).is_err(),
+      "a set should be refused"
+    );
+    assert_eq!(completion.claim(), Some("call-5".to_string()));
+  });
+}
+
 #[test]
 fn an_exception_answers_the_call() {
   Python::attach(|py| {
@@ -82,27 +99,59 @@ fn an_exception_answers_the_call() {
   });
 }
 
+#[test]
+fn an_entry_with_a_timeout_of_its_own_overrides_the_default() {
+  let timeouts = Timeouts::new(
+    Some(10.0),
+    HashMap::from([
+      ("export".to_string(), Some(300.0)),
+      ("watch".to_string(), None),
+    ]),
+  );
+  assert_eq!(timeouts.of("export"), Some(300.0));
+  assert_eq!(timeouts.of("watch"), None, "an entry may lift the limit");
+  assert_eq!(timeouts.of("total"), Some(10.0));
+}
+
 /// What every portal test has in front of it: a stand-in for the Completion
-/// that records the answer and lets the test wait for it.
+/// that records the answer and lets the test wait for it. Like the real one,
+/// it takes the first answer, and keeps any after it apart as late.
 const HARNESS: &str = r#"
-import asyncio, logging, threading, time
+import asyncio, itertools, logging, threading, time
 
 logging.getLogger('dry').addHandler(logging.NullHandler())
 
+ids = itertools.count()
+
 class Answer:
     def __init__(self):
+        self.id = f'call-{next(ids)}'
         self.done = threading.Event()
         self.value = None
         self.error = None
+        self.streamed = []
+        self.late = []
+
+    def stream(self, value):
+        self.streamed.append(value)
 
     def resolve(self, value):
+        if self.done.is_set():
+            self.late.append(value)
+            return
         self.value = value
         self.done.set()
 
     def reject(self, error):
+        if self.done.is_set():
+            self.late.append(error)
+            return
         self.error = error
         self.done.set()
 
+    def expire(self, error):
+        self.reject(error)
+
     def wait(self):
         assert self.done.wait(10), 'the Call was never answered'
         return self
@@ -333,6 +382,257 @@ verdict = f'{type(error).__name__}: {error}'
   );
 }
 
+#[test]
+fn a_generator_streams_what_it_yields_and_answers_with_what_it_returns() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+def rows(count):
+    for row in range(count):
+        yield row
+    return 'done'
+
+answer = Answer()
+portal.dispatch('rows', rows, (3,), answer)
+answer.wait()
+verdict = repr((answer.streamed, answer.value))
+"#
+    ),
+    "([0, 1, 2], 'done')"
+  );
+}
+
+#[test]
+fn an_async_generator_streams_on_the_loop() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+async def rows(count):
+    for row in range(count):
+        await asyncio.sleep(0.01)
+        yield row
+
+answer = Answer()
+portal.dispatch('rows', rows, (3,), answer)
+answer.wait()
+verdict = repr((answer.streamed, answer.value))
+"#
+    ),
+    "([0, 1, 2], None)"
+  );
+}
+
+#[test]
+fn a_generator_that_raises_rejects_after_what_it_yielded() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+def rows():
+    yield 'first'
+    raise PermissionError('denied')
+
+answer = Answer()
+portal.dispatch('rows', rows, (), answer)
+error = answer.wait().error
+verdict = f'{answer.streamed} {type(error).__name__}: {error}'
+"#
+    ),
+    "['first'] PermissionError: denied"
+  );
+}
+
+#[test]
+fn a_cancelled_coroutine_is_cancelled_at_its_next_await() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+started, cleaned = threading.Event(), threading.Event()
+
+async def wait_forever():
+    started.set()
+    try:
+        await asyncio.sleep(60)
+    finally:
+        cleaned.set()
+
+answer = Answer()
+portal.dispatch('wait_forever', wait_forever, (), answer)
+assert started.wait(10)
+portal.cancel(answer.id)
+error = answer.wait().error
+verdict = f'{type(error).__name__} {cleaned.wait(10)}'
+"#
+    ),
+    "CancelledError True"
+  );
+}
+
+/// A thread cannot be interrupted, so a callable in the pool is told and
+/// stops itself; what it returns after that is not the Call's answer.
+#[test]
+fn a_cancelled_callable_in_the_pool_is_told() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+started = threading.Event()
+
+def count():
+    started.set()
+    done = 0
+    while not portal.cancelled():
+        done += 1
+        time.sleep(0.01)
+    return done
+
+answer = Answer()
+portal.dispatch('count', count, (), answer)
+assert started.wait(10)
+portal.cancel(answer.id)
+error = answer.wait().error
+verdict = f'{type(error).__name__} {portal.cancelled()}'
+"#
+    ),
+    "CancelledError False"
+  );
+}
+
+#[test]
+fn a_cancelled_generator_is_closed_before_its_next_step() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+answer = Answer()
+closed = threading.Event()
+
+def rows():
+    try:
+        yield 'first'
+        portal.cancel(answer.id)
+        yield 'second'
+        yield 'third'
+    finally:
+        closed.set()
+
+portal.dispatch('rows', rows, (), answer)
+error = answer.wait().error
+verdict = f'{answer.streamed} {type(error).__name__} {closed.is_set()}'
+"#
+    ),
+    "['first', 'second'] CancelledError True"
+  );
+}
+
+/// A cancellation that arrives after the answer finds nothing to stop.
+#[test]
+fn cancelling_an_answered_call_does_nothing() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+answer = Answer()
+portal.dispatch('name', lambda: 'answered', (), answer)
+answer.wait()
+portal.cancel(answer.id)
+portal.cancel('never-made')
+verdict = f'{answer.value} {answer.id in portal._calls}'
+"#
+    ),
+    "answered False"
+  );
+}
+
+#[test]
+fn a_call_that_outlives_its_timeout_is_rejected_and_cancelled() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+cleaned = threading.Event()
+
+async def wait_forever():
+    try:
+        await asyncio.sleep(60)
+    finally:
+        cleaned.set()
+
+answer = Answer()
+portal.dispatch('wait_forever', wait_forever, (), answer, 0.05)
+error = answer.wait().error
+verdict = f'{type(error).__name__}: {error} {cleaned.wait(10)}'
+"#
+    ),
+    "TimeoutError: The Call to 'wait_forever' timed out after 0.05 seconds. True"
+  );
+}
+
+/// A timeout is the limit doing its job, not a fault in the callable, so it is
+/// a warning rather than an error.
+#[test]
+fn a_timeout_is_logged_as_a_warning() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+levels = []
+
+class Levels(logging.Handler):
+    def emit(self, record):
+        levels.append(record.levelname)
+
+logging.getLogger('dry.bridge').addHandler(Levels())
+
+async def wait_forever():
+    await asyncio.sleep(60)
+
+answer = Answer()
+portal.dispatch('wait_forever', wait_forever, (), answer, 0.05)
+answer.wait()
+verdict = levels[0]
+"#
+    ),
+    "WARNING"
+  );
+}
+
+/// A callable in the pool that stops once it is told answers after all, and
+/// that answer is the late one the Completion drops.
+#[test]
+fn a_callable_in_the_pool_past_its_timeout_answers_late() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+def count():
+    while not portal.cancelled():
+        time.sleep(0.01)
+    return 'too late'
+
+answer = Answer()
+portal.dispatch('count', count, (), answer, 0.05)
+error = answer.wait().error
+for _ in range(1000):
+    if answer.late:
+        break
+    time.sleep(0.01)
+verdict = f'{type(error).__name__} {len(answer.late)}'
+"#
+    ),
+    "TimeoutError 1"
+  );
+}
+
+#[test]
+fn a_call_answered_in_time_is_not_expired() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+answer = Answer()
+portal.dispatch('name', lambda: 'answered', (), answer, 0.05)
+answer.wait()
+time.sleep(0.2)
+verdict = f'{answer.value} {answer.late}'
+"#
+    ),
+    "answered []"
+  );
+}
+
 /// The refusal comes from `dry/signature.py`, reached by the relative import
 /// the portal is loaded inside its package for: the wording is `mismatch`'s
 /// own, so a Call refused before it ran is what this asserts, not a callback
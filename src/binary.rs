@@ -0,0 +1,278 @@
+//! Binary values on the Bridge, and the side channel that carries them.
+//!
+//! JSON has no bytes, and the Bridge is text in both directions: a Call
+//! arrives as a string the page posted, and its answer leaves as a script the
+//! Webview evaluates. So a binary value crosses as an object of its own
+//! shape:
+//!
+//! - `{"__dry_bytes__": "<base64>"}`, the bytes written into the message;
+//! - `{"__dry_bytes_at__": "<id>"}`, the bytes held here and carried over
+//!   HTTP exactly as they are, with nothing added.
+//!
+//! The second needs an origin Dry answers, which the page has when it was
+//! served from a Root, over either transport. It is only worth a request for
+//! a value large enough that a third more to carry costs more than the round
+//! trip. Everywhere else, base64 is what a text channel allows.
+//!
+//! `types` writes every binary value as base64, with no side effects. Which
+//! values go aside instead is settled here, by `set_aside_within`, once, as a
+//! Call's answer or an Event is built to be sent: a value written out for any
+//! other reason holds nothing and takes no room.
+//!
+//! A value is held until it is fetched, once, or until it has waited `EXPIRY`
+//! for a page that navigated away. Room is bounded, and when it runs out a
+//! value is written into the message instead.
+
+use std::{
+  borrow::Cow,
+  sync::{
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+  },
+  time::{Duration, Instant},
+};
+use wry::http::{
+  Method, Request, Response,
+  header::{CACHE_CONTROL, CONTENT_TYPE},
+};
+
+use crate::{logs, loopback, types::PythonType};
+
+pub const BINARY_JS: &str = include_str!("js/binary.js");
+
+#[cfg(test)]
+mod tests;
+
+/// The key of a binary value written into the message, as base64.
+pub const INLINE: &str = "__dry_bytes__";
+
+/// The key of a binary value held aside, by the id it is fetched with.
+pub const ASIDE: &str = "__dry_bytes_at__";
+
+/// Where on the page's origin the side channel answers. Nothing in a Root is
+/// reachable beneath it.
+pub const PATH: &str = "/__dry/bytes";
+
+/// The smallest value worth a request of its own. `binary.js` holds the same
+/// figure for the other direction.
+const MINIMUM: usize = 8 * 1024;
+
+/// The most the side channel holds at once, in both directions together.
+pub const ROOM: usize = 64 * 1024 * 1024;
+
+/// How long a value waits to be fetched before its room is taken back.
+const EXPIRY: Duration = Duration::from_secs(60);
+
+const OCTETS: &str = "application/octet-stream";
+const TEXT: &str = "text/plain; charset=utf-8";
+
+const ALPHABET: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Whether the page can reach the side channel at all.
+static OFFERED: AtomicBool = AtomicBool::new(false);
+
+static HELD: Mutex<Vec<Held>> = Mutex::new(Vec::new());
+
+struct Held {
+  id: String,
+  since: Instant,
+  bytes: Vec<u8>,
+}
+
+/// Opens the side channel for a page served at `origin`, and returns the
+/// script that tells the page where to find it. A page anywhere else writes
+/// its bytes into the message, so nothing it sends leaves for a server Dry
+/// does not run.
+pub fn offer(origin: &str) -> String {
+  OFFERED.store(true, Ordering::Relaxed);
+  let origin = serde_json::to_string(origin).expect("a string always serializes");
+  format!(
+    "Object.defineProperty(window.dry, 'binaryOrigin', {{ value: {origin}, \
+     writable: false, configurable: false, enumerable: false }});"
+  )
+}
+
+/// Holds bytes to be fetched, and returns the id to fetch them by, or `None`
+/// when they are better written into the message.
+fn set_aside(bytes: &[u8]) -> Option<String> {
+  if !OFFERED.load(Ordering::Relaxed) || bytes.len() < MINIMUM {
+    return None;
+  }
+  hold(bytes.to_vec())
+}
+
+/// Holds aside every binary value in a message about to be sent that is worth
+/// a request of its own, and leaves in its place the object the page fetches
+/// it by. The rest stay as they are, to be written in as base64.
+pub fn set_aside_within(value: &mut PythonType) {
+  match value {
+    PythonType::Bytes(bytes) => {
+      if let Some(id) = set_aside(bytes) {
+        *value = PythonType::Object(vec![(ASIDE.to_string(), PythonType::String(id))]);
+      }
+    },
+    PythonType::Array(items) => items.iter_mut().for_each(set_aside_within),
+    PythonType::Object(entries) => {
+      entries
+        .iter_mut()
+        .for_each(|(_, item)| set_aside_within(item));
+    },
+    _ => {},
+  }
+}
+
+/// Takes the bytes held under an id. A value is fetched once, so a second
+/// take finds nothing.
+pub fn take(id: &str) -> Option<Vec<u8>> {
+  let mut held = HELD.lock().ok()?;
+  let at = held.iter().position(|entry| entry.id == id)?;
+  Some(held.swap_remove(at).bytes)
+}
+
+fn hold(bytes: Vec<u8>) -> Option<String> {
+  let mut held = HELD.lock().ok()?;
+  held.retain(|entry| entry.since.elapsed() < EXPIRY);
+  let holding: usize = held.iter().map(|entry| entry.bytes.len()).sum();
+  if holding + bytes.len() > ROOM {
+    return None;
+  }
+  let id = loopback::token().ok()?;
+  held.push(Held {
+    id: id.clone(),
+    since: Instant::now(),
+    bytes,
+  });
+  Some(id)
+}
+
+/// Answers a request on the side channel, and logs it as a Root's requests
+/// are logged, or returns `None` when the request is for something else.
+///
+/// `GET /__dry/bytes/<id>` hands over a value Python sent. `POST
+/// /__dry/bytes?length=<n>` holds one the page is about to send, and answers
+/// with its id; the length is checked against the body, since a webview that
+/// drops a custom protocol's request body would otherwise hand over nothing
+/// and call it a value.
+pub fn answer(request: &Request<Vec<u8>>) -> Option<Response<Cow<'static, [u8]>>> {
+  let path = request.uri().path();
+  let rest = path.strip_prefix(PATH)?;
+  if !rest.is_empty() && !rest.starts_with('/') {
+    return None;
+  }
+
+  let started = Instant::now();
+  let response = match (request.method(), rest.strip_prefix('/')) {
+    (&Method::GET, Some(id)) => match take(id) {
+      Some(bytes) => respond(200, OCTETS, Cow::Owned(bytes)),
+      None => respond(404, TEXT, Cow::Borrowed(b"No value is held under this id.")),
+    },
+    (&Method::POST, None) => receive(request),
+    _ => respond(
+      405,
+      TEXT,
+      Cow::Borrowed(b"The side channel takes a POST and answers a GET."),
+    ),
+  };
+  logs::request(
+    request.method().as_str(),
+    path,
+    None,
+    response.status().as_u16(),
+    response.body().len(),
+    started.elapsed(),
+  );
+  Some(response)
+}
+
+fn receive(request: &Request<Vec<u8>>) -> Response<Cow<'static, [u8]>> {
+  let declared = request
+    .uri()
+    .query()
+    .and_then(|query| {
+      query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("length="))
+    })
+    .and_then(|length| length.parse::<usize>().ok());
+  if declared != Some(request.body().len()) {
+    return respond(
+      400,
+      TEXT,
+      Cow::Borrowed(b"The body is not the length it was declared at."),
+    );
+  }
+  match hold(request.body().clone()) {
+    Some(id) => respond(201, TEXT, Cow::Owned(id.into_bytes())),
+    None => respond(
+      507,
+      TEXT,
+      Cow::Borrowed(b"There is no room to hold another value."),
+    ),
+  }
+}
+
+fn respond(
+  status: u16, content_type: &str, body: Cow<'static, [u8]>,
+) -> Response<Cow<'static, [u8]>> {
+  Response::builder()
+    .status(status)
+    .header(CONTENT_TYPE, content_type)
+    .header(CACHE_CONTROL, "no-store")
+    .body(body)
+    .expect("Failed to build response")
+}
+
+/// Standard base64, padded, as `btoa` writes it.
+pub fn encode(bytes: &[u8]) -> String {
+  let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+  for chunk in bytes.chunks(3) {
+    let word = chunk.iter().enumerate().fold(0u32, |word, (at, byte)| {
+      word | (u32::from(*byte) << (16 - 8 * at))
+    });
+    for at in 0..4 {
+      if at <= chunk.len() {
+        text.push(ALPHABET[((word >> (18 - 6 * at)) & 0x3f) as usize] as char);
+      } else {
+        text.push('=');
+      }
+    }
+  }
+  text
+}
+
+/// Reads standard base64, padded, as `btoa` writes it, or `None` for anything
+/// else.
+pub fn decode(text: &str) -> Option<Vec<u8>> {
+  let text = text.as_bytes();
+  if !text.len().is_multiple_of(4) {
+    return None;
+  }
+  let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+  let quads = text.len() / 4;
+  for (index, quad) in text.chunks_exact(4).enumerate() {
+    let padding = quad.iter().rev().take_while(|&&c| c == b'=').count();
+    if padding > 2 || (padding > 0 && index + 1 != quads) {
+      return None;
+    }
+    let mut word = 0u32;
+    for (at, c) in quad[..4 - padding].iter().enumerate() {
+      word |= sextet(*c)? << (18 - 6 * at);
+    }
+    bytes.extend_from_slice(&word.to_be_bytes()[1..4 - padding]);
+  }
+  Some(bytes)
+}
+
+/// The six bits a base64 character stands for.
+fn sextet(c: u8) -> Option<u32> {
+  let value = match c {
+    b'A'..=b'Z' => c - b'A',
+    b'a'..=b'z' => c - b'a' + 26,
+    b'0'..=b'9' => c - b'0' + 52,
+    b'+' => 62,
+    b'/' => 63,
+    _ => return None,
+  };
+  Some(u32::from(value))
+}
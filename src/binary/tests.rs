@@ -0,0 +1,142 @@
+//! Tests for the side channel and the base64 a binary value is otherwise
+//! written in.
+//!
+//! Nothing here opens a window. A request is built by hand and answered the
+//! way the Root's protocol and the loopback server answer one.
+
+use super::*;
+
+fn request(method: Method, uri: &str, body: &[u8]) -> Request<Vec<u8>> {
+  Request::builder()
+    .method(method)
+    .uri(uri)
+    .body(body.to_vec())
+    .expect("the request should build")
+}
+
+/// Sends a body aside as the page does, and returns the id it was held under.
+fn sent_aside(body: &[u8]) -> String {
+  let uri = format!("{PATH}?length={}", body.len());
+  let response = answer(&request(Method::POST, &uri, body)).expect("the path is ours");
+  assert_eq!(response.status(), 201);
+  String::from_utf8(response.body().to_vec()).expect("an id is text")
+}
+
+#[test]
+fn base64_is_written_as_btoa_writes_it() {
+  assert_eq!(encode(b""), "");
+  assert_eq!(encode(b"f"), "Zg==");
+  assert_eq!(encode(b"fo"), "Zm8=");
+  assert_eq!(encode(b"foo"), "Zm9v");
+  assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+  assert_eq!(encode(&[0xff, 0xfe, 0x00]), "//4A");
+}
+
+#[test]
+fn base64_reads_back_every_byte() {
+  let bytes: Vec<u8> = (0..=255).cycle().take(1000).collect();
+  for length in [0, 1, 2, 3, 4, 999, 1000] {
+    assert_eq!(
+      decode(&encode(&bytes[..length])),
+      Some(bytes[..length].to_vec())
+    );
+  }
+}
+
+#[test]
+fn what_is_not_base64_is_refused() {
+  assert_eq!(decode("Zg="), None);
+  assert_eq!(decode("Z==="), None);
+  assert_eq!(decode("Zg==Zm8="), None);
+  assert_eq!(decode("Zm9-"), None);
+  assert_eq!(decode("Zm 9"), None);
+}
+
+#[test]
+fn a_value_sent_aside_is_fetched_once() {
+  let id = sent_aside(b"\x00\x01\x02");
+
+  let fetched = answer(&request(Method::GET, &format!("{PATH}/{id}"), b""))
+    .expect("the path is ours");
+  assert_eq!(fetched.status(), 200);
+  assert_eq!(fetched.headers()[CONTENT_TYPE], OCTETS);
+  assert_eq!(fetched.body().as_ref(), b"\x00\x01\x02");
+
+  let again = answer(&request(Method::GET, &format!("{PATH}/{id}"), b""))
+    .expect("the path is ours");
+  assert_eq!(again.status(), 404);
+}
+
+#[test]
+fn a_body_that_is_not_its_declared_length_is_refused() {
+  // What a webview that drops a custom protocol's request body would send.
+  let response = answer(&request(Method::POST, &format!("{PATH}?length=3"), b""))
+    .expect("the path is ours");
+  assert_eq!(response.status(), 400);
+
+  let response =
+    answer(&request(Method::POST, PATH, b"abc")).expect("the path is ours");
+  assert_eq!(response.status(), 400);
+}
+
+#[test]
+fn only_the_side_channel_is_answered() {
+  assert!(answer(&request(Method::GET, "/index.html", b"")).is_none());
+  assert!(answer(&request(Method::GET, "/__dry/bytesmith.js", b"")).is_none());
+
+  let response =
+    answer(&request(Method::DELETE, &format!("{PATH}/x"), b"")).expect("ours");
+  assert_eq!(response.status(), 405);
+}
+
+#[test]
+fn a_value_taken_is_the_value_held() {
+  let id = sent_aside(b"held");
+  assert_eq!(take(&id), Some(b"held".to_vec()));
+  assert_eq!(take(&id), None);
+}
+
+/// A large value in a message being sent goes aside, wherever it sits, and a
+/// small one stays to be written in.
+#[test]
+fn a_message_being_sent_sets_its_large_values_aside() {
+  offer("dry://localhost");
+  let large = vec![7; MINIMUM];
+  let mut value = PythonType::Array(vec![
+    PythonType::Object(vec![("data".into(), PythonType::Bytes(large.clone()))]),
+    PythonType::Bytes(b"small".to_vec()),
+  ]);
+
+  set_aside_within(&mut value);
+
+  let PythonType::Array(items) = &value else {
+    panic!("the array should stay an array: {value:?}");
+  };
+  let PythonType::Object(entries) = &items[0] else {
+    panic!("the object should stay an object: {value:?}");
+  };
+  let PythonType::Object(held) = &entries[0].1 else {
+    panic!("the large value should be set aside: {value:?}");
+  };
+  let [(key, PythonType::String(id))] = held.as_slice() else {
+    panic!("the large value should be named by its id: {value:?}");
+  };
+  assert_eq!(key, ASIDE);
+  assert_eq!(take(id), Some(large));
+  assert_eq!(items[1], PythonType::Bytes(b"small".to_vec()));
+}
+
+/// Writing a value out is only that: a large one is written in, and nothing
+/// is held or takes room for it.
+#[test]
+fn writing_a_value_out_holds_nothing() {
+  offer("dry://localhost");
+  let large = vec![9; MINIMUM + 1];
+
+  let json = crate::types::to_json(&PythonType::Bytes(large.clone()))
+    .expect("the value should be written");
+
+  assert!(json.starts_with(&format!("{{\"{INLINE}\":")), "{json}");
+  let held = HELD.lock().expect("the store should not be poisoned");
+  assert!(!held.iter().any(|entry| entry.bytes == large));
+}
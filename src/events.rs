@@ -25,9 +25,10 @@ use tao::{
 use wry::WebView;
 
 use crate::{
+  binary,
   errors::BridgeError,
   logs,
-  types::{PythonType, from_python, to_python},
+  types::{PythonType, decode, from_python, to_python},
   window::{self, resize, state},
 };
 
@@ -155,10 +156,11 @@ pub fn eval_js(script: &str) -> PyResult<()> {
 
 /// Hands an Event to the frontend's listeners.
 pub fn deliver_to_frontend(name: &str, value: &PythonType) -> Result<(), String> {
-  let event = BridgeEvent {
+  let mut event = BridgeEvent {
     name: name.to_string(),
     value: value.clone(),
   };
+  binary::set_aside_within(&mut event.value);
   let script = event_script(&event)
     .map_err(|err| format!("The Event could not be written: {err}"))?;
   send_to_event_loop(AppEvent::RunJavascript(script))
@@ -169,10 +171,24 @@ pub fn deliver_to_frontend(name: &str, value: &PythonType) -> Result<(), String>
 /// Called on the thread that draws the window, and returns as soon as the
 /// portal has taken the listeners off it. Nothing here can fail in a way the
 /// sender could act on — an Event has no sender waiting — so a failure is
-/// logged and dropped.
+/// logged and dropped. The value is one Dry made, so it is handed over as it
+/// was made, past the `object_hook=` hook.
 pub fn deliver_to_python(name: &str, value: &PythonType) {
+  deliver(name, value, to_python);
+}
+
+/// Hands an Event the frontend emitted to Python's listeners, its objects
+/// written through the `object_hook=` hook as a Call's arguments are.
+fn deliver_from_frontend(name: &str, value: &PythonType) {
+  deliver(name, value, decode);
+}
+
+/// How an Event's value becomes the Python object its listeners are handed.
+type Writer = for<'py> fn(Python<'py>, &PythonType) -> PyResult<Bound<'py, PyAny>>;
+
+fn deliver(name: &str, value: &PythonType, writer: Writer) {
   Python::attach(|py| match py.import(PORTAL) {
-    Ok(portal) => deliver_through(&portal, name, value),
+    Ok(portal) => deliver_through(&portal, name, value, writer),
     Err(err) => {
       logs::error(
         logs::BRIDGE,
@@ -183,9 +199,11 @@ pub fn deliver_to_python(name: &str, value: &PythonType) {
 }
 
 /// The half of `deliver_to_python` a test can hold a portal up to.
-fn deliver_through(portal: &Bound<'_, PyModule>, name: &str, value: &PythonType) {
+fn deliver_through(
+  portal: &Bound<'_, PyModule>, name: &str, value: &PythonType, writer: Writer,
+) {
   let deliver = || -> PyResult<()> {
-    let value = to_python(portal.py(), value)?;
+    let value = writer(portal.py(), value)?;
     portal.call_method1("deliver", (name, value))?;
     Ok(())
   };
@@ -223,10 +241,11 @@ pub fn emit_reserved(name: &str, value: PythonType) {
 /// holds the WebView on the thread that draws it evaluates the script here and
 /// now, and the frontend hears the request before the close hook is asked.
 pub fn emit_reserved_now(webview: &WebView, name: &str, value: PythonType) {
-  let event = BridgeEvent {
+  let mut event = BridgeEvent {
     name: name.to_string(),
-    value,
+    value: value.clone(),
   };
+  binary::set_aside_within(&mut event.value);
   match event_script(&event) {
     Ok(script) => run_javascript(webview, &script),
     Err(err) => logs::error(
@@ -234,7 +253,7 @@ pub fn emit_reserved_now(webview: &WebView, name: &str, value: PythonType) {
       format!("The Event '{name}' could not be written: {err}"),
     ),
   }
-  deliver_to_python(name, &event.value);
+  deliver_to_python(name, &value);
 }
 
 /// Reads one Event off the Bridge and hands it to Python.
@@ -255,7 +274,7 @@ pub fn handle_event_request(body: &str) {
     return;
   }
 
-  deliver_to_python(&event.name, &event.value);
+  deliver_from_frontend(&event.name, &event.value);
 }
 
 pub fn run_event_loop(
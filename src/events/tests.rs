@@ -20,7 +20,7 @@ use super::{
   BridgeEvent, allowed_by, deliver_through, emittable, event_script, parse_event,
   shut_down_through,
 };
-use crate::types::PythonType;
+use crate::types::{PythonType, decode};
 
 /// What every close test has in front of it: a stand-in for the Completion,
 /// and Dry's own logger kept quiet so that a deliberately raising hook does
@@ -431,7 +431,7 @@ fn through_the_bus(
       .expect("the test setup should run");
 
     for (event, value) in events {
-      deliver_through(&portal, event, value);
+      deliver_through(&portal, event, value, decode);
     }
 
     let question = CString::new(verdict).expect("the test verdict should be readable");
@@ -1,6 +1,18 @@
 // The Api proxy, exposed as dry.api. Every property read returns a function
 // that posts a Call over the Bridge and resolves a Promise with Python's
-// return value.
+// return value. Binary values and big integers go out and come back through
+// dry.binary.
+//
+// A Call to a generator streams: Rust hands each value it yields to
+// dry.yieldCall, ahead of the answer that ends the Call. The Promise is an
+// async iterable over those values too, so `for await` reads them as they
+// come, and the iteration ends when the Promise settles. Values yielded before
+// the page starts reading are kept for it.
+//
+// A Call given an AbortSignal as its last argument can be cancelled: on abort
+// the Promise rejects with the signal's reason, an AbortError unless the page
+// gave another, and Python is told to stop the work. A `break` out of
+// `for await` cancels the Call the same way.
 //
 // The pending-call store lives in this closure, so no page script can read or
 // tamper with in-flight Calls. Rust resolves them through dry.resolveCall,
@@ -10,16 +22,116 @@
 (() => {
     const pending = new Map();
 
+    // What a Call has yielded and the page has not read yet, and the iterator
+    // that reads it. `promise` is the Call's own, whose settling ends it, and
+    // `stop` cancels the Call when the page stops reading.
+    const streamOf = (promise, stop) => {
+        const steps = [];
+        const readers = [];
+        let finished = false;
+        let stopped = false;
+
+        const last = () =>
+            stopped
+                ? Promise.resolve({ value: undefined, done: true })
+                : promise.then((value) => ({ value, done: true }));
+
+        const iterator = {
+            next: () => {
+                if (steps.length > 0) return steps.shift();
+                if (finished || stopped) return last();
+                return new Promise((resolve) => readers.push(resolve));
+            },
+            // A `break` out of `for await`: nothing more is read, whatever
+            // is still yielded is dropped, and the generator is stopped.
+            return: (value) => {
+                stopped = true;
+                steps.length = 0;
+                for (const reader of readers.splice(0)) reader(last());
+                stop();
+                return Promise.resolve({ value, done: true });
+            },
+            [Symbol.asyncIterator]() {
+                return this;
+            },
+        };
+
+        const put = (value) => {
+            if (stopped) return;
+            const step = value.then((item) => ({ value: item, done: false }));
+            // Handled here so a stream nobody reads raises no alarm; a reader
+            // awaiting the step still sees its rejection.
+            step.catch(() => {});
+            const reader = readers.shift();
+            if (reader) reader(step);
+            else steps.push(step);
+        };
+
+        const finish = () => {
+            finished = true;
+            for (const reader of readers.splice(0)) reader(last());
+        };
+
+        return { iterator, put, finish };
+    };
+
+    // Settles a Call on this side and tells Python to stop working on it.
+    // A Call already answered has nothing left to cancel.
+    const cancel = (call_id, reason) => {
+        const call = pending.get(call_id);
+        if (!call) return;
+        pending.delete(call_id);
+        call.settled();
+        call.reject(reason);
+        call.stream.finish();
+        window.dry.binary.post('dry_cancel:', call_id);
+    };
+
     const api = new Proxy({}, {
-        get: (_target, name) => (...args) => new Promise((resolve, reject) => {
+        get: (_target, name) => (...args) => {
             const call_id = Math.random().toString(36).slice(2, 11);
-            pending.set(call_id, { resolve, reject });
-            window.ipc.postMessage(JSON.stringify({
-                call_id: call_id,
-                function: name,
-                arguments: args,
-            }));
-        }),
+            const signal = args.at(-1) instanceof AbortSignal ? args.pop() : null;
+            const call = { settled: () => {} };
+            const promise = new Promise((resolve, reject) => {
+                call.resolve = resolve;
+                call.reject = reject;
+            });
+            call.stream = streamOf(promise, () =>
+                cancel(call_id, new DOMException('The iteration stopped.', 'AbortError'))
+            );
+            if (signal?.aborted) {
+                call.reject(signal.reason);
+                call.stream.finish();
+            } else {
+                // Posted first, so a Call refused on the way out leaves
+                // nothing pending; its answer cannot arrive before it has
+                // left.
+                try {
+                    window.dry.binary.post('', {
+                        call_id: call_id,
+                        function: name,
+                        arguments: args,
+                    });
+                    pending.set(call_id, call);
+                } catch (error) {
+                    call.reject(error);
+                    call.stream.finish();
+                }
+            }
+            if (signal && pending.has(call_id)) {
+                const aborted = () => cancel(call_id, signal.reason);
+                signal.addEventListener('abort', aborted, { once: true });
+                call.settled = () => signal.removeEventListener('abort', aborted);
+            }
+            return Object.defineProperty(promise, Symbol.asyncIterator, {
+                value: () => {
+                    // Read through the iterator, so a rejection surfaces
+                    // there and is not reported again as unhandled.
+                    promise.catch(() => {});
+                    return call.stream.iterator;
+                },
+            });
+        },
     });
 
     const resolveCall = (response) => {
@@ -27,6 +139,7 @@
         const call = pending.get(call_id);
         if (!call) return;
         pending.delete(call_id);
+        call.settled();
         if (error) {
             // Rust sends "TypeName: message", so the Python exception's type
             // survives as the name of the Error the Promise rejects with.
@@ -35,8 +148,28 @@
             if (separator !== -1) rejection.name = error.slice(0, separator);
             call.reject(rejection);
         } else {
-            call.resolve(result);
+            // A Promise, when a binary value in the result is still to be
+            // fetched, which the Call's own Promise then settles with.
+            try {
+                call.resolve(window.dry.binary.read(result));
+            } catch (error) {
+                call.reject(error);
+            }
+        }
+        call.stream.finish();
+    };
+
+    const yieldCall = (response) => {
+        const { call_id, value } = response;
+        const call = pending.get(call_id);
+        if (!call) return;
+        let read;
+        try {
+            read = Promise.resolve(window.dry.binary.read(value));
+        } catch (error) {
+            read = Promise.reject(error);
         }
+        call.stream.put(read);
     };
 
     Object.defineProperty(window.dry, 'api', {
@@ -52,4 +185,11 @@
         configurable: false,
         enumerable: false,
     });
+
+    Object.defineProperty(window.dry, 'yieldCall', {
+        value: yieldCall,
+        writable: false,
+        configurable: false,
+        enumerable: false,
+    });
 })();
@@ -0,0 +1,212 @@
+// Binary values on the Bridge, for dry.api and dry.emit on the way out and for
+// what Python sends on the way back.
+//
+// JSON has no bytes, so a binary value crosses as an object of a shape kept
+// for it: {"__dry_bytes__": "<base64>"} written into the message, or, for a
+// page served from a Root, {"__dry_bytes_at__": "<id>"} naming a body carried
+// over HTTP on the page's own origin, byte for byte. A Uint8Array, an
+// ArrayBuffer or any other view of one goes out; a Uint8Array comes back.
+//
+// A value sent aside has to arrive before the message that names it, and
+// messages have to leave in the order they were posted, so whatever is posted
+// after one waits its turn behind it. A page that sends no bytes never waits.
+//
+// The other value JSON cannot carry is an integer past 2**53, which
+// JSON.parse would read with digits missing. With big integers switched on,
+// Rust says so as dry.bigIntegers, and one crosses as {"__dry_bigint__":
+// "<digits>"}: a BigInt on this side, an int on Python's. Switched off, a
+// BigInt is refused as JSON.stringify refuses it, and the key is a key like
+// any other.
+//
+// Exposed as dry.binary, non-enumerable and non-writable like dry.resolveCall:
+// it is for api.js and events.js, not for the page.
+
+(() => {
+    const INLINE = '__dry_bytes__';
+    const ASIDE = '__dry_bytes_at__';
+    const PATH = '/__dry/bytes';
+    const BIG_INTEGER = '__dry_bigint__';
+
+    // The smallest value worth a request of its own. binary.rs holds the same
+    // figure for the other direction.
+    const MINIMUM = 8 * 1024;
+
+    // Where the side channel answers, or null when this page cannot reach it.
+    // Rust names the origin for a page served from a Root; a page anywhere
+    // else writes its bytes into the message, so nothing it sends leaves for a
+    // server Dry does not run.
+    const sideChannel = () => {
+        const origin = window.dry.binaryOrigin;
+        const here = location.protocol + '//' + location.host;
+        return typeof origin === 'string' && origin === here ? origin : null;
+    };
+
+    const reserved = (value) =>
+        value !== null &&
+        typeof value === 'object' &&
+        (Object.hasOwn(value, INLINE) || Object.hasOwn(value, ASIDE));
+
+    // Reserved only with big integers switched on; otherwise a key like any
+    // other, as it was before they existed.
+    const reservedForBig = (value) =>
+        window.dry.bigIntegers === true &&
+        value !== null &&
+        typeof value === 'object' &&
+        Object.hasOwn(value, BIG_INTEGER);
+
+    const bytesOf = (value) => {
+        if (value instanceof ArrayBuffer) return new Uint8Array(value);
+        if (ArrayBuffer.isView(value)) {
+            return new Uint8Array(value.buffer, value.byteOffset, value.byteLength);
+        }
+        return null;
+    };
+
+    const toBase64 = (bytes) => {
+        let text = '';
+        // In slices, so no single call is handed more arguments than the
+        // engine allows.
+        for (let at = 0; at < bytes.length; at += 0x8000) {
+            text += String.fromCharCode.apply(null, bytes.subarray(at, at + 0x8000));
+        }
+        return btoa(text);
+    };
+
+    const fromBase64 = (text) => {
+        const decoded = atob(text);
+        const bytes = new Uint8Array(decoded.length);
+        for (let at = 0; at < decoded.length; at++) {
+            bytes[at] = decoded.charCodeAt(at);
+        }
+        return bytes;
+    };
+
+    // Sends a value aside, and resolves with how the message is to name it:
+    // by id once it has arrived, or written in if it could not be sent.
+    const sendAside = (origin, bytes) =>
+        fetch(`${origin}${PATH}?length=${bytes.length}`, {
+            method: 'POST',
+            body: bytes,
+        })
+            .then((response) => (response.ok ? response.text() : Promise.reject()))
+            .then(
+                (id) => ({ [ASIDE]: id }),
+                () => ({ [INLINE]: toBase64(bytes) })
+            );
+
+    let queue = Promise.resolve();
+    let waiting = 0;
+
+    // Posts a message over the Bridge, after every message posted before it.
+    // Throws, before anything is sent, for a value that holds a reserved key.
+    const post = (prefix, message) => {
+        const origin = sideChannel();
+        const aside = [];
+        const text = JSON.stringify(message, (_key, value) => {
+            if (typeof value === 'bigint') {
+                if (window.dry.bigIntegers !== true) {
+                    throw new TypeError(
+                        'A BigInt crosses the Bridge only with big_integers ' +
+                        'switched on.'
+                    );
+                }
+                return { [BIG_INTEGER]: value.toString() };
+            }
+            const bytes = bytesOf(value);
+            if (bytes === null) {
+                if (reserved(value)) {
+                    throw new TypeError(
+                        `'${INLINE}' and '${ASIDE}' are reserved keys: the ` +
+                        `Bridge writes a binary value under them.`
+                    );
+                }
+                if (reservedForBig(value)) {
+                    throw new TypeError(
+                        `'${BIG_INTEGER}' is a reserved key: with big_integers ` +
+                        `switched on, the Bridge writes a big integer under it.`
+                    );
+                }
+                return value;
+            }
+            if (origin === null || bytes.length < MINIMUM) {
+                return { [INLINE]: toBase64(bytes) };
+            }
+            // Numbered for now, and named once the value has arrived.
+            aside.push(sendAside(origin, bytes));
+            return { [ASIDE]: aside.length - 1 };
+        });
+
+        if (aside.length === 0 && waiting === 0) {
+            window.ipc.postMessage(prefix + text);
+            return;
+        }
+
+        waiting += 1;
+        queue = queue
+            .then(() => Promise.all(aside))
+            .then((sent) => {
+                const named = JSON.parse(text, (_key, value) =>
+                    value !== null && typeof value === 'object' &&
+                    typeof value[ASIDE] === 'number'
+                        ? sent[value[ASIDE]]
+                        : value
+                );
+                window.ipc.postMessage(prefix + JSON.stringify(named));
+            })
+            .catch((error) => console.error('A message could not be sent.', error))
+            .finally(() => {
+                waiting -= 1;
+            });
+    };
+
+    // Reads a value Python sent, every binary value in it a Uint8Array and
+    // every big integer a BigInt. A value sent aside has to be fetched first,
+    // so then the answer is a Promise.
+    const read = (value) => {
+        const origin = window.dry.binaryOrigin;
+        const fetching = [];
+
+        const visit = (holder, key) => {
+            const item = holder[key];
+            if (item === null || typeof item !== 'object') return;
+            if (typeof item[INLINE] === 'string') {
+                holder[key] = fromBase64(item[INLINE]);
+            } else if (
+                window.dry.bigIntegers === true &&
+                typeof item[BIG_INTEGER] === 'string'
+            ) {
+                holder[key] = BigInt(item[BIG_INTEGER]);
+            } else if (typeof item[ASIDE] === 'string') {
+                fetching.push(
+                    fetch(`${origin}${PATH}/${item[ASIDE]}`)
+                        .then((response) => {
+                            if (!response.ok) {
+                                throw new Error(
+                                    `A binary value could not be fetched: ` +
+                                    `${response.status}.`
+                                );
+                            }
+                            return response.arrayBuffer();
+                        })
+                        .then((buffer) => {
+                            holder[key] = new Uint8Array(buffer);
+                        })
+                );
+            } else {
+                for (const inner of Object.keys(item)) visit(item, inner);
+            }
+        };
+
+        const top = { value };
+        visit(top, 'value');
+        if (fetching.length === 0) return top.value;
+        return Promise.all(fetching).then(() => top.value);
+    };
+
+    Object.defineProperty(window.dry, 'binary', {
+        value: Object.freeze({ post, read }),
+        writable: false,
+        configurable: false,
+        enumerable: false,
+    });
+})();
@@ -1,4 +1,5 @@
 // The Event half of the Bridge, exposed as dry.on, dry.off and dry.emit.
+// Binary values and big integers go out and come back through dry.binary.
 //
 // An Event is a name and a value, and it returns nothing: emit posts and
 // returns, and a listener's return value is dropped. That is the whole
@@ -56,27 +57,55 @@
                 `it as much as you like, but emit under a name of your own.`
             );
         }
-        window.ipc.postMessage(
-            'dry_event:' + JSON.stringify({ name: name, value: value })
-        );
+        window.dry.binary.post('dry_event:', { name: name, value: value });
     };
 
     // Every listener registered for the name gets the Event, in the order they
     // registered, and one that throws does not rob the rest of theirs.
-    const deliverEvent = (event) => {
-        const registered = listeners.get(event.name);
+    const dispatch = (name, value) => {
+        const registered = listeners.get(name);
         if (!registered) return;
         for (const listener of [...registered]) {
             try {
-                listener(event.value);
+                listener(value);
             } catch (error) {
-                console.error(
-                    `A listener for the Event '${event.name}' threw.`, error
-                );
+                console.error(`A listener for the Event '${name}' threw.`, error);
             }
         }
     };
 
+    let delivering = Promise.resolve();
+    let waiting = 0;
+
+    // An Event whose value holds bytes still to be fetched waits for them, and
+    // every Event after it waits its turn, so listeners hear Events in the
+    // order Python emitted them. Without such a wait, delivery is immediate.
+    const deliverEvent = (event) => {
+        let value;
+        try {
+            value = window.dry.binary.read(event.value);
+        } catch (error) {
+            console.error(`The Event '${event.name}' could not be read.`, error);
+            return;
+        }
+        if (!(value instanceof Promise) && waiting === 0) {
+            dispatch(event.name, value);
+            return;
+        }
+        waiting += 1;
+        delivering = delivering
+            .then(() => value)
+            .then(
+                (read) => dispatch(event.name, read),
+                (error) => console.error(
+                    `The Event '${event.name}' could not be read.`, error
+                )
+            )
+            .finally(() => {
+                waiting -= 1;
+            });
+    };
+
     const surface = { on, off, emit };
 
     for (const [name, value] of Object.entries(surface)) {
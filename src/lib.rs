@@ -1,13 +1,19 @@
 mod api;
+mod binary;
 mod errors;
 mod events;
 mod logs;
+mod loopback;
+mod protocols;
+mod reload;
+mod root;
 mod types;
 mod webview;
 mod window;
+mod workers;
 
 use pyo3::prelude::*;
-use std::{collections::HashMap, sync::Mutex};
+use std::{collections::HashMap, path::PathBuf, sync::Mutex};
 use tao::{
   event_loop::{EventLoop, EventLoopBuilder},
   window::Window,
@@ -16,7 +22,9 @@ use wry::WebView;
 
 use errors::{BridgeError, WebviewError, catch_panic};
 use events::{AppEvent, PROXY, run_event_loop};
-use webview::{build_ipc_handler, build_webview};
+use reload::Reload;
+use root::{Root, Source};
+use webview::{RootOrigin, Transport, build_ipc_handler, build_webview};
 use window::build_window;
 
 #[pymodule(gil_used = true)]
@@ -51,10 +59,106 @@ struct Settings {
   icon_path: Option<String>,
   html: Option<String>,
   url: Option<String>,
+  root: Option<RootSettings>,
+  protocols: Option<HashMap<String, Py<PyAny>>>,
   api: Option<HashMap<String, Py<PyAny>>>,
   dev_tools: bool,
   user_data_folder: String,
   default: Option<Py<PyAny>>,
+  codecs: Vec<String>,
+  object_hook: Option<Py<PyAny>>,
+  big_integers: bool,
+  call_timeout: Option<f64>,
+  call_timeouts: HashMap<String, Option<f64>>,
+}
+
+/// A Root as Python hands it down: what is mounted where, and how to serve it.
+#[derive(FromPyObject)]
+#[pyo3(from_item_all)]
+struct RootSettings {
+  transport: String,
+  origin: String,
+  secure_origin: bool,
+  mounts: Vec<MountSettings>,
+  cache_control: Vec<(String, String)>,
+  headers: Vec<(String, String)>,
+  header_overrides: Vec<(String, Vec<(String, String)>)>,
+  mime_types: Vec<(String, String)>,
+  spa_fallback: bool,
+  stream_threshold: u64,
+  live_reload: Option<String>,
+  include: Vec<String>,
+  exclude: Vec<String>,
+  dotfiles: bool,
+  integrity: Option<Vec<(String, String)>>,
+}
+
+/// One directory or zip archive, and the URL prefix it is served beneath.
+/// `dry.interface` has already checked the path exists, and that a file there
+/// is an archive; `within` is the directory inside that archive a package's
+/// resources live in, empty for the whole of it.
+#[derive(FromPyObject)]
+#[pyo3(from_item_all)]
+struct MountSettings {
+  prefix: String,
+  path: String,
+  within: String,
+}
+
+impl MountSettings {
+  fn into_mount(self) -> Result<(String, Source), String> {
+    let path = PathBuf::from(self.path);
+    let source = if path.is_file() {
+      Source::archive(&path, &self.within)?
+    } else {
+      Source::directory(path)
+    };
+    Ok((self.prefix, source))
+  }
+}
+
+impl RootSettings {
+  /// The Root these settings describe, or the reason they describe none.
+  fn into_root(self) -> PyResult<Root> {
+    self
+      .mounts
+      .into_iter()
+      .map(MountSettings::into_mount)
+      .collect::<Result<Vec<_>, _>>()
+      .and_then(Root::mounting)
+      .and_then(|root| {
+        root
+          .with_spa_fallback(self.spa_fallback)
+          .with_stream_threshold(self.stream_threshold)
+          .with_dotfiles(self.dotfiles)
+          .with_filters(self.include, self.exclude)?
+          .with_integrity(self.integrity)?
+          .with_cache_control(self.cache_control)?
+          .with_headers(self.headers, self.header_overrides)?
+          .with_mime_types(self.mime_types)
+      })
+      .map_err(unservable)
+  }
+
+  /// How the Root reaches the Webview, and under which origin, which no
+  /// protocol handler may also answer.
+  fn transport(
+    &self, protocols: Option<&HashMap<String, Py<PyAny>>>,
+  ) -> PyResult<Transport> {
+    let origin =
+      RootOrigin::parse(&self.origin, self.secure_origin).map_err(unservable)?;
+    if protocols.is_some_and(|protocols| protocols.contains_key(origin.scheme())) {
+      return Err(unservable(format!(
+        "The scheme '{}' is the Root's, and cannot also be given a handler.",
+        origin.scheme()
+      )));
+    }
+    Transport::from_setting(&self.transport, origin).map_err(unservable)
+  }
+}
+
+fn unservable(reason: String) -> PyErr {
+  WebviewError::new_err(format!("The Root could not be served: {reason}"))
 }
 
 /// A Webview open on screen, on its way to the event loop.
@@ -81,9 +185,20 @@ impl Opened {
 
 #[pyfunction]
 fn run(py: Python<'_>, mut settings: Settings) -> PyResult<()> {
-  // The `default=` hook ADR-0002 promises, installed before anything can
-  // cross the Bridge.
+  // The `default=` hook ADR-0002 promises, the codecs offered ahead of it,
+  // the `object_hook=` hook for the other direction, and whether big integers
+  // cross, installed before anything can cross the Bridge.
   types::set_default_hook(settings.default.take());
+  types::set_object_hook(settings.object_hook.take());
+  types::set_big_integers(settings.big_integers);
+  types::set_codecs(
+    settings
+      .codecs
+      .iter()
+      .map(|name| types::Codec::from_setting(name))
+      .collect::<Result<_, _>>()
+      .map_err(|reason| BridgeError::new_err(format!("{reason}.")))?,
+  );
 
   let opened = catch_panic(|| open(py, settings))?;
 
@@ -114,12 +229,64 @@ fn open(py: Python<'_>, settings: Settings) -> PyResult<Opened> {
     }
   }
 
-  if settings.html.is_none() && settings.url.is_none() {
+  // A limit for an entry the Api does not have can only be a misspelling, and
+  // the entry it meant would otherwise run under the default unnoticed.
+  for name in settings.call_timeouts.keys() {
+    if !settings
+      .api
+      .as_ref()
+      .is_some_and(|api| api.contains_key(name))
+    {
+      return Err(BridgeError::new_err(format!(
+        "call_timeouts names '{name}', which is not an Api entry."
+      )));
+    }
+  }
+  api::set_timeouts(api::Timeouts::new(
+    settings.call_timeout,
+    settings.call_timeouts.clone(),
+  ));
+
+  if let Some(protocols) = &settings.protocols {
+    for (scheme, handler) in protocols {
+      if !handler.bind(py).is_callable() {
+        return Err(WebviewError::new_err(format!(
+          "The handler for the protocol '{scheme}' is not callable."
+        )));
+      }
+    }
+  }
+
+  if settings.html.is_none() && settings.url.is_none() && settings.root.is_none() {
     return Err(WebviewError::new_err(
       "The Webview has no Content. Set content to an HTML string, a URL, or a path.",
     ));
   }
 
+  let live_reload = settings
+    .root
+    .as_ref()
+    .and_then(|root| root.live_reload.as_deref())
+    .map(Reload::from_setting)
+    .transpose()
+    .map_err(WebviewError::new_err)?;
+  let transport = settings
+    .root
+    .as_ref()
+    .map(|root| root.transport(settings.protocols.as_ref()))
+    .transpose()?;
+  let root = settings.root.map(RootSettings::into_root).transpose()?;
+  // Scanned now, so an edit made while the window opens is a change too.
+  let watcher = root
+    .as_ref()
+    .zip(live_reload)
+    .map(|(root, reload)| (root.watcher(), reload));
+  let served = root
+    .zip(transport)
+    .map(|(root, transport)| transport.serve(root))
+    .transpose()
+    .map_err(|err| unservable(err.to_string()))?;
+
   let event_loop = EventLoopBuilder::<AppEvent>::with_user_event().build();
 
   let proxy = event_loop.create_proxy();
@@ -145,15 +312,22 @@ fn open(py: Python<'_>, settings: Settings) -> PyResult<Opened> {
     ipc_handler,
     settings.html,
     settings.url,
+    served,
+    settings.protocols.unwrap_or_default(),
     settings.decorations,
     has_api,
     settings.dev_tools,
+    settings.big_integers,
     settings.user_data_folder,
   )
   .map_err(|err| {
     WebviewError::new_err(format!("The web content could not be built: {err}"))
   })?;
 
+  if let Some((watcher, reload)) = watcher {
+    reload::watch(watcher, reload);
+  }
+
   Ok(Opened {
     event_loop,
     window,
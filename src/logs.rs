@@ -7,6 +7,17 @@
 //! nothing sees nothing.
 
 use pyo3::{PyResult, Python, types::PyAnyMethods};
+use std::{
+  sync::{
+    OnceLock,
+    mpsc::{self, Sender},
+  },
+  thread,
+  time::Duration,
+};
+
+#[cfg(test)]
+mod tests;
 
 /// Opening the Webview, and the window it lives in.
 pub const WEBVIEW: &str = "dry.webview";
@@ -14,6 +25,9 @@ pub const WEBVIEW: &str = "dry.webview";
 /// Messages crossing the Bridge, in either direction.
 pub const BRIDGE: &str = "dry.bridge";
 
+/// Every request a Root or a protocol handler answered, one line each.
+pub const PROTOCOL: &str = "dry.webview.protocol";
+
 pub fn debug(logger: &str, message: impl AsRef<str>) {
   record(logger, "debug", message.as_ref());
 }
@@ -40,3 +54,39 @@ fn emit(py: Python<'_>, logger: &str, level: &str, message: &str) -> PyResult<()
     .call_method1(level, (message,))?;
   Ok(())
 }
+
+/// Logs one request a Root or a protocol handler answered: at debug when it
+/// succeeded, at warning when the page was told `4xx` or `5xx`, which is the
+/// line to look for when a page renders blank. `file` is what a Root resolved
+/// the request to, when it resolved it to anything.
+///
+/// Serving a Root needs no Python, and it should not start waiting on the GIL
+/// to say so, so the line is handed to a thread of its own and recorded there.
+pub fn request(
+  method: &str, target: &str, file: Option<&str>, status: u16, bytes: usize,
+  elapsed: Duration,
+) {
+  let level = if status >= 400 { "warning" } else { "debug" };
+  let message = request_line(method, target, file, status, bytes, elapsed);
+  static QUEUE: OnceLock<Sender<(&'static str, String)>> = OnceLock::new();
+  let queue = QUEUE.get_or_init(|| {
+    let (sender, receiver) = mpsc::channel::<(&'static str, String)>();
+    thread::spawn(move || {
+      for (level, message) in receiver {
+        record(PROTOCOL, level, &message);
+      }
+    });
+    sender
+  });
+  let _ = queue.send((level, message));
+}
+
+/// `GET /assets/app.js (assets/app.js): 200, 5120 bytes in 1.2 ms`.
+fn request_line(
+  method: &str, target: &str, file: Option<&str>, status: u16, bytes: usize,
+  elapsed: Duration,
+) -> String {
+  let file = file.map(|file| format!(" ({file})")).unwrap_or_default();
+  let milliseconds = elapsed.as_secs_f64() * 1000.0;
+  format!("{method} {target}{file}: {status}, {bytes} bytes in {milliseconds:.1} ms")
+}
@@ -0,0 +1,34 @@
+//! Tests for what the request log says. Where a record goes is Python's
+//! `logging`, and not tested here.
+
+use super::*;
+
+#[test]
+fn a_request_line_names_the_file_a_root_resolved() {
+  assert_eq!(
+    request_line(
+      "GET",
+      "/assets/app.js",
+      Some("assets/app.js"),
+      200,
+      5120,
+      Duration::from_micros(1234),
+    ),
+    "GET /assets/app.js (assets/app.js): 200, 5120 bytes in 1.2 ms"
+  );
+}
+
+#[test]
+fn a_request_line_without_a_file_names_only_the_request() {
+  assert_eq!(
+    request_line(
+      "GET",
+      "app://thumbnails/42.png",
+      None,
+      503,
+      26,
+      Duration::from_millis(30),
+    ),
+    "GET app://thumbnails/42.png: 503, 26 bytes in 30.0 ms"
+  );
+}
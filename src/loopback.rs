@@ -0,0 +1,519 @@
+//! Serving a Root over HTTP on the loopback interface, for a frontend that will
+//! not run at any origin but an `http` one: an OAuth provider that only
+//! redirects to `http://127.0.0.1`, a WASM loader that fetches by URL, a
+//! service worker on a webview that will not register one for a custom scheme.
+//!
+//! The server is Dry's own and answers with the same `Root` the internal
+//! protocol does, so every rule of serving a Root holds here unchanged. It
+//! listens on `127.0.0.1` at a port the system picks, one thread per
+//! connection and `MAX_CONNECTIONS` at once, for as long as the process runs.
+//!
+//! A request's head must arrive whole within `HEAD_TIMEOUT` of its first byte,
+//! and a connection whose request is refused is closed once it is answered, so
+//! a stranger holds a connection until a deadline, not for as long as it keeps
+//! trickling. When every place is taken, a new connection takes the
+//! place of the oldest one that has not yet been let in: the Webview is never
+//! locked out of its own Root by connections that will not be answered.
+//!
+//! Anything on the machine can connect to a loopback port, so the server only
+//! answers the Webview. Each session draws a token, and the Webview is sent
+//! first to an address carrying it, which trades it for a cookie and redirects
+//! to `/`. Every other request must carry that cookie, and name the server by
+//! the address it listens at, which a page elsewhere rebinding a host name of
+//! its own onto `127.0.0.1` cannot. Both are read off a request's head, before
+//! its body: a request that shows neither has its body refused unread, so a
+//! stranger cannot make the server hold anything.
+//!
+//! One exposure remains. A cookie is not scoped by port, so the Webview sends
+//! it to every server on `127.0.0.1` the page reaches, a redirect included,
+//! and a process listening on another loopback port that the page is sent to
+//! learns the token. The cookie is named for the port, so two sessions do not
+//! overwrite each other's, but it is the page that decides where it goes. A
+//! page that only loads from its own Root sends it nowhere else.
+
+use std::{
+  borrow::Cow,
+  io::{self, BufRead, BufReader, Read, Write},
+  net::{Ipv4Addr, Shutdown, TcpListener, TcpStream},
+  sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+  },
+  thread,
+  time::{Duration, Instant},
+};
+use wry::http::{
+  Method, Request, Response,
+  header::{CONNECTION, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, HOST, TRANSFER_ENCODING},
+};
+
+use crate::{binary, logs, root::Root};
+
+#[cfg(test)]
+mod tests;
+
+/// Where the Webview trades the token for the cookie. Nothing in a Root is
+/// reachable at it.
+const SESSION_PATH: &str = "/__dry/session";
+
+/// The cookie that carries the token on every request after the first,
+/// followed by the port, since every port on `127.0.0.1` shares a cookie jar.
+const COOKIE_PREFIX: &str = "dry-session-";
+
+/// The most a request's line and headers may hold. A Webview sends a few
+/// kilobytes at most; anything longer is not the Webview.
+const MAX_HEAD: u64 = 64 * 1024;
+
+/// The most a request's body may hold: a binary value the page sends aside,
+/// as large as the side channel has room for. A Root itself answers reads,
+/// and a read has no body, so no other request may carry one.
+const MAX_BODY: u64 = binary::ROOM as u64;
+
+/// The most connections served at once. A Webview opens a handful to one
+/// host. One past this takes the place of the oldest connection not yet let
+/// in, and is closed as soon as it is accepted when every one has been.
+const MAX_CONNECTIONS: usize = 32;
+
+/// How long a connection may sit idle before it is closed.
+const IDLE: Duration = Duration::from_secs(30);
+
+/// How long a request's head may take to arrive once its first byte has. The
+/// Webview sends a head in one write; a client trickling one is let go when
+/// this runs out, however steadily it trickles.
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The content type every answer that is not the Root's is written in.
+const TEXT: &str = "text/plain; charset=utf-8";
+
+/// The Root, and what a request must show to be answered from it.
+struct Session {
+  root: Root,
+  token: String,
+  /// The `Host` a request from the Webview names: `127.0.0.1:<port>`.
+  host: String,
+  /// The name of the cookie the token travels in.
+  cookie: String,
+}
+
+/// Who a request is from, as far as its head tells.
+enum Admission {
+  /// The Webview, come to trade the token for the cookie.
+  Entry,
+  /// The Webview, carrying the cookie.
+  Admitted,
+  Refused(&'static str),
+}
+
+/// The connections open at once, oldest first.
+#[derive(Default)]
+struct Connections(Mutex<Vec<Open>>);
+
+/// An open connection, as the others see it: whether it has been let in, and
+/// a handle to close it by when it has not and its place is needed.
+struct Open {
+  admitted: Arc<AtomicBool>,
+  stream: TcpStream,
+}
+
+/// One connection's place among `MAX_CONNECTIONS`, given back when it closes.
+struct Connection {
+  connections: Arc<Connections>,
+  admitted: Arc<AtomicBool>,
+}
+
+impl Connection {
+  /// A place for a connection just accepted, made by closing the oldest one not
+  /// yet let in when there is none free. `None` when every one has been.
+  fn take(connections: &Arc<Connections>, stream: &TcpStream) -> Option<Self> {
+    let stream = stream.try_clone().ok()?;
+    let Ok(mut open) = connections.0.lock() else {
+      return None;
+    };
+    if open.len() >= MAX_CONNECTIONS {
+      let oldest = open
+        .iter()
+        .position(|open| !open.admitted.load(Ordering::Acquire))?;
+      let _ = open.remove(oldest).stream.shutdown(Shutdown::Both);
+    }
+    let admitted = Arc::new(AtomicBool::new(false));
+    open.push(Open {
+      admitted: Arc::clone(&admitted),
+      stream,
+    });
+    Some(Connection {
+      connections: Arc::clone(connections),
+      admitted,
+    })
+  }
+
+  /// Marks the connection as the Webview's, to be kept when others are closed
+  /// to make room.
+  fn admit(&self) {
+    self.admitted.store(true, Ordering::Release);
+  }
+}
+
+impl Drop for Connection {
+  fn drop(&mut self) {
+    if let Ok(mut open) = self.connections.0.lock() {
+      open.retain(|open| !Arc::ptr_eq(&open.admitted, &self.admitted));
+    }
+  }
+}
+
+/// A connection read with two clocks: `IDLE` for each read, and a deadline,
+/// when there is one, for all of them together.
+struct Timed {
+  stream: TcpStream,
+  deadline: Option<Instant>,
+}
+
+impl Read for Timed {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let wait = match self.deadline {
+      Some(deadline) => deadline
+        .checked_duration_since(Instant::now())
+        .filter(|left| !left.is_zero())
+        .ok_or_else(|| {
+          io::Error::new(io::ErrorKind::TimedOut, "the request head took too long")
+        })?
+        .min(IDLE),
+      None => IDLE,
+    };
+    self.stream.set_read_timeout(Some(wait))?;
+    self.stream.read(buf)
+  }
+}
+
+/// Starts serving a Root, and returns the address the Webview is to open
+/// first, the token and all.
+pub fn start(root: Root) -> io::Result<String> {
+  let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+  let address = listener.local_addr()?;
+  let host = address.to_string();
+  let token = token()?;
+  let entry = format!("http://{host}{SESSION_PATH}?token={token}");
+  let cookie = format!("{COOKIE_PREFIX}{}", address.port());
+  let session = Arc::new(Session {
+    root,
+    token,
+    host,
+    cookie,
+  });
+  let connections = Arc::new(Connections::default());
+
+  thread::spawn(move || {
+    for stream in listener.incoming() {
+      let Ok(stream) = stream else {
+        continue;
+      };
+      // Dropped, and so closed, when there is no room for it.
+      let Some(connection) = Connection::take(&connections, &stream) else {
+        continue;
+      };
+      let session = Arc::clone(&session);
+      thread::spawn(move || {
+        if let Err(err) = session.converse(stream, &connection) {
+          logs::debug(
+            logs::WEBVIEW,
+            format!("A loopback connection to the Root ended early: {err}"),
+          );
+        }
+      });
+    }
+  });
+
+  Ok(entry)
+}
+
+impl Session {
+  /// Answers requests on one connection until the client closes it, asks for
+  /// it to be closed, leaves it idle, takes too long over a head or is refused.
+  fn converse(&self, stream: TcpStream, connection: &Connection) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(Timed {
+      stream,
+      deadline: None,
+    });
+    loop {
+      // Idle until the next request begins, and then against the clock.
+      if reader.fill_buf()?.is_empty() {
+        break;
+      }
+      reader.get_mut().deadline = Some(Instant::now() + HEAD_TIMEOUT);
+      let Some((head, length, keep_alive)) = read_head(&mut reader)? else {
+        break;
+      };
+      reader.get_mut().deadline = None;
+      let keep_alive = match self.admit(&head) {
+        Admission::Refused(_) => false,
+        Admission::Entry | Admission::Admitted => {
+          connection.admit();
+          keep_alive
+        },
+      };
+      if length > 0
+        && let Some(response) = self.refuse_body(&head, length)
+      {
+        // The body is still on the wire, unread, so the connection goes.
+        let head = head.map(|()| Vec::new());
+        return write_response(&mut writer, &head, &response, false);
+      }
+      let mut body = Vec::new();
+      (&mut reader).take(length).read_to_end(&mut body)?;
+      let request = head.map(|()| body);
+      let response = self.answer(&request);
+      write_response(&mut writer, &request, &response, keep_alive)?;
+      if !keep_alive {
+        break;
+      }
+    }
+    Ok(())
+  }
+
+  /// Who a request is from, read off its head alone.
+  fn admit<B>(&self, request: &Request<B>) -> Admission {
+    let host = request
+      .headers()
+      .get(HOST)
+      .and_then(|host| host.to_str().ok());
+    if host != Some(self.host.as_str()) {
+      return Admission::Refused("This server answers for 127.0.0.1 only.");
+    }
+
+    if request.uri().path() == SESSION_PATH {
+      let offered = request
+        .uri()
+        .query()
+        .and_then(|query| pairs(query, '&').find(|(name, _)| *name == "token"))
+        .map(|(_, value)| value);
+      if !offered.is_some_and(|offered| self.holds(offered)) {
+        return Admission::Refused("The session token is not this session's.");
+      }
+      return Admission::Entry;
+    }
+
+    let carried = request
+      .headers()
+      .get_all(COOKIE)
+      .iter()
+      .filter_map(|cookie| cookie.to_str().ok())
+      .flat_map(|cookie| pairs(cookie, ';'))
+      .any(|(name, value)| name == self.cookie && self.holds(value));
+    if !carried {
+      return Admission::Refused(
+        "This server only answers the Webview that started it.",
+      );
+    }
+    Admission::Admitted
+  }
+
+  /// The answer to a request announcing a body it may not send, or `None`
+  /// when the body is to be read: only a binary value the Webview sends
+  /// aside has one.
+  fn refuse_body(
+    &self, head: &Request<()>, length: u64,
+  ) -> Option<Response<Cow<'static, [u8]>>> {
+    let started = Instant::now();
+    let (status, message) = match self.admit(head) {
+      Admission::Admitted
+        if head.method() == Method::POST && head.uri().path() == binary::PATH =>
+      {
+        if length <= MAX_BODY {
+          return None;
+        }
+        (413, "The request body is too long.")
+      },
+      Admission::Admitted | Admission::Entry => {
+        (413, "Only a binary value sent aside carries a body.")
+      },
+      Admission::Refused(message) => (403, message),
+    };
+    logs::request(
+      head.method().as_str(),
+      head.uri().path(),
+      None,
+      status,
+      message.len(),
+      started.elapsed(),
+    );
+    Some(text(status, message))
+  }
+
+  /// The response to one request: the Root's, for the Webview, and a `403`
+  /// for anyone else.
+  fn answer(&self, request: &Request<Vec<u8>>) -> Response<Cow<'static, [u8]>> {
+    let started = Instant::now();
+    match self.admit(request) {
+      Admission::Entry => Response::builder()
+        .status(303)
+        .header("location", "/")
+        .header(
+          "set-cookie",
+          format!(
+            "{}={}; Path=/; HttpOnly; SameSite=Strict",
+            self.cookie, self.token
+          ),
+        )
+        .body(Cow::Borrowed(&[][..]))
+        .expect("Failed to build response"),
+      Admission::Admitted => {
+        binary::answer(request).unwrap_or_else(|| self.root.serve(request))
+      },
+      Admission::Refused(message) => {
+        logs::request(
+          request.method().as_str(),
+          request.uri().path(),
+          None,
+          403,
+          message.len(),
+          started.elapsed(),
+        );
+        text(403, message)
+      },
+    }
+  }
+
+  /// Whether a token is this session's, compared in time that does not depend
+  /// on how much of it is right.
+  fn holds(&self, offered: &str) -> bool {
+    offered.len() == self.token.len()
+      && offered
+        .bytes()
+        .zip(self.token.bytes())
+        .fold(0, |difference, (a, b)| difference | (a ^ b))
+        == 0
+  }
+}
+
+/// 128 bits, as hex, that no other process can guess, drawn from the
+/// operating system's random number generator. Fails only when the system
+/// has none to offer, and then nothing is served.
+pub fn token() -> io::Result<String> {
+  let mut bytes = [0u8; 16];
+  getrandom::fill(&mut bytes).map_err(|err| io::Error::other(err.to_string()))?;
+  Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// `name=value` pairs, split on a separator and trimmed, as a query string and
+/// a `Cookie` header both write them.
+fn pairs(text: &str, separator: char) -> impl Iterator<Item = (&str, &str)> {
+  text
+    .split(separator)
+    .filter_map(|pair| pair.trim().split_once('='))
+}
+
+/// Reads one request's head off a connection, with the length of the body
+/// that follows it, and whether the connection stays open after it is
+/// answered. `None` once the client has closed the connection.
+///
+/// The body is left on the wire, to be read once the head has shown the
+/// request may send one.
+fn read_head(
+  reader: &mut impl BufRead,
+) -> io::Result<Option<(Request<()>, u64, bool)>> {
+  let mut head = String::new();
+  let mut limited = reader.take(MAX_HEAD);
+  loop {
+    let read = limited.read_line(&mut head)?;
+    if read == 0 {
+      if head.trim().is_empty() {
+        return Ok(None);
+      }
+      return Err(invalid("the request ended inside its headers"));
+    }
+    // Blank lines before a request line are allowed, and skipped.
+    if head.trim().is_empty() {
+      head.clear();
+      continue;
+    }
+    if head.ends_with("\r\n\r\n") || head.ends_with("\n\n") {
+      break;
+    }
+  }
+
+  let mut lines = head.lines();
+  let request_line = lines.next().unwrap_or_default();
+  let mut parts = request_line.split(' ');
+  let (Some(method), Some(target), Some(version), None) =
+    (parts.next(), parts.next(), parts.next(), parts.next())
+  else {
+    return Err(invalid("the request line is not one"));
+  };
+
+  let mut builder = Request::builder().method(method).uri(target);
+  let mut length = 0;
+  let mut keep_alive = version == "HTTP/1.1";
+  for line in lines.filter(|line| !line.is_empty()) {
+    let (name, value) = line
+      .split_once(':')
+      .ok_or_else(|| invalid("a header has no value"))?;
+    let value = value.trim();
+    if name.eq_ignore_ascii_case(CONTENT_LENGTH.as_str()) {
+      length = value
+        .parse::<u64>()
+        .map_err(|_| invalid("the content length is not a number"))?;
+    } else if name.eq_ignore_ascii_case(TRANSFER_ENCODING.as_str()) {
+      return Err(invalid("a chunked request body is not read"));
+    } else if name.eq_ignore_ascii_case(CONNECTION.as_str()) {
+      keep_alive = keep_alive && !value.eq_ignore_ascii_case("close");
+    }
+    builder = builder.header(name, value);
+  }
+
+  let request = builder.body(()).map_err(|err| invalid(&err.to_string()))?;
+  Ok(Some((request, length, keep_alive)))
+}
+
+/// Writes a response. Its length is the body's, whatever the Root said, but
+/// for a `HEAD`, which is answered with the headers alone: its length is the
+/// one the Root counted for the body it did not read.
+fn write_response(
+  writer: &mut TcpStream, request: &Request<Vec<u8>>,
+  response: &Response<Cow<'static, [u8]>>, keep_alive: bool,
+) -> io::Result<()> {
+  let status = response.status();
+  let mut head = format!(
+    "HTTP/1.1 {} {}\r\n",
+    status.as_u16(),
+    status.canonical_reason().unwrap_or_default()
+  );
+  for (name, value) in response.headers() {
+    if [CONTENT_LENGTH, TRANSFER_ENCODING, CONNECTION].contains(name) {
+      continue;
+    }
+    let Ok(value) = value.to_str() else {
+      continue;
+    };
+    head.push_str(&format!("{name}: {value}\r\n"));
+  }
+  let length = response
+    .headers()
+    .get(CONTENT_LENGTH)
+    .filter(|_| request.method() == Method::HEAD)
+    .and_then(|length| length.to_str().ok()?.parse::<u64>().ok())
+    .unwrap_or(response.body().len() as u64);
+  head.push_str(&format!("content-length: {length}\r\n"));
+  if !keep_alive {
+    head.push_str("connection: close\r\n");
+  }
+  head.push_str("\r\n");
+
+  writer.write_all(head.as_bytes())?;
+  if request.method() != Method::HEAD {
+    writer.write_all(response.body())?;
+  }
+  writer.flush()
+}
+
+fn text(status: u16, message: &'static str) -> Response<Cow<'static, [u8]>> {
+  Response::builder()
+    .status(status)
+    .header(CONTENT_TYPE, TEXT)
+    .body(Cow::Borrowed(message.as_bytes()))
+    .expect("Failed to build response")
+}
+
+fn invalid(reason: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
@@ -0,0 +1,428 @@
+//! Tests for the loopback server: who it answers, how the Webview proves it is
+//! the Webview, and what goes over the wire.
+//!
+//! Everything here runs against a temporary directory on disk and a server on
+//! `127.0.0.1`. Nothing opens a window or runs an event loop.
+
+use super::*;
+use crate::root::Source;
+use std::{
+  fs,
+  path::PathBuf,
+  sync::atomic::{AtomicU32, Ordering},
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A Root over a throwaway directory holding one page, removed on drop.
+struct Fixture {
+  directory: PathBuf,
+}
+
+impl Fixture {
+  fn new() -> Self {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let directory = std::env::temp_dir().join(format!(
+      "dry-loopback-{}-{}",
+      SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("the clock should be after the epoch")
+        .as_nanos(),
+      COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&directory).expect("the fixture directory should be created");
+    fs::write(directory.join("index.html"), "<h1>Root</h1>")
+      .expect("the page should be written");
+    Fixture { directory }
+  }
+
+  fn root(&self) -> Root {
+    Root::mounting(vec![(
+      "/".to_string(),
+      Source::directory(self.directory.clone()),
+    )])
+    .expect("'/' should be mounted")
+  }
+
+  fn session(&self) -> Session {
+    Session {
+      root: self.root(),
+      token: "0123456789abcdef".into(),
+      host: "127.0.0.1:4000".into(),
+      cookie: "dry-session-4000".into(),
+    }
+  }
+}
+
+impl Drop for Fixture {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.directory);
+  }
+}
+
+fn get(uri: &str, headers: &[(&str, &str)]) -> Request<Vec<u8>> {
+  headers
+    .iter()
+    .fold(Request::get(uri), |builder, (name, value)| {
+      builder.header(*name, *value)
+    })
+    .body(Vec::new())
+    .expect("the request should build")
+}
+
+#[test]
+fn the_token_is_traded_for_a_cookie() {
+  let fixture = Fixture::new();
+  let response = fixture.session().answer(&get(
+    "/__dry/session?token=0123456789abcdef",
+    &[("host", "127.0.0.1:4000")],
+  ));
+
+  assert_eq!(response.status(), 303);
+  assert_eq!(response.headers()["location"], "/");
+  assert_eq!(
+    response.headers()["set-cookie"],
+    "dry-session-4000=0123456789abcdef; Path=/; HttpOnly; SameSite=Strict"
+  );
+}
+
+#[test]
+fn a_token_that_is_not_the_sessions_is_refused() {
+  let fixture = Fixture::new();
+  for uri in [
+    "/__dry/session?token=0123456789abcdee",
+    "/__dry/session?token=0123",
+    "/__dry/session",
+  ] {
+    let response = fixture
+      .session()
+      .answer(&get(uri, &[("host", "127.0.0.1:4000")]));
+    assert_eq!(response.status(), 403, "{uri}");
+  }
+}
+
+#[test]
+fn only_a_request_carrying_the_cookie_reaches_the_root() {
+  let fixture = Fixture::new();
+  let session = fixture.session();
+
+  let without = session.answer(&get("/", &[("host", "127.0.0.1:4000")]));
+  let with = session.answer(&get(
+    "/",
+    &[
+      ("host", "127.0.0.1:4000"),
+      ("cookie", "theme=dark; dry-session-4000=0123456789abcdef"),
+    ],
+  ));
+
+  assert_eq!(without.status(), 403);
+  assert_eq!(with.status(), 200);
+  assert_eq!(with.body().as_ref(), b"<h1>Root</h1>");
+}
+
+/// A page elsewhere can point a host name of its own at `127.0.0.1`, and its
+/// requests then name that host. They are refused whatever they carry.
+#[test]
+fn a_request_naming_another_host_is_refused() {
+  let fixture = Fixture::new();
+  let response = fixture.session().answer(&get(
+    "/",
+    &[
+      ("host", "rebound.example:4000"),
+      ("cookie", "dry-session-4000=0123456789abcdef"),
+    ],
+  ));
+
+  assert_eq!(response.status(), 403);
+}
+
+/// Every port on `127.0.0.1` shares one cookie jar, so the cookie is this
+/// port's, and another session's is not this one's.
+#[test]
+fn a_cookie_named_for_another_port_is_refused() {
+  let fixture = Fixture::new();
+  let response = fixture.session().answer(&get(
+    "/",
+    &[
+      ("host", "127.0.0.1:4000"),
+      ("cookie", "dry-session-4001=0123456789abcdef"),
+    ],
+  ));
+
+  assert_eq!(response.status(), 403);
+}
+
+#[test]
+fn every_session_draws_its_own_token() {
+  let first = token().expect("the system should offer randomness");
+  let second = token().expect("the system should offer randomness");
+
+  assert_eq!(first.len(), 32);
+  assert!(first.bytes().all(|byte| byte.is_ascii_hexdigit()));
+  assert_ne!(first, second);
+}
+
+/// The token an entry address carries, and the name of the cookie it is
+/// traded for.
+fn credentials(entry: &str) -> (&str, String) {
+  let token = entry
+    .split_once("token=")
+    .expect("the entry should carry the token")
+    .1;
+  let port = entry
+    .trim_start_matches("http://127.0.0.1:")
+    .split('/')
+    .next()
+    .expect("the entry should name a port");
+  (token, format!("dry-session-{port}"))
+}
+
+/// Over the wire, as the Webview goes: the entry address, then the page, on
+/// one connection kept open between them.
+#[test]
+fn the_webview_is_let_in_and_served_over_one_connection() {
+  let fixture = Fixture::new();
+  let entry = start(fixture.root()).expect("the server should start");
+  let rest = entry
+    .strip_prefix("http://")
+    .expect("the entry should be http");
+  let (host, path) = rest.split_once('/').expect("the entry should have a path");
+  let (token, cookie) = credentials(&entry);
+
+  let mut stream = TcpStream::connect(host).expect("the server should accept");
+  write!(stream, "GET /{path} HTTP/1.1\r\nHost: {host}\r\n\r\n")
+    .expect("the request should be sent");
+  write!(
+    stream,
+    "GET / HTTP/1.1\r\nHost: {host}\r\nCookie: {cookie}={token}\r\n\
+     Connection: close\r\n\r\n"
+  )
+  .expect("the request should be sent");
+
+  let mut answered = String::new();
+  stream
+    .read_to_string(&mut answered)
+    .expect("the answers should be read");
+
+  assert!(
+    answered.starts_with("HTTP/1.1 303 See Other\r\n"),
+    "{answered}"
+  );
+  assert!(answered.contains("\r\nHTTP/1.1 200 OK\r\n"), "{answered}");
+  assert!(answered.contains("content-length: 13\r\n"), "{answered}");
+  assert!(answered.ends_with("\r\n\r\n<h1>Root</h1>"), "{answered}");
+}
+
+#[test]
+fn a_head_request_is_answered_without_a_body() {
+  let fixture = Fixture::new();
+  let entry = start(fixture.root()).expect("the server should start");
+  let host = entry
+    .trim_start_matches("http://")
+    .split('/')
+    .next()
+    .expect("the entry should name a host");
+  let (token, cookie) = credentials(&entry);
+
+  let mut stream = TcpStream::connect(host).expect("the server should accept");
+  write!(
+    stream,
+    "HEAD / HTTP/1.1\r\nHost: {host}\r\nCookie: {cookie}={token}\r\n\
+     Connection: close\r\n\r\n"
+  )
+  .expect("the request should be sent");
+
+  let mut answered = String::new();
+  stream
+    .read_to_string(&mut answered)
+    .expect("the answer should be read");
+
+  assert!(answered.starts_with("HTTP/1.1 200 OK\r\n"), "{answered}");
+  assert!(answered.contains("content-length: 13\r\n"), "{answered}");
+  assert!(answered.ends_with("\r\n\r\n"), "{answered}");
+}
+
+/// The Bridge's side channel shares the server, behind the same cookie.
+#[test]
+fn a_binary_value_is_sent_aside_over_the_session() {
+  let fixture = Fixture::new();
+  let session = fixture.session();
+  let post = |cookie: &str| {
+    Request::post("/__dry/bytes?length=3")
+      .header("host", "127.0.0.1:4000")
+      .header("cookie", cookie)
+      .body(b"abc".to_vec())
+      .expect("the request should build")
+  };
+
+  assert_eq!(session.answer(&post("theme=dark")).status(), 403);
+
+  let sent = session.answer(&post("dry-session-4000=0123456789abcdef"));
+  assert_eq!(sent.status(), 201);
+  let id = String::from_utf8(sent.body().to_vec()).expect("an id is text");
+
+  let fetched = session.answer(&get(
+    &format!("/__dry/bytes/{id}"),
+    &[
+      ("host", "127.0.0.1:4000"),
+      ("cookie", "dry-session-4000=0123456789abcdef"),
+    ],
+  ));
+  assert_eq!(fetched.status(), 200);
+  assert_eq!(fetched.body().as_ref(), b"abc");
+}
+
+/// A stranger announcing a body is refused on its head, and the connection is
+/// closed with the body unread: nothing it says it will send is held.
+#[test]
+fn a_body_from_a_stranger_is_refused_unread() {
+  let fixture = Fixture::new();
+  let entry = start(fixture.root()).expect("the server should start");
+  let host = entry
+    .trim_start_matches("http://")
+    .split('/')
+    .next()
+    .expect("the entry should name a host");
+
+  let mut stream = TcpStream::connect(host).expect("the server should accept");
+  write!(
+    stream,
+    "POST /__dry/bytes?length=67108864 HTTP/1.1\r\nHost: {host}\r\n\
+     Content-Length: 67108864\r\n\r\n"
+  )
+  .expect("the head should be sent");
+
+  let mut answered = String::new();
+  stream
+    .read_to_string(&mut answered)
+    .expect("the answer should be read, and the connection closed");
+
+  assert!(
+    answered.starts_with("HTTP/1.1 403 Forbidden\r\n"),
+    "{answered}"
+  );
+  assert!(answered.contains("connection: close\r\n"), "{answered}");
+}
+
+/// Even the Webview sends a body only with a binary value sent aside.
+#[test]
+fn a_body_anywhere_but_the_side_channel_is_refused() {
+  let fixture = Fixture::new();
+  let session = fixture.session();
+  let cookie = "dry-session-4000=0123456789abcdef";
+  let head = |method: Method, uri: &str| {
+    Request::builder()
+      .method(method)
+      .uri(uri)
+      .header("host", "127.0.0.1:4000")
+      .header("cookie", cookie)
+      .body(())
+      .expect("the request should build")
+  };
+
+  let refused = session
+    .refuse_body(&head(Method::POST, "/index.html"), 3)
+    .expect("a body to the Root is refused");
+  assert_eq!(refused.status(), 413);
+
+  let too_long = session
+    .refuse_body(&head(Method::POST, "/__dry/bytes"), MAX_BODY + 1)
+    .expect("a body past the side channel's room is refused");
+  assert_eq!(too_long.status(), 413);
+
+  assert!(
+    session
+      .refuse_body(&head(Method::POST, "/__dry/bytes?length=3"), 3)
+      .is_none()
+  );
+}
+
+/// A stranger that asks for nothing but a page is closed on once answered,
+/// rather than kept for a next request it has no business sending.
+#[test]
+fn a_refused_connection_is_closed_once_answered() {
+  let fixture = Fixture::new();
+  let entry = start(fixture.root()).expect("the server should start");
+  let host = entry
+    .trim_start_matches("http://")
+    .split('/')
+    .next()
+    .expect("the entry should name a host");
+
+  let mut stream = TcpStream::connect(host).expect("the server should accept");
+  write!(stream, "GET / HTTP/1.1\r\nHost: {host}\r\n\r\n")
+    .expect("the request should be sent");
+
+  let mut answered = String::new();
+  stream
+    .read_to_string(&mut answered)
+    .expect("the answer should be read, and the connection closed");
+
+  assert!(
+    answered.starts_with("HTTP/1.1 403 Forbidden\r\n"),
+    "{answered}"
+  );
+  assert!(answered.contains("connection: close\r\n"), "{answered}");
+}
+
+/// A head sent a byte at a time is given up on when its deadline passes, not
+/// when the connection has sat idle for `IDLE`.
+#[test]
+fn a_head_that_does_not_arrive_in_time_is_given_up_on() {
+  let (mut client, server) = accepted(1).remove(0);
+  client
+    .write_all(b"GET / HTTP/1.1\r\n")
+    .expect("the start of a head should be sent");
+  let started = Instant::now();
+  let mut reader = BufReader::new(Timed {
+    stream: server,
+    deadline: Some(started + Duration::from_millis(100)),
+  });
+
+  assert!(read_head(&mut reader).is_err());
+  assert!(started.elapsed() < IDLE);
+
+  let err = reader
+    .read(&mut [0; 1])
+    .expect_err("the deadline has passed");
+  assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+}
+
+/// Connections to a listener of their own: each client's end, and the end a
+/// server would have accepted.
+fn accepted(count: usize) -> Vec<(TcpStream, TcpStream)> {
+  let listener =
+    TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).expect("a listener should bind");
+  let address = listener.local_addr().expect("the listener has an address");
+  (0..count)
+    .map(|_| {
+      let client = TcpStream::connect(address).expect("the listener should accept");
+      let (server, _) = listener.accept().expect("the connection should arrive");
+      (client, server)
+    })
+    .collect()
+}
+
+/// Full, a server makes room by closing the oldest connection not let in, and
+/// turns a newcomer away only when every connection has been.
+#[test]
+fn connections_past_the_limit_make_room_from_strangers() {
+  let connections = Arc::new(Connections::default());
+  let streams = accepted(MAX_CONNECTIONS + 2);
+  let held: Vec<_> = streams[..MAX_CONNECTIONS]
+    .iter()
+    .map(|(_, server)| {
+      Connection::take(&connections, server).expect("a connection within the limit")
+    })
+    .collect();
+  held[1..].iter().for_each(Connection::admit);
+
+  let newcomer = Connection::take(&connections, &streams[MAX_CONNECTIONS].1)
+    .expect("the stranger's place should be taken");
+  let mut closed = &streams[0].0;
+  assert_eq!(closed.read(&mut [0; 1]).expect("the stranger is closed"), 0);
+
+  newcomer.admit();
+  assert!(Connection::take(&connections, &streams[MAX_CONNECTIONS + 1].1).is_none());
+  drop(held);
+  assert!(Connection::take(&connections, &streams[MAX_CONNECTIONS + 1].1).is_some());
+}
@@ -0,0 +1,207 @@
+//! Protocols the application answers itself: `app://thumbnails/42.png` handed
+//! to a Python callable, the way a Root answers `localfile://` from disk.
+//!
+//! A handler is user code, so it runs where a Call runs. The request is read
+//! off the Webview on the thread that draws the window, handed to the portal
+//! in `dry/portal.py`, and that thread goes back to drawing; the Responder
+//! answers whenever the handler finishes, from whichever thread it finished
+//! on. A handler that takes a second to render a thumbnail holds up that
+//! thumbnail and nothing else.
+
+use pyo3::{
+  Bound, Py, PyAny, PyErr, PyResult, Python,
+  exceptions::PyValueError,
+  pyclass, pymethods,
+  types::{PyAnyMethods, PyBytes, PyBytesMethods},
+};
+use std::{borrow::Cow, sync::Mutex, time::Instant};
+use wry::{
+  RequestAsyncResponder,
+  http::{Request, Response, header::CONTENT_TYPE},
+};
+
+use crate::{errors::py_error_message, logs};
+
+#[cfg(test)]
+mod tests;
+
+/// The module that owns the asyncio loop and the thread pool.
+const PORTAL: &str = "dry.portal";
+
+/// The content type every answer that is not the handler's is written in.
+const TEXT: &str = "text/plain; charset=utf-8";
+
+/// One request's right to a response, handed to Python along with it.
+///
+/// The Webview waits on every request it makes until it is answered, so a
+/// Responder answers exactly once, and one dropped without answering — the
+/// portal closing under it, say — answers on its way out rather than leaving
+/// the page's `fetch` pending forever.
+#[pyclass(frozen)]
+pub struct Responder {
+  responder: Mutex<Option<RequestAsyncResponder>>,
+  method: String,
+  url: String,
+  /// When the request arrived, for the request log.
+  started: Instant,
+}
+
+impl Responder {
+  fn new(responder: RequestAsyncResponder, method: String, url: String) -> Self {
+    Responder {
+      responder: Mutex::new(Some(responder)),
+      method,
+      url,
+      started: Instant::now(),
+    }
+  }
+
+  /// Takes the right to respond, or `None` if the request is answered.
+  fn claim(&self) -> Option<RequestAsyncResponder> {
+    self.responder.lock().ok().and_then(|mut slot| slot.take())
+  }
+
+  fn answer(&self, response: Response<Cow<'static, [u8]>>) {
+    if let Some(responder) = self.claim() {
+      self.respond_with(responder, response);
+    }
+  }
+
+  fn respond_with(
+    &self, responder: RequestAsyncResponder, response: Response<Cow<'static, [u8]>>,
+  ) {
+    logs::request(
+      &self.method,
+      &self.url,
+      None,
+      response.status().as_u16(),
+      response.body().len(),
+      self.started.elapsed(),
+    );
+    responder.respond(response);
+  }
+}
+
+#[pymethods]
+impl Responder {
+  /// Answers with what the handler returned. Raises if that is not a
+  /// response — a status outside 100–599, a header that cannot be sent —
+  /// leaving the request unanswered so the failure can answer it instead.
+  fn respond(
+    &self, status: u16, headers: Vec<(String, String)>, body: &Bound<'_, PyBytes>,
+  ) -> PyResult<()> {
+    let response = response(status, &headers, body.as_bytes().to_vec())
+      .map_err(PyValueError::new_err)?;
+    self.answer(response);
+    Ok(())
+  }
+
+  /// Answers with the exception that ended the handler, as a `500` whose body
+  /// names it, so the failure shows in the page's network panel.
+  fn fail(&self, error: &Bound<'_, PyAny>) {
+    let message = py_error_message(error.py(), &PyErr::from_value(error.clone()));
+    self.answer(failure(500, &message));
+  }
+}
+
+impl Drop for Responder {
+  fn drop(&mut self) {
+    if let Some(responder) = self.claim() {
+      logs::warning(
+        logs::WEBVIEW,
+        format!("The handler for {} never answered.", self.url),
+      );
+      self.respond_with(responder, failure(503, "The handler never answered."));
+    }
+  }
+}
+
+/// Hands one request to the portal and returns. The GIL is held for the
+/// conversion only; the handler itself runs on a thread of the portal's
+/// choosing, long after this has returned.
+pub fn serve(
+  scheme: &str, handler: &Py<PyAny>, request: Request<Vec<u8>>,
+  responder: RequestAsyncResponder,
+) {
+  let url = request.uri().to_string();
+  Python::attach(|py| {
+    let method = request.method().to_string();
+    let responder = match Py::new(py, Responder::new(responder, method, url.clone())) {
+      Ok(responder) => responder,
+      Err(err) => {
+        logs::error(
+          logs::WEBVIEW,
+          format!("The request for {url} could not be answered: {err}"),
+        );
+        return;
+      },
+    };
+
+    let start = || -> PyResult<()> {
+      let headers = request_headers(&request);
+      let body = PyBytes::new(py, request.body());
+      let fields = (
+        request.method().as_str(),
+        &url,
+        request.uri().path(),
+        headers,
+        body,
+      );
+      py.import(PORTAL)?
+        .call_method1("serve", (scheme, handler, fields, &responder))?;
+      Ok(())
+    };
+
+    if let Err(err) = start() {
+      let message = py_error_message(py, &err);
+      logs::error(
+        logs::WEBVIEW,
+        format!("The request for {url} could not be handed over: {message}"),
+      );
+      responder.get().answer(failure(500, &message));
+    }
+  });
+}
+
+/// A request's headers as the handler reads them: lowercase names, and a
+/// header sent more than once joined into one value with commas, as HTTP
+/// allows for every header but `Set-Cookie` — which a request never carries.
+fn request_headers(request: &Request<Vec<u8>>) -> Vec<(String, String)> {
+  request
+    .headers()
+    .keys()
+    .map(|name| {
+      let values: Vec<&str> = request
+        .headers()
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect();
+      (name.as_str().to_string(), values.join(", "))
+    })
+    .collect()
+}
+
+/// The response a handler described, or the reason it describes none.
+fn response(
+  status: u16, headers: &[(String, String)], body: Vec<u8>,
+) -> Result<Response<Cow<'static, [u8]>>, String> {
+  if !(100..=599).contains(&status) {
+    return Err(format!("{status} is not an HTTP status."));
+  }
+  let mut builder = Response::builder().status(status);
+  for (name, value) in headers {
+    builder = builder.header(name.as_str(), value.as_str());
+  }
+  builder
+    .body(Cow::Owned(body))
+    .map_err(|err| format!("The response could not be built: {err}"))
+}
+
+fn failure(status: u16, message: &str) -> Response<Cow<'static, [u8]>> {
+  Response::builder()
+    .status(status)
+    .header(CONTENT_TYPE, TEXT)
+    .body(Cow::Owned(message.as_bytes().to_vec()))
+    .expect("Failed to build response")
+}
@@ -0,0 +1,259 @@
+//! Tests for protocol handlers, on both sides of the portal.
+//!
+//! From Rust: turning what a handler returned into a response, and a request's
+//! headers into what a handler reads. From Python: the portal running a
+//! handler off the calling thread and answering through a stand-in for the
+//! Responder, loaded without the `dry` package around it, as the Call tests
+//! load it. Only the Webview's own responder is missing, and that is wry's.
+
+use pyo3::types::{PyDict, PyDictMethods, PyModule};
+use std::{
+  ffi::{CStr, CString},
+  sync::atomic::{AtomicUsize, Ordering},
+};
+use wry::http::header::{ACCEPT, HeaderValue};
+
+use super::*;
+
+#[test]
+fn a_described_response_is_built_as_described() {
+  let response = response(
+    201,
+    &[("Content-Type".into(), "image/png".into())],
+    vec![1, 2, 3],
+  )
+  .expect("the response should build");
+
+  assert_eq!(response.status(), 201);
+  assert_eq!(response.headers()[CONTENT_TYPE], "image/png");
+  assert_eq!(response.body().as_ref(), [1, 2, 3]);
+}
+
+#[test]
+fn a_status_outside_http_is_refused() {
+  assert!(response(99, &[], Vec::new()).is_err());
+  assert!(response(600, &[], Vec::new()).is_err());
+}
+
+#[test]
+fn a_header_that_cannot_be_sent_is_refused() {
+  assert!(response(200, &[("Bad Name".into(), "x".into())], Vec::new()).is_err());
+  assert!(response(200, &[("X-Ok".into(), "line\nbreak".into())], Vec::new()).is_err());
+}
+
+#[test]
+fn a_repeated_request_header_reads_as_one() {
+  let mut request = Request::get("app://thumbnails/42.png")
+    .body(Vec::new())
+    .expect("the request should build");
+  request
+    .headers_mut()
+    .append(ACCEPT, HeaderValue::from_static("image/avif"));
+  request
+    .headers_mut()
+    .append(ACCEPT, HeaderValue::from_static("image/png"));
+
+  assert_eq!(
+    request_headers(&request),
+    [("accept".to_string(), "image/avif, image/png".to_string())]
+  );
+}
+
+#[test]
+fn a_failure_is_text_naming_it() {
+  let response = failure(500, "ValueError: no such thumbnail");
+  assert_eq!(response.status(), 500);
+  assert_eq!(response.headers()[CONTENT_TYPE], TEXT);
+  assert_eq!(response.body().as_ref(), b"ValueError: no such thumbnail");
+}
+
+/// What every portal test has in front of it: a stand-in for the Responder
+/// that records the answer and lets the test wait for it.
+const HARNESS: &str = r#"
+import asyncio, logging, threading
+
+logging.getLogger('dry').addHandler(logging.NullHandler())
+
+class Answer:
+    def __init__(self):
+        self.done = threading.Event()
+        self.response = None
+        self.error = None
+
+    def respond(self, status, headers, body):
+        self.response = (status, headers, body)
+        self.done.set()
+
+    def fail(self, error):
+        self.error = error
+        self.done.set()
+
+    def wait(self):
+        assert self.done.wait(10), 'the request was never answered'
+        return self
+
+def request(path='/42.png'):
+    answer = Answer()
+    fields = ('GET', f'app://thumbnails{path}', path, [('accept', 'image/png')], b'')
+    return fields, answer
+"#;
+
+/// Puts `dry/portal.py` inside a package of its own and imports it there, so
+/// its relative imports resolve without the extension module — see the Call
+/// tests, which load it the same way and say why.
+const LOAD_PORTAL: &CStr = cr#"
+from importlib.machinery import ModuleSpec
+from importlib.util import module_from_spec, spec_from_file_location
+from sys import modules
+
+spec = ModuleSpec(package, None, is_package=True)
+spec.submodule_search_locations = [directory]
+modules[package] = module_from_spec(spec)
+
+spec = spec_from_file_location(f'{package}.portal', f'{directory}/portal.py')
+portal = module_from_spec(spec)
+modules[spec.name] = portal
+spec.loader.exec_module(portal)
+"#;
+
+/// Loads a portal of its own and runs `body` against it. The body leaves what
+/// it found in `verdict`.
+fn through_the_portal(body: &str) -> String {
+  static PORTALS: AtomicUsize = AtomicUsize::new(0);
+  let package = format!(
+    "dry_portal_serving_{}",
+    PORTALS.fetch_add(1, Ordering::Relaxed)
+  );
+
+  Python::attach(|py| {
+    let globals = PyDict::new(py);
+    globals
+      .set_item("package", package)
+      .expect("the package name should be reachable from the loader");
+    globals
+      .set_item("directory", concat!(env!("CARGO_MANIFEST_DIR"), "/dry"))
+      .expect("the package directory should be reachable from the loader");
+    py.run(LOAD_PORTAL, Some(&globals), None)
+      .expect("the portal should import");
+    let portal = globals
+      .get_item("portal")
+      .expect("the loader should leave the portal")
+      .expect("the loader should leave the portal")
+      .cast_into::<PyModule>()
+      .expect("the portal should be a module");
+
+    let globals = PyDict::new(py);
+    globals
+      .set_item("portal", portal)
+      .expect("the portal should be reachable from the test");
+    let script = CString::new(format!("{HARNESS}\n{body}\nportal.shutdown()\n"))
+      .expect("the test body should be readable");
+    py.run(&script, Some(&globals), None)
+      .expect("the test body should run");
+
+    globals
+      .get_item("verdict")
+      .expect("the test body should leave a verdict")
+      .expect("the test body should leave a verdict")
+      .extract()
+      .expect("the verdict should be a string")
+  })
+}
+
+#[test]
+fn a_handler_answers_with_what_it_returns() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+def thumbnail(request):
+    return 200, {'Content-Type': 'image/png'}, request.path.encode()
+
+fields, answer = request()
+portal.serve('app', thumbnail, fields, answer)
+verdict = repr(answer.wait().response)
+"#
+    ),
+    "(200, [('Content-Type', 'image/png')], b'/42.png')"
+  );
+}
+
+#[test]
+fn a_handler_reads_the_request_it_was_sent() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+def echo(request):
+    return 200, {}, f'{request.method} {request.url} {request.headers["accept"]}'
+
+fields, answer = request()
+portal.serve('app', echo, fields, answer)
+verdict = answer.wait().response[2].decode()
+"#
+    ),
+    "GET app://thumbnails/42.png image/png"
+  );
+}
+
+#[test]
+fn a_coroutine_handler_is_awaited() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+async def later(request):
+    await asyncio.sleep(0.01)
+    return 204, {}, b''
+
+fields, answer = request()
+portal.serve('app', later, fields, answer)
+verdict = str(answer.wait().response[0])
+"#
+    ),
+    "204"
+  );
+}
+
+#[test]
+fn a_handler_that_raises_fails_the_request() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+def missing(request):
+    raise FileNotFoundError(request.path)
+
+fields, answer = request()
+portal.serve('app', missing, fields, answer)
+verdict = repr(answer.wait().error)
+"#
+    ),
+    "FileNotFoundError('/42.png')"
+  );
+}
+
+#[test]
+fn a_handler_returning_something_else_fails_the_request() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+fields, answer = request()
+portal.serve('app', lambda request: b'just bytes', fields, answer)
+verdict = type(answer.wait().error).__name__
+"#
+    ),
+    "TypeError"
+  );
+}
+
+#[test]
+fn a_request_after_the_close_is_refused() {
+  assert_eq!(
+    through_the_portal(
+      r#"
+portal.shutdown()
+fields, answer = request()
+portal.serve('app', lambda request: (200, {}, b''), fields, answer)
+verdict = type(answer.wait().error).__name__
+"#
+    ),
+    "RuntimeError"
+  );
+}
@@ -0,0 +1,106 @@
+//! Live reload: the directories a Root serves watched while the Webview runs,
+//! and the page told when their files change, so an edit shows without
+//! closing the window and running the app again.
+//!
+//! The watching is `root::watch`'s, by polling on a thread of its own. Once a
+//! burst of changes has settled, Dry emits `window:root-changed` to both sides
+//! with the paths that changed, and, unless the page asked to handle it
+//! itself, reloads the page.
+
+use std::{
+  thread,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  events::{AppEvent, emit_reserved, send_to_event_loop},
+  logs,
+  root::{Debounce, Watcher},
+  types::PythonType,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The reserved Event naming the paths that changed, `/` and all, as the page
+/// would ask for them.
+pub const ROOT_CHANGED: &str = "window:root-changed";
+
+/// How often the directories are scanned.
+const INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the directories must be quiet before a change is announced: long
+/// enough that a bundler writing its output over a second announces once.
+const QUIET: Duration = Duration::from_millis(400);
+
+/// What a change to the Root does to the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reload {
+  /// Emit `window:root-changed`, then reload the page.
+  Page,
+  /// Emit `window:root-changed` and nothing more, for a frontend that swaps a
+  /// stylesheet in place rather than losing its state.
+  Event,
+}
+
+impl Reload {
+  /// Reads the `live_reload` setting: `'reload'` or `'event'`.
+  pub fn from_setting(setting: &str) -> Result<Self, String> {
+    match setting {
+      "reload" => Ok(Reload::Page),
+      "event" => Ok(Reload::Event),
+      other => Err(format!(
+        "live_reload must be 'reload' or 'event', not '{other}'."
+      )),
+    }
+  }
+}
+
+/// Starts watching, for as long as the process runs.
+pub fn watch(mut watcher: Watcher, reload: Reload) {
+  if watcher.is_empty() {
+    logs::warning(
+      logs::WEBVIEW,
+      "Live reload has nothing to watch: the Root is served from archives only, \
+       which are read once when the Webview opens.",
+    );
+    return;
+  }
+
+  thread::spawn(move || {
+    let mut debounce = Debounce::new(QUIET);
+    loop {
+      thread::sleep(INTERVAL);
+      if let Some(changed) = debounce.feed(watcher.changes(), Instant::now())
+        && !announce(changed, reload)
+      {
+        return;
+      }
+    }
+  });
+}
+
+/// Tells both sides what changed, and reloads the page if that is the
+/// setting. Returns whether the window is still there to tell.
+fn announce(changed: Vec<String>, reload: Reload) -> bool {
+  logs::debug(
+    logs::WEBVIEW,
+    format!("The Root changed: {}", changed.join(", ")),
+  );
+  emit_reserved(ROOT_CHANGED, changed_paths(changed));
+  match reload {
+    Reload::Page => {
+      send_to_event_loop(AppEvent::RunJavascript(RELOAD.to_string())).is_ok()
+    },
+    Reload::Event => true,
+  }
+}
+
+/// The script that reloads the page. Every file in the Root is revalidated on
+/// the way back in, so nothing stale is kept.
+const RELOAD: &str = "window.location.reload()";
+
+/// The paths that changed, as the Event carries them.
+fn changed_paths(changed: Vec<String>) -> PythonType {
+  PythonType::Array(changed.into_iter().map(PythonType::String).collect())
+}
@@ -0,0 +1,22 @@
+//! Tests for reading the live reload setting and writing what the Event
+//! carries. The watching itself is tested in `root::watch`.
+
+use super::*;
+
+#[test]
+fn the_setting_names_what_a_change_does() {
+  assert_eq!(Reload::from_setting("reload"), Ok(Reload::Page));
+  assert_eq!(Reload::from_setting("event"), Ok(Reload::Event));
+  assert!(Reload::from_setting("hot").is_err());
+}
+
+#[test]
+fn the_event_carries_the_paths_that_changed() {
+  assert_eq!(
+    changed_paths(vec!["/index.html".into(), "/themes/dark.css".into()]),
+    PythonType::Array(vec![
+      PythonType::String("/index.html".into()),
+      PythonType::String("/themes/dark.css".into()),
+    ])
+  );
+}
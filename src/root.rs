@@ -0,0 +1,945 @@
+//! A Root: a local directory, or a zip archive, served to the Webview over the
+//! internal protocol, one file per request.
+//!
+//! Everything here answers a request with a response and touches nothing but
+//! the file system, so a test can serve a temporary directory without opening
+//! a Webview or running an event loop. `webview.rs` is what registers the
+//! protocol and hands each request in. Where the files are read from is
+//! `source`'s business; this answers for them the same way either way, and a
+//! Root may be several sources at once, each mounted beneath a URL prefix.
+//!
+//! A Root answers the way a static file server would, because that is what a
+//! compiled frontend was built against. A `<video>` seeks with a `Range`
+//! request and expects a `206` back, so ranges are honoured — see `range` —
+//! and every file carries the validators an `If-Range` is compared against —
+//! see `validators`. The same validators answer a reload: a file the Webview
+//! already holds is a `304` that reads nothing, and how long it may hold one
+//! without asking is the cache policy in the Root's settings. A bundle a build
+//! left compressed beside itself is sent compressed — see `encoding`. Whatever
+//! else a response should carry, a Content-Security-Policy or the isolation
+//! headers `SharedArrayBuffer` asks for, is the Root's headers setting.
+
+use std::{
+  borrow::Cow, cmp::Reverse, collections::HashMap, io, path::Path, time::Instant,
+};
+use wry::http::{
+  HeaderValue, Method, Request, Response,
+  header::{
+    ACCEPT_ENCODING, ACCEPT_RANGES, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH,
+    CONTENT_RANGE, CONTENT_TYPE, ETAG, HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    IF_RANGE, LAST_MODIFIED, RANGE, TRANSFER_ENCODING, VARY,
+  },
+};
+
+use crate::logs;
+use encoding::{Encoding, PRECOMPRESSED};
+use glob::Glob;
+use integrity::Manifest;
+use range::Ranges;
+use source::Opened;
+pub use source::Source;
+use validators::Validators;
+pub use watch::{Debounce, Watcher};
+
+mod archive;
+mod encoding;
+mod glob;
+mod integrity;
+mod range;
+mod sha256;
+mod source;
+mod validators;
+mod watch;
+
+#[cfg(test)]
+mod tests;
+
+/// The file a request for a directory is served from.
+pub const ROOT_INDEX: &str = "index.html";
+
+/// How long a file may be before a range request for it is answered a chunk
+/// at a time: 8 MiB.
+pub const DEFAULT_STREAM_THRESHOLD: u64 = 8 * 1024 * 1024;
+
+/// The content type every answer that is not a file is written in.
+const TEXT: &str = "text/plain; charset=utf-8";
+
+/// The `Cache-Control` a file is served with when no rule names it.
+///
+/// The Webview may keep a copy but must ask before using it, and because every
+/// file carries validators the asking costs a `stat` and a `304`, not a read.
+/// A reload after a rebuild therefore always sees the rebuild, which is the
+/// one thing a default must never get wrong.
+const DEFAULT_CACHE_CONTROL: &str = "no-cache";
+
+/// What the `immutable` policy stands for: a year, and no revalidation at all,
+/// for a file whose name changes whenever its content does.
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+/// The headers a Root works out for itself on every response. Setting one of
+/// them by hand would contradict the file, the range or the encoding actually
+/// sent, so they are refused in the headers setting; `Cache-Control` has a
+/// setting of its own.
+const COMPUTED_HEADERS: [HeaderName; 10] = [
+  ACCEPT_RANGES,
+  CACHE_CONTROL,
+  CONTENT_ENCODING,
+  CONTENT_LENGTH,
+  CONTENT_RANGE,
+  CONTENT_TYPE,
+  ETAG,
+  LAST_MODIFIED,
+  TRANSFER_ENCODING,
+  VARY,
+];
+
+/// A Root: a local directory or a zip archive served to the Webview, one file
+/// per request.
+pub struct Root {
+  /// Where each URL prefix is served from, the longest prefix first, so the
+  /// first mount a path falls under is the one that answers it.
+  mounts: Vec<Mount>,
+  cache_control: Vec<CacheRule>,
+  headers: Vec<(HeaderName, HeaderValue)>,
+  header_rules: Vec<HeaderRule>,
+  /// Content types by extension, lowercase and without the dot, consulted
+  /// before the built-in table.
+  mime_types: HashMap<String, String>,
+  spa_fallback: bool,
+  stream_threshold: u64,
+  /// Patterns a file must match one of to be served; empty lets every file.
+  include: Vec<Glob>,
+  /// Patterns no file served may match.
+  exclude: Vec<Glob>,
+  /// Whether a file with a segment starting with `.` may be served at all.
+  dotfiles: bool,
+  /// What every file served must hash to, when the Root was shipped with one.
+  integrity: Option<Manifest>,
+}
+
+/// One rule of a Root's cache policy: the files a pattern names are served
+/// with this `Cache-Control`.
+struct CacheRule {
+  pattern: Glob,
+  value: HeaderValue,
+}
+
+/// One source of files, served beneath a URL prefix: `/` for the top of the
+/// Root, `/themes` for everything under it.
+struct Mount {
+  /// The prefix's segments; none for `/`.
+  segments: Vec<String>,
+  source: Source,
+}
+
+/// One override of a Root's headers: the files a pattern names are served with
+/// these instead. An empty value leaves that header off them.
+struct HeaderRule {
+  pattern: Glob,
+  headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+/// Why a request did not reach a file inside the Root.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+  /// The request resolved outside the Root, or tried to.
+  Outside,
+  /// The request resolved inside the Root but there is no file there.
+  NotFound,
+}
+
+impl Root {
+  /// A Root made of several sources, each served beneath a URL prefix:
+  /// `/` for the compiled frontend, `/themes` for a directory of user themes.
+  /// A path is answered by the mount with the longest prefix it falls under,
+  /// and only by that one, so a file missing from `/themes` is a 404 even if
+  /// the frontend has a `themes` directory of its own. Each mount keeps its
+  /// own containment: nothing under one prefix reaches another's files.
+  ///
+  /// A prefix that could not be a path, or one given twice, is refused here.
+  pub fn mounting(mounts: Vec<(String, Source)>) -> Result<Self, String> {
+    let mut read: Vec<Mount> = Vec::with_capacity(mounts.len());
+    for (prefix, source) in mounts {
+      let segments = prefix_segments(&prefix)?;
+      if read.iter().any(|mount| mount.segments == segments) {
+        return Err(format!("'{prefix}' is mounted more than once."));
+      }
+      read.push(Mount { segments, source });
+    }
+    if read.is_empty() {
+      return Err("There is nothing to serve: no mount was given.".to_string());
+    }
+    read.sort_by_key(|mount| Reverse(mount.segments.len()));
+    Ok(Root::serving(read))
+  }
+
+  fn serving(mounts: Vec<Mount>) -> Self {
+    Root {
+      mounts,
+      cache_control: Vec::new(),
+      headers: Vec::new(),
+      header_rules: Vec::new(),
+      mime_types: HashMap::new(),
+      spa_fallback: false,
+      stream_threshold: DEFAULT_STREAM_THRESHOLD,
+      include: Vec::new(),
+      exclude: Vec::new(),
+      dotfiles: false,
+      integrity: None,
+    }
+  }
+
+  /// Sets whether a miss is answered with the index instead of a 404, for a
+  /// single-page app whose router owns the paths beneath it.
+  ///
+  /// Only a path whose last segment has no extension falls back: that is a
+  /// route, `/settings/profile`, and the index is what draws it. A missing
+  /// `app.js` or `logo.svg` is still a 404, because handing a script tag an
+  /// HTML page hides the broken reference behind a syntax error.
+  pub fn with_spa_fallback(mut self, spa_fallback: bool) -> Self {
+    self.spa_fallback = spa_fallback;
+    self
+  }
+
+  /// Sets how long a file may be before it is streamed: a range request for a
+  /// longer file is answered with no more than this many bytes, the first of
+  /// the ranges it asked for, and the media element or `fetch` asking goes on
+  /// to ask for the next. A `<video>` opens a large file with `bytes=0-`, so
+  /// a 2 GB recording is read a chunk at a time rather than whole.
+  ///
+  /// A request with no `Range` at all is still answered with the whole file,
+  /// as HTTP requires; the Webview's protocol takes a body, not a stream.
+  pub fn with_stream_threshold(mut self, stream_threshold: u64) -> Self {
+    self.stream_threshold = stream_threshold.max(1);
+    self
+  }
+
+  /// Sets which files may be served at all. With include patterns, a file
+  /// must match one of them; a file matching any exclude pattern is never
+  /// served. A file held back is a 404, exactly as if it were not there: a
+  /// 403 would tell a curious page that `.env` exists.
+  ///
+  /// A pattern that names nothing is refused here, when the Webview is
+  /// opened.
+  pub fn with_filters(
+    mut self, include: Vec<String>, exclude: Vec<String>,
+  ) -> Result<Self, String> {
+    self.include = include
+      .iter()
+      .map(|pattern| Glob::new(pattern))
+      .collect::<Result<_, _>>()?;
+    self.exclude = exclude
+      .iter()
+      .map(|pattern| Glob::new(pattern))
+      .collect::<Result<_, _>>()?;
+    Ok(self)
+  }
+
+  /// Sets whether dotfiles are served: `.env`, `.git/config`, anything with a
+  /// segment starting with `.`. Off by default, since a build folder is where
+  /// such a file ends up by accident and never where a frontend asks for one.
+  pub fn with_dotfiles(mut self, dotfiles: bool) -> Self {
+    self.dotfiles = dotfiles;
+    self
+  }
+
+  /// Sets the integrity manifest: every file's name, and the SHA-256 its
+  /// content must hash to, as hex. With one, a file the manifest does not
+  /// name, or names with another digest, is refused with a `500` and logged.
+  pub fn with_integrity(
+    mut self, digests: Option<Vec<(String, String)>>,
+  ) -> Result<Self, String> {
+    self.integrity = digests.map(Manifest::new).transpose()?;
+    Ok(self)
+  }
+
+  /// Sets the cache policy: patterns, in order, each naming the
+  /// `Cache-Control` the files it matches are served with. The first pattern
+  /// matching a file decides it, and a file none of them matches gets
+  /// `no-cache`.
+  ///
+  /// A policy is a `Cache-Control` value, or `immutable` for the year-long,
+  /// never-revalidated caching a hashed asset filename allows. A pattern that
+  /// names nothing, or a value that cannot be a header, is refused here, when
+  /// the Webview is opened, rather than ignored on every request after.
+  pub fn with_cache_control(
+    mut self, rules: Vec<(String, String)>,
+  ) -> Result<Self, String> {
+    for (pattern, policy) in rules {
+      let directive = match policy.as_str() {
+        "immutable" => IMMUTABLE,
+        directive => directive,
+      };
+      let value = HeaderValue::from_str(directive).map_err(|_| {
+        format!(
+          "The cache policy for '{pattern}' is not a Cache-Control value: {policy:?}."
+        )
+      })?;
+      self.cache_control.push(CacheRule {
+        pattern: Glob::new(&pattern)?,
+        value,
+      });
+    }
+    Ok(self)
+  }
+
+  /// Sets the headers every response carries, and the overrides for the files
+  /// a pattern names. Each header an override sets replaces the default for
+  /// the files it matches, and the first override matching a file and setting
+  /// a header decides that header; an empty value leaves it off.
+  ///
+  /// Everything is read here, so a name or a value that cannot be a header,
+  /// or a header the Root computes itself, is refused when the Webview is
+  /// opened rather than dropped from every response after.
+  pub fn with_headers(
+    mut self, defaults: Vec<(String, String)>,
+    overrides: Vec<(String, Vec<(String, String)>)>,
+  ) -> Result<Self, String> {
+    self.headers = header_pairs(defaults)?;
+    for (pattern, headers) in overrides {
+      self.header_rules.push(HeaderRule {
+        pattern: Glob::new(&pattern)?,
+        headers: header_pairs(headers)?,
+      });
+    }
+    Ok(self)
+  }
+
+  /// Sets content types by extension, over the built-in table: `.glb` to
+  /// `model/gltf-binary`, or `.ts` to something other than TypeScript. An
+  /// extension may have dots of its own, `.d.ts`, and the longest one a file
+  /// name ends in decides it.
+  ///
+  /// An extension that names nothing, or a type that cannot be a header, is
+  /// refused here, when the Webview is opened.
+  pub fn with_mime_types(
+    mut self, types: Vec<(String, String)>,
+  ) -> Result<Self, String> {
+    for (extension, content_type) in types {
+      let key = extension.strip_prefix('.').unwrap_or(&extension);
+      if key.is_empty() || key.contains(['/', '\\']) || key.ends_with('.') {
+        return Err(format!("'{extension}' is not a file extension."));
+      }
+      if HeaderValue::from_str(&content_type).is_err() || content_type.is_empty() {
+        return Err(format!(
+          "The content type for '{extension}' is not a header value: {content_type:?}."
+        ));
+      }
+      self
+        .mime_types
+        .insert(key.to_ascii_lowercase(), content_type);
+    }
+    Ok(self)
+  }
+
+  /// A watcher over every directory the Root serves, for live reload. An
+  /// archive's index is read once, when the Root is made, so an archive is
+  /// not watched: a rebuilt one needs the Webview opened again. A file the
+  /// filters hold back is not watched either, so its name never reaches the
+  /// page.
+  pub fn watcher(&self) -> Watcher {
+    let (dotfiles, include, exclude) =
+      (self.dotfiles, self.include.clone(), self.exclude.clone());
+    Watcher::new(
+      self
+        .mounts
+        .iter()
+        .filter_map(|mount| match &mount.source {
+          Source::Directory(directory) => {
+            Some((mount.segments.join("/"), directory.clone()))
+          },
+          Source::Archive(_) => None,
+        })
+        .collect(),
+      move |name| filtered(name, dotfiles, &include, &exclude),
+    )
+  }
+
+  /// Answers one request for a path beneath the Root, and logs it.
+  pub fn serve(&self, request: &Request<Vec<u8>>) -> Response<Cow<'static, [u8]>> {
+    let started = Instant::now();
+    let (served, mut response) = self.answer(request);
+    self.add_headers(&mut response, served.as_deref());
+    logs::request(
+      request.method().as_str(),
+      request.uri().path(),
+      served.as_deref(),
+      response.status().as_u16(),
+      response.body().len(),
+      started.elapsed(),
+    );
+    response
+  }
+
+  /// The response to a request, and the name of the file it was answered
+  /// with, if a file answered it.
+  fn answer(
+    &self, request: &Request<Vec<u8>>,
+  ) -> (Option<String>, Response<Cow<'static, [u8]>>) {
+    let request_path = request.uri().path();
+    let name = match self.resolve(request_path) {
+      Err(Rejection::NotFound) if self.falls_back(request_path) => self.resolve("/"),
+      resolved => resolved,
+    };
+    match name {
+      Ok(name) => match self.serve_resolved(&name, request) {
+        Ok(response) => (Some(name), response),
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => self.error_page(
+          500,
+          Cow::Owned(
+            format!("Not the file that was shipped: {request_path}").into_bytes(),
+          ),
+        ),
+        Err(_) => self.error_page(404, not_found(request_path)),
+      },
+      Err(Rejection::NotFound) => self.error_page(404, not_found(request_path)),
+      Err(Rejection::Outside) => self.error_page(
+        403,
+        Cow::Owned(format!("Outside the root: {request_path}").into_bytes()),
+      ),
+    }
+  }
+
+  /// Answers with an error, from the page the Root keeps for it at its top —
+  /// `404.html`, `403.html` — or, when it keeps none, as text saying what
+  /// went wrong. Either way the status is the error's, so a `fetch` still
+  /// sees the failure; only what a person looking at the window reads
+  /// changes.
+  fn error_page(
+    &self, status: u16, text: Cow<'static, [u8]>,
+  ) -> (Option<String>, Response<Cow<'static, [u8]>>) {
+    let page = format!("{status}.html");
+    let found = self.resolve_components(&[&page]).ok().and_then(|name| {
+      let content = self.open(&name).and_then(Opened::read_all).ok()?;
+      Some((name, content))
+    });
+    match found {
+      Some((name, content)) => {
+        let response = respond(status, self.content_type(&name), Cow::Owned(content));
+        (Some(name), response)
+      },
+      None => (None, respond(status, TEXT, text)),
+    }
+  }
+
+  /// Adds the headers the settings ask for: the defaults on every response,
+  /// a `404` included, and the overrides on a file they name.
+  fn add_headers(
+    &self, response: &mut Response<Cow<'static, [u8]>>, name: Option<&str>,
+  ) {
+    let rules = self
+      .header_rules
+      .iter()
+      .filter(|rule| name.is_some_and(|name| rule.pattern.matches(name)));
+    let mut decided = Vec::new();
+    for (header, value) in rules.flat_map(|rule| &rule.headers).chain(&self.headers) {
+      if decided.contains(&header) {
+        continue;
+      }
+      decided.push(header);
+      if !value.is_empty() {
+        response.headers_mut().insert(header, value.clone());
+      }
+    }
+  }
+
+  /// Whether a path the Root has no file for is a route the index draws.
+  fn falls_back(&self, request_path: &str) -> bool {
+    let decoded = percent_decode(request_path);
+    let last = decoded.rsplit('/').next().unwrap_or_default();
+    self.spa_fallback && Path::new(last).extension().is_none()
+  }
+
+  /// Turns a requested path into the name of the file it reaches inside the
+  /// Root, relative to its top, if the filters let it be served.
+  ///
+  /// A path escaping the Root is refused twice over: `..` and anything that
+  /// would be read as a separator or a drive are rejected here, before the
+  /// source is asked, and a directory then requires the canonical result to
+  /// sit beneath it, which catches a symlink pointing out of it.
+  pub fn resolve(&self, request_path: &str) -> Result<String, Rejection> {
+    let decoded = percent_decode(request_path);
+
+    let mut components = Vec::new();
+    for component in decoded.split('/') {
+      if component.is_empty() || component == "." {
+        continue;
+      }
+      if component == ".."
+        || component.contains('\\')
+        || component.contains(':')
+        || component.contains('\0')
+      {
+        return Err(Rejection::Outside);
+      }
+      components.push(component);
+    }
+
+    let name = self.resolve_components(&components)?;
+    if !self.serves(&name) {
+      return Err(Rejection::NotFound);
+    }
+    Ok(name)
+  }
+
+  /// Whether the filters let a file be served. It is the name the request
+  /// resolved to that is judged, so a symlink to `.env` is as hidden as
+  /// `.env`, and a directory's index is judged as itself.
+  fn serves(&self, name: &str) -> bool {
+    filtered(name, self.dotfiles, &self.include, &self.exclude)
+  }
+
+  /// The name of the file some components reach, through the mount whose
+  /// prefix they fall under. A name carries its mount's prefix, so the cache
+  /// policy and the headers see the path the page asked for.
+  fn resolve_components(&self, components: &[&str]) -> Result<String, Rejection> {
+    let (mount, rest) = self.mount(components).ok_or(Rejection::NotFound)?;
+    let name = mount.source.resolve(rest)?;
+    Ok(
+      mount
+        .segments
+        .iter()
+        .map(String::as_str)
+        .chain(Some(name.as_str()).filter(|name| !name.is_empty()))
+        .collect::<Vec<_>>()
+        .join("/"),
+    )
+  }
+
+  /// The mount with the longest prefix some components fall under, and the
+  /// components that remain beneath it.
+  fn mount<'a, 'c>(
+    &'a self, components: &'c [&'c str],
+  ) -> Option<(&'a Mount, &'c [&'c str])> {
+    self.mounts.iter().find_map(|mount| {
+      let depth = mount.segments.len();
+      let under = components.len() >= depth
+        && mount
+          .segments
+          .iter()
+          .zip(components)
+          .all(|(segment, component)| segment == component);
+      under.then(|| (mount, &components[depth..]))
+    })
+  }
+
+  /// Opens a file `resolve` named, through its mount.
+  fn open(&self, name: &str) -> io::Result<Opened> {
+    let components: Vec<&str> = name.split('/').collect();
+    let (mount, rest) = self
+      .mount(&components)
+      .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+    let mut opened = mount.source.open(&rest.join("/"))?;
+    if let Some(manifest) = &self.integrity {
+      manifest.check(name, &mut opened)?;
+    }
+    Ok(opened)
+  }
+
+  /// Answers with a file inside the Root, or with the precompressed sibling
+  /// standing in for it when the request can take one. The sibling brings its
+  /// own bytes and validators; the content type and the cache policy stay the
+  /// original's, because that is what the page asked for.
+  fn serve_resolved(
+    &self, name: &str, request: &Request<Vec<u8>>,
+  ) -> io::Result<Response<Cow<'static, [u8]>>> {
+    let siblings = self.precompressed(name);
+    let chosen = header(request, ACCEPT_ENCODING).and_then(|accept_encoding| {
+      encoding::accepted(accept_encoding)
+        .into_iter()
+        .find_map(|wanted| siblings.iter().find(|(encoding, _)| *encoding == wanted))
+    });
+
+    let (served, coding) = match chosen {
+      Some((encoding, sibling)) => (sibling.as_str(), Some(encoding.name)),
+      None => (name, None),
+    };
+
+    let opened = self.open(served)?;
+    let mut response = serve_file(
+      opened,
+      self.content_type(name),
+      request,
+      self.stream_threshold,
+    )?;
+    let status = response.status();
+    let headers = response.headers_mut();
+    headers.insert(CACHE_CONTROL, self.cache_control(name));
+    if let Some(coding) = coding
+      && status != 416
+    {
+      headers.insert(CONTENT_ENCODING, HeaderValue::from_static(coding));
+    }
+    if !siblings.is_empty() {
+      headers.insert(VARY, HeaderValue::from_static("Accept-Encoding"));
+    }
+
+    Ok(response)
+  }
+
+  /// The precompressed siblings a file has inside the Root: `app.js.br` and
+  /// `app.js.gz` beside `app.js`. Each is resolved the way the file itself
+  /// was, so a sibling that is a symlink out of the Root is no sibling.
+  fn precompressed(&self, name: &str) -> Vec<(&'static Encoding, String)> {
+    PRECOMPRESSED
+      .iter()
+      .filter_map(|encoding| {
+        let extension = format!(".{}", encoding.extension);
+        let sibling = format!("{name}{extension}");
+        let components: Vec<&str> = sibling.split('/').collect();
+        let resolved = self.resolve_components(&components).ok()?;
+        resolved
+          .ends_with(&extension)
+          .then_some((encoding, resolved))
+      })
+      .collect()
+  }
+
+  /// The content type a file inside the Root is served with: the settings'
+  /// for the longest extension they name, else the built-in table's.
+  fn content_type(&self, name: &str) -> &str {
+    let file_name = name.rsplit('/').next().unwrap_or(name).to_ascii_lowercase();
+    file_name
+      .match_indices('.')
+      .find_map(|(at, _)| self.mime_types.get(&file_name[at + 1..]))
+      .map(String::as_str)
+      .unwrap_or_else(|| content_type(Path::new(name)))
+  }
+
+  /// The `Cache-Control` a file inside the Root is served with.
+  fn cache_control(&self, name: &str) -> HeaderValue {
+    self
+      .cache_control
+      .iter()
+      .find(|rule| rule.pattern.matches(name))
+      .map(|rule| rule.value.clone())
+      .unwrap_or(HeaderValue::from_static(DEFAULT_CACHE_CONTROL))
+  }
+}
+
+/// Whether the filters let a file be served: dotfiles only when they are on,
+/// one of the include patterns when there are any, and no exclude pattern.
+fn filtered(name: &str, dotfiles: bool, include: &[Glob], exclude: &[Glob]) -> bool {
+  let dotted = name.split('/').any(|segment| segment.starts_with('.'));
+  (dotfiles || !dotted)
+    && (include.is_empty() || include.iter().any(|glob| glob.matches(name)))
+    && !exclude.iter().any(|glob| glob.matches(name))
+}
+
+/// Answers with the file itself, the part of it a `Range` asked for, the `416`
+/// that says no part of it could be, or the `304` that says the copy the
+/// Webview already holds is still this file.
+///
+/// From a directory, only the bytes that are sent are read: a `<video>`
+/// seeking to the middle of a large file costs the range it asked for, not the
+/// file, and a revalidated bundle costs nothing but its metadata. A file
+/// longer than `stream_threshold` is sent no more than that much per range
+/// request, so even `bytes=0-` reads one chunk. A request with no `Range` at
+/// all is answered `200` with the whole file, since HTTP allows a `206` only
+/// to a request that asked for part. An archive entry is inflated whole, since
+/// deflate cannot be entered in the middle.
+///
+/// A `HEAD` reads nothing: it gets the headers a `GET` would, with the length
+/// of the body it would have had counted from the metadata and said outright.
+fn serve_file(
+  mut opened: Opened, content_type: &str, request: &Request<Vec<u8>>,
+  stream_threshold: u64,
+) -> io::Result<Response<Cow<'static, [u8]>>> {
+  let length = opened.length();
+  let validators = opened.validators().cloned();
+
+  let ranges = match &validators {
+    Some(validators) if not_modified(request, validators) => None,
+    _ => Some(requested_ranges(request, validators.as_ref(), length)),
+  }
+  .map(|ranges| match ranges {
+    // RFC 9110 lets a server send less than was asked, and one run of several.
+    Ranges::Satisfiable(ranges) if length > stream_threshold => {
+      Ranges::Satisfiable(vec![ranges[0].at_most(stream_threshold)])
+    },
+    ranges => ranges,
+  });
+
+  let head = request.method() == Method::HEAD;
+  let mut response = match ranges {
+    None => respond(304, content_type, Cow::Borrowed(&[])),
+    Some(Ranges::Whole) if head => bodiless(200, content_type, length),
+    Some(Ranges::Whole) => respond(200, content_type, Cow::Owned(opened.read_all()?)),
+    Some(Ranges::Unsatisfiable) => {
+      let mut response = respond(
+        416,
+        TEXT,
+        Cow::Owned(
+          format!("Range not satisfiable: {}", request.uri().path()).into_bytes(),
+        ),
+      );
+      insert(&mut response, CONTENT_RANGE, &format!("bytes */{length}"));
+      response
+    },
+    Some(Ranges::Satisfiable(ranges)) => match ranges.as_slice() {
+      [single] => {
+        let mut response = if head {
+          bodiless(206, content_type, single.len())
+        } else {
+          respond(206, content_type, Cow::Owned(opened.read_range(single)?))
+        };
+        insert(&mut response, CONTENT_RANGE, &single.content_range(length));
+        response
+      },
+      several if head => bodiless(
+        206,
+        &range::multipart_type(),
+        range::multipart_length(content_type, length, several),
+      ),
+      several => {
+        let mut parts = Vec::with_capacity(several.len());
+        for range in several {
+          parts.push((*range, opened.read_range(range)?));
+        }
+        respond(
+          206,
+          &range::multipart_type(),
+          Cow::Owned(range::multipart_body(content_type, length, &parts)),
+        )
+      },
+    },
+  };
+
+  insert(&mut response, ACCEPT_RANGES, "bytes");
+  if let Some(validators) = &validators {
+    insert(&mut response, ETAG, &validators.etag);
+    insert(&mut response, LAST_MODIFIED, &validators.last_modified());
+  }
+
+  Ok(response)
+}
+
+/// A mount's prefix as segments, refusing one that a request path could never
+/// fall under or that would climb out of the Root.
+fn prefix_segments(prefix: &str) -> Result<Vec<String>, String> {
+  if !prefix.starts_with('/') {
+    return Err(format!(
+      "'{prefix}' is not a URL prefix: it must start with '/'."
+    ));
+  }
+  prefix
+    .split('/')
+    .filter(|segment| !segment.is_empty())
+    .map(|segment| {
+      if segment == "."
+        || segment == ".."
+        || segment.contains(['\\', ':', '\0', '%', '?', '#'])
+      {
+        Err(format!("'{prefix}' is not a URL prefix a Root can mount."))
+      } else {
+        Ok(segment.to_string())
+      }
+    })
+    .collect()
+}
+
+/// Headers as the settings name them, read into what a response carries.
+fn header_pairs(
+  headers: Vec<(String, String)>,
+) -> Result<Vec<(HeaderName, HeaderValue)>, String> {
+  headers
+    .into_iter()
+    .map(|(name, value)| {
+      let header = HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| format!("'{name}' is not a header name."))?;
+      if COMPUTED_HEADERS.contains(&header) {
+        return Err(format!(
+          "{name} is worked out for every response and cannot be set."
+        ));
+      }
+      let value = HeaderValue::from_str(&value).map_err(|_| {
+        format!("The value of {name} is not a header value: {value:?}.")
+      })?;
+      Ok((header, value))
+    })
+    .collect()
+}
+
+/// Whether the copy a request says it holds is still the file on disk.
+///
+/// `If-None-Match` is asked first and, when it is there, alone, as RFC 9110
+/// orders it; `If-Modified-Since` only speaks for a request that sent no tag.
+/// Only a read is answered this way, which is every request a Webview makes of
+/// a Root.
+fn not_modified(request: &Request<Vec<u8>>, validators: &Validators) -> bool {
+  if request.method() != Method::GET && request.method() != Method::HEAD {
+    return false;
+  }
+  if let Some(tags) = header(request, IF_NONE_MATCH) {
+    return validators.satisfy_if_none_match(tags);
+  }
+  header(request, IF_MODIFIED_SINCE)
+    .is_some_and(|since| validators.unmodified_since(since))
+}
+
+/// The ranges a request asked for, if it asked for any that still apply.
+///
+/// An `If-Range` that no longer matches the file means the client's copy is
+/// stale, and the ranges it asks for would be stitched onto the wrong bytes, so
+/// the whole file is sent instead — exactly what RFC 9110 prescribes.
+fn requested_ranges(
+  request: &Request<Vec<u8>>, validators: Option<&Validators>, length: u64,
+) -> Ranges {
+  let Some(range) = header(request, RANGE) else {
+    return Ranges::Whole;
+  };
+
+  if let Some(condition) = header(request, IF_RANGE)
+    && !validators.is_some_and(|validators| validators.satisfy_if_range(condition))
+  {
+    return Ranges::Whole;
+  }
+
+  range::parse(range, length)
+}
+
+/// A request header as text, or `None` when it is absent or not text.
+fn header(request: &Request<Vec<u8>>, name: HeaderName) -> Option<&str> {
+  request.headers().get(name)?.to_str().ok()
+}
+
+/// Sets a response header. A value that cannot be a header is left off rather
+/// than failing the response it would have decorated.
+fn insert(response: &mut Response<Cow<'static, [u8]>>, name: HeaderName, value: &str) {
+  if let Ok(value) = HeaderValue::from_str(value) {
+    response.headers_mut().insert(name, value);
+  }
+}
+
+/// The answer to a `HEAD`: no body, and the length the body would have had.
+fn bodiless(
+  status: u16, content_type: &str, length: u64,
+) -> Response<Cow<'static, [u8]>> {
+  let mut response = respond(status, content_type, Cow::Borrowed(&[]));
+  insert(&mut response, CONTENT_LENGTH, &length.to_string());
+  response
+}
+
+fn respond(
+  status: u16, content_type: &str, body: Cow<'static, [u8]>,
+) -> Response<Cow<'static, [u8]>> {
+  Response::builder()
+    .status(status)
+    .header(CONTENT_TYPE, content_type)
+    .body(body)
+    .expect("Failed to build response")
+}
+
+fn not_found(request_path: &str) -> Cow<'static, [u8]> {
+  Cow::Owned(format!("Not found: {request_path}").into_bytes())
+}
+
+/// The content type of a file, read from its extension by the built-in table.
+/// An extension we do not know gets `application/octet-stream` rather than a
+/// guess at the bytes.
+fn content_type(path: &Path) -> &'static str {
+  let extension = path
+    .extension()
+    .and_then(|extension| extension.to_str())
+    .unwrap_or_default()
+    .to_ascii_lowercase();
+
+  match extension.as_str() {
+    "html" | "htm" => "text/html; charset=utf-8",
+    "xhtml" => "application/xhtml+xml; charset=utf-8",
+    "js" | "mjs" | "cjs" => "text/javascript; charset=utf-8",
+    // TypeScript, as a source map points the inspector at it. A Root holding
+    // MPEG transport streams instead can say so in its MIME types.
+    "ts" | "mts" | "cts" | "tsx" => "text/typescript; charset=utf-8",
+    "css" => "text/css; charset=utf-8",
+    "json" | "map" => "application/json; charset=utf-8",
+    "jsonld" => "application/ld+json; charset=utf-8",
+    "webmanifest" => "application/manifest+json; charset=utf-8",
+    "geojson" => "application/geo+json; charset=utf-8",
+    "txt" => "text/plain; charset=utf-8",
+    "md" | "markdown" => "text/markdown; charset=utf-8",
+    "csv" => "text/csv; charset=utf-8",
+    "vtt" => "text/vtt; charset=utf-8",
+    "ics" => "text/calendar; charset=utf-8",
+    "wgsl" => "text/wgsl; charset=utf-8",
+    "yaml" | "yml" => "application/yaml; charset=utf-8",
+    "toml" => "application/toml; charset=utf-8",
+    "xml" => "application/xml; charset=utf-8",
+    "rss" => "application/rss+xml; charset=utf-8",
+    "atom" => "application/atom+xml; charset=utf-8",
+    "wasm" => "application/wasm",
+    "pdf" => "application/pdf",
+    "zip" => "application/zip",
+    "gz" => "application/gzip",
+    "tar" => "application/x-tar",
+    "svg" => "image/svg+xml",
+    "png" => "image/png",
+    "apng" => "image/apng",
+    "jpg" | "jpeg" => "image/jpeg",
+    "gif" => "image/gif",
+    "webp" => "image/webp",
+    "avif" => "image/avif",
+    "jxl" => "image/jxl",
+    "bmp" => "image/bmp",
+    "tif" | "tiff" => "image/tiff",
+    "ico" | "cur" => "image/x-icon",
+    "ktx2" => "image/ktx2",
+    "woff" => "font/woff",
+    "woff2" => "font/woff2",
+    "ttf" => "font/ttf",
+    "otf" => "font/otf",
+    "eot" => "application/vnd.ms-fontobject",
+    "mp3" => "audio/mpeg",
+    "wav" => "audio/wav",
+    "ogg" | "oga" | "opus" => "audio/ogg",
+    "flac" => "audio/flac",
+    "aac" => "audio/aac",
+    "m4a" => "audio/mp4",
+    "weba" => "audio/webm",
+    "mp4" | "m4v" => "video/mp4",
+    "webm" => "video/webm",
+    "ogv" => "video/ogg",
+    "mov" => "video/quicktime",
+    "m3u8" => "application/vnd.apple.mpegurl",
+    "mpd" => "application/dash+xml",
+    "glb" => "model/gltf-binary",
+    "gltf" => "model/gltf+json",
+    "obj" => "model/obj",
+    "stl" => "model/stl",
+    "usdz" => "model/vnd.usdz+zip",
+    _ => "application/octet-stream",
+  }
+}
+
+/// Reads the percent-escapes out of a request path, so a file whose name holds
+/// a space or a non-ASCII character is found on disk.
+fn percent_decode(input: &str) -> String {
+  fn hex(byte: u8) -> Option<u8> {
+    match byte {
+      b'0'..=b'9' => Some(byte - b'0'),
+      b'a'..=b'f' => Some(byte - b'a' + 10),
+      b'A'..=b'F' => Some(byte - b'A' + 10),
+      _ => None,
+    }
+  }
+
+  let bytes = input.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut index = 0;
+
+  while index < bytes.len() {
+    if bytes[index] == b'%'
+      && let Some(&high) = bytes.get(index + 1)
+      && let Some(&low) = bytes.get(index + 2)
+      && let (Some(high), Some(low)) = (hex(high), hex(low))
+    {
+      decoded.push(high * 16 + low);
+      index += 3;
+      continue;
+    }
+    decoded.push(bytes[index]);
+    index += 1;
+  }
+
+  String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,297 @@
+//! A zip archive read as a Root: the whole compiled frontend shipped as one
+//! file, and served without being unpacked anywhere.
+//!
+//! Only the central directory is read when the archive is opened, into an
+//! index of names; an entry's bytes are read when a request asks for them.
+//! That is enough of the format for what bundlers and `zipfile` write —
+//! stored and deflated entries, unencrypted, in an archive under 4 GiB. Zip64
+//! and anything else are refused when the archive is opened, not on the
+//! request that happens to reach them.
+//!
+//! An archive need not start at the top of its file. A zip appended to an
+//! executable, the way `zipapp` or a self-extracting bundle is built, records
+//! offsets from its own start; the distance between where its central
+//! directory is and where it says it is is the length of what precedes it,
+//! and every offset is moved by that much — as `zipfile` does.
+//!
+//! A Root may also be one directory inside an archive, the way a package
+//! imported from a zip sees its own files. Only the entries beneath it are
+//! indexed, under names relative to it, so nothing outside it can be named.
+
+use miniz_oxide::inflate::decompress_to_vec_with_limit;
+use std::{
+  collections::{HashMap, HashSet},
+  fs::File,
+  io::{self, Read, Seek, SeekFrom},
+  path::{Path, PathBuf},
+};
+
+use super::validators::{Validators, days_from_civil};
+
+#[cfg(test)]
+mod tests;
+
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const CENTRAL_DIRECTORY_HEADER: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+
+/// The fixed part of each record; a name, extra field and comment follow.
+const END_LENGTH: usize = 22;
+const CENTRAL_LENGTH: usize = 46;
+const LOCAL_LENGTH: usize = 30;
+
+/// The longest comment an archive may end with, which bounds how far from the
+/// end of the file its last record can sit.
+const MAX_COMMENT: usize = u16::MAX as usize;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+/// Bit 0 of an entry's flags: its content is encrypted.
+const ENCRYPTED: u16 = 0x0001;
+
+/// A zip archive's index, and where on disk to read its entries from.
+pub struct Archive {
+  path: PathBuf,
+  /// How many bytes precede the archive in its file.
+  offset: u64,
+  /// The directory inside the archive being served, `/`-terminated, or empty
+  /// for the whole of it.
+  within: String,
+  entries: HashMap<String, Entry>,
+  directories: HashSet<String>,
+}
+
+/// What the central directory records about one file in the archive.
+struct Entry {
+  method: u16,
+  crc: u32,
+  compressed: u64,
+  length: u64,
+  header: u64,
+  modified: u64,
+}
+
+impl Archive {
+  /// Reads the index of the archive at a path, or of one directory inside it,
+  /// or says why it cannot be served.
+  pub fn open(path: &Path, within: &str) -> Result<Self, String> {
+    let unreadable =
+      |error: io::Error| format!("{} could not be read: {error}", path.display());
+    let mut file = File::open(path).map_err(unreadable)?;
+    let size = file.metadata().map_err(unreadable)?.len();
+
+    let tail_length = size.min((END_LENGTH + MAX_COMMENT) as u64);
+    let mut tail = vec![0; tail_length as usize];
+    file
+      .seek(SeekFrom::Start(size - tail_length))
+      .and_then(|_| file.read_exact(&mut tail))
+      .map_err(unreadable)?;
+
+    let not_a_zip = || format!("{} is not a zip archive.", path.display());
+    let end = find_end(&tail).ok_or_else(not_a_zip)?;
+    let record = &tail[end..];
+    let count = u16_at(record, 10);
+    let directory_length = u32_at(record, 12);
+    let directory_offset = u32_at(record, 16);
+    if count == u16::MAX || directory_length == u32::MAX || directory_offset == u32::MAX
+    {
+      return Err(format!(
+        "{} is a Zip64 archive, which cannot be served.",
+        path.display()
+      ));
+    }
+
+    let end_position = size - tail_length + end as u64;
+    let offset = end_position
+      .checked_sub(u64::from(directory_length) + u64::from(directory_offset))
+      .ok_or_else(not_a_zip)?;
+
+    let mut directory = vec![0; directory_length as usize];
+    file
+      .seek(SeekFrom::Start(offset + u64::from(directory_offset)))
+      .and_then(|_| file.read_exact(&mut directory))
+      .map_err(unreadable)?;
+
+    let within = within.trim_matches('/');
+    let mut archive = Archive {
+      path: path.to_path_buf(),
+      offset,
+      within: match within {
+        "" => String::new(),
+        within => format!("{within}/"),
+      },
+      entries: HashMap::new(),
+      directories: HashSet::new(),
+    };
+    archive.index(&directory)?;
+    if !archive.is_dir("") {
+      return Err(format!(
+        "'{within}' is not a directory in {}.",
+        path.display()
+      ));
+    }
+    Ok(archive)
+  }
+
+  /// Reads every record of the central directory into the index, refusing
+  /// the archive at the first entry it could not serve.
+  fn index(&mut self, directory: &[u8]) -> Result<(), String> {
+    let path = self.path.display().to_string();
+    let malformed = || format!("{path} has a central directory that cannot be read.");
+    let mut at = 0;
+    while at < directory.len() {
+      let record = directory
+        .get(at..at + CENTRAL_LENGTH)
+        .filter(|record| u32_at(record, 0) == CENTRAL_DIRECTORY_HEADER)
+        .ok_or_else(malformed)?;
+      let flags = u16_at(record, 8);
+      let method = u16_at(record, 10);
+      let name_length = usize::from(u16_at(record, 28));
+      let extra_length = usize::from(u16_at(record, 30));
+      let comment_length = usize::from(u16_at(record, 32));
+      let name_start = at + CENTRAL_LENGTH;
+      let name = directory
+        .get(name_start..name_start + name_length)
+        .ok_or_else(malformed)?;
+      let name = String::from_utf8_lossy(name).replace('\\', "/");
+      at = name_start + name_length + extra_length + comment_length;
+
+      let Some(name) = name.strip_prefix(&self.within).map(str::to_string) else {
+        continue;
+      };
+      // Anything at all beneath the directory served is what makes it one.
+      self.directories.insert(String::new());
+      if name.is_empty() {
+        continue;
+      }
+
+      let entry = Entry {
+        method,
+        crc: u32_at(record, 16),
+        compressed: u64::from(u32_at(record, 20)),
+        length: u64::from(u32_at(record, 24)),
+        header: u64::from(u32_at(record, 42)),
+        modified: dos_time(u16_at(record, 14), u16_at(record, 12)),
+      };
+
+      self.add_parents(&name);
+      if let Some(folder) = name.strip_suffix('/') {
+        self.directories.insert(folder.to_string());
+        continue;
+      }
+      if flags & ENCRYPTED != 0 {
+        return Err(format!("'{name}' is encrypted, which cannot be served."));
+      }
+      if method != STORED && method != DEFLATED {
+        return Err(format!(
+          "'{name}' is compressed with method {method}; only stored and deflated \
+           entries can be served."
+        ));
+      }
+      if entry.compressed == u64::from(u32::MAX)
+        || entry.length == u64::from(u32::MAX)
+        || entry.header == u64::from(u32::MAX)
+      {
+        return Err(format!(
+          "'{name}' is a Zip64 entry, which cannot be served."
+        ));
+      }
+      self.entries.insert(name, entry);
+    }
+    Ok(())
+  }
+
+  /// Records every directory above a name, since an archive need not list
+  /// them as entries of their own.
+  fn add_parents(&mut self, name: &str) {
+    let mut parent = name.trim_end_matches('/');
+    while let Some((above, _)) = parent.rsplit_once('/') {
+      self.directories.insert(above.to_string());
+      parent = above;
+    }
+  }
+
+  /// Whether a name is a file in the archive.
+  pub fn is_file(&self, name: &str) -> bool {
+    self.entries.contains_key(name)
+  }
+
+  /// Whether a name is a directory in the archive. The top of the whole
+  /// archive is always one, even empty; the top of a directory inside it is
+  /// one when the archive holds it.
+  pub fn is_dir(&self, name: &str) -> bool {
+    (name.is_empty() && self.within.is_empty()) || self.directories.contains(name)
+  }
+
+  /// The content of a file in the archive, and its validators.
+  pub fn read(&self, name: &str) -> io::Result<(Vec<u8>, Validators)> {
+    let entry = self
+      .entries
+      .get(name)
+      .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+
+    let mut file = File::open(&self.path)?;
+    let mut header = [0; LOCAL_LENGTH];
+    file.seek(SeekFrom::Start(self.offset + entry.header))?;
+    file.read_exact(&mut header)?;
+    if u32_at(&header, 0) != LOCAL_FILE_HEADER {
+      return Err(invalid(name, "has no local header where the index says"));
+    }
+    let skip = u64::from(u16_at(&header, 26)) + u64::from(u16_at(&header, 28));
+    file.seek(SeekFrom::Current(skip as i64))?;
+
+    let mut stored = vec![0; entry.compressed as usize];
+    file.read_exact(&mut stored)?;
+
+    let content = match entry.method {
+      DEFLATED => decompress_to_vec_with_limit(&stored, entry.length as usize)
+        .map_err(|_| invalid(name, "does not inflate"))?,
+      _ => stored,
+    };
+    if content.len() as u64 != entry.length {
+      return Err(invalid(name, "is not the length the index says"));
+    }
+
+    let validators = Validators::of_entry(entry.length, entry.crc, entry.modified);
+    Ok((content, validators))
+  }
+}
+
+/// Where the end-of-central-directory record starts in the tail of a file:
+/// the last signature whose comment runs exactly to the end, so a comment
+/// that happens to contain the signature is not mistaken for the record.
+fn find_end(tail: &[u8]) -> Option<usize> {
+  (0..=tail.len().checked_sub(END_LENGTH)?).rev().find(|&at| {
+    u32_at(tail, at) == END_OF_CENTRAL_DIRECTORY
+      && at + END_LENGTH + usize::from(u16_at(tail, at + 20)) == tail.len()
+  })
+}
+
+/// An MS-DOS date and time, as a zip stores them, in seconds since the epoch.
+/// They carry no zone, so they are read as UTC; what matters to a validator is
+/// that the same entry always gives the same answer.
+fn dos_time(date: u16, time: u16) -> u64 {
+  let year = 1980 + u64::from(date >> 9);
+  let month = u64::from((date >> 5) & 0x0f);
+  let day = u64::from(date & 0x1f);
+  if !(1..=12).contains(&month) || day == 0 {
+    return 0;
+  }
+  let hour = u64::from(time >> 11);
+  let minute = u64::from((time >> 5) & 0x3f);
+  let second = u64::from(time & 0x1f) * 2;
+  days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second
+}
+
+fn invalid(name: &str, reason: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, format!("'{name}' {reason}."))
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> u16 {
+  u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+  u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
@@ -0,0 +1,161 @@
+//! Tests for reading a zip archive's index and entries, against archives the
+//! Root tests' writer builds.
+
+use super::*;
+use crate::root::tests::zip;
+use std::{
+  fs,
+  sync::atomic::{AtomicU32, Ordering},
+};
+
+/// An archive written to a temporary file, removed when the test drops it.
+struct Written {
+  path: PathBuf,
+}
+
+impl Written {
+  fn new(bytes: &[u8]) -> Self {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let path = std::env::temp_dir().join(format!(
+      "dry-archive-{}-{}.zip",
+      std::process::id(),
+      COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&path, bytes).expect("the archive should be written");
+    Written { path }
+  }
+
+  fn open(&self) -> Result<Archive, String> {
+    Archive::open(&self.path, "")
+  }
+
+  fn open_within(&self, within: &str) -> Result<Archive, String> {
+    Archive::open(&self.path, within)
+  }
+}
+
+impl Drop for Written {
+  fn drop(&mut self) {
+    let _ = fs::remove_file(&self.path);
+  }
+}
+
+fn content(archive: &Archive, name: &str) -> String {
+  let (content, _) = archive.read(name).expect("the entry should be read");
+  String::from_utf8(content).expect("the entry should be text")
+}
+
+#[test]
+fn stored_and_deflated_entries_read_back() {
+  for deflate in [false, true] {
+    let written =
+      Written::new(&zip(&[("a.txt", "alpha"), ("b/c.txt", "gamma")], deflate));
+    let archive = written.open().expect("the archive should be read");
+
+    assert_eq!(content(&archive, "a.txt"), "alpha");
+    assert_eq!(content(&archive, "b/c.txt"), "gamma");
+  }
+}
+
+#[test]
+fn directories_are_known_without_entries_of_their_own() {
+  let written = Written::new(&zip(&[("a/b/c.txt", "x"), ("d/", "")], false));
+  let archive = written.open().expect("the archive should be read");
+
+  assert!(archive.is_dir(""));
+  assert!(archive.is_dir("a"));
+  assert!(archive.is_dir("a/b"));
+  assert!(archive.is_dir("d"));
+  assert!(!archive.is_dir("a/b/c.txt"));
+  assert!(archive.is_file("a/b/c.txt"));
+  assert!(!archive.is_file("a/b"));
+}
+
+#[test]
+fn an_entry_tag_follows_its_content() {
+  let first = Written::new(&zip(&[("a.txt", "alpha")], true));
+  let second = Written::new(&zip(&[("a.txt", "omega")], true));
+
+  let (_, first) = first.open().unwrap().read("a.txt").unwrap();
+  let (_, second) = second.open().unwrap().read("a.txt").unwrap();
+  assert_ne!(first.etag, second.etag);
+  assert_eq!(first.modified, second.modified);
+}
+
+#[test]
+fn a_comment_holding_the_signature_is_not_the_record() {
+  let mut bytes = zip(&[("a.txt", "alpha")], false);
+  let comment = b"built by PK\x05\x06, which is not a record at all".to_vec();
+  let at = bytes.len() - 2;
+  bytes[at..].copy_from_slice(&(comment.len() as u16).to_le_bytes());
+  bytes.extend(&comment);
+
+  let written = Written::new(&bytes);
+  let archive = written.open().expect("the archive should be read");
+  assert_eq!(content(&archive, "a.txt"), "alpha");
+}
+
+#[test]
+fn an_entry_that_cannot_be_served_refuses_the_archive() {
+  let bytes = zip(&[("a.txt", "alpha")], false);
+  let central = bytes
+    .windows(4)
+    .position(|window| window == CENTRAL_DIRECTORY_HEADER.to_le_bytes())
+    .expect("the archive should have a central directory");
+
+  let mut encrypted = bytes.clone();
+  encrypted[central + 8] |= ENCRYPTED as u8;
+  assert!(Written::new(&encrypted).open().is_err());
+
+  let mut bzip2 = bytes.clone();
+  bzip2[central + 10] = 12;
+  assert!(Written::new(&bzip2).open().is_err());
+}
+
+#[test]
+fn a_truncated_archive_is_refused() {
+  let bytes = zip(&[("a.txt", "alpha")], false);
+  assert!(Written::new(&bytes[..bytes.len() - 4]).open().is_err());
+  assert!(Written::new(b"").open().is_err());
+}
+
+#[test]
+fn a_directory_inside_is_served_as_the_top() {
+  let written = Written::new(&zip(
+    &[
+      ("myapp/__init__.py", ""),
+      ("myapp/frontend/dist/index.html", "<h1>Packaged</h1>"),
+      ("myapp/frontend/dist/assets/app.js", "x"),
+    ],
+    true,
+  ));
+
+  for within in ["myapp/frontend/dist", "/myapp/frontend/dist/"] {
+    let archive = written
+      .open_within(within)
+      .expect("the directory should be read");
+    assert_eq!(content(&archive, "index.html"), "<h1>Packaged</h1>");
+    assert!(archive.is_dir(""));
+    assert!(archive.is_dir("assets"));
+    assert!(archive.is_file("assets/app.js"));
+    assert!(!archive.is_file("myapp/__init__.py"));
+    assert!(!archive.is_file("../../__init__.py"));
+  }
+}
+
+#[test]
+fn a_directory_the_archive_does_not_hold_is_refused() {
+  let written = Written::new(&zip(&[("myapp/index.html", "x")], false));
+
+  assert!(written.open_within("other").is_err());
+  assert!(written.open_within("myapp/index.html").is_err());
+}
+
+#[test]
+fn dos_times_are_read_as_utc() {
+  // 2024-01-01 12:34:56.
+  let date = (44 << 9) | (1 << 5) | 1;
+  let time = (12 << 11) | (34 << 5) | (56 / 2);
+  assert_eq!(dos_time(date, time), 1_704_112_496);
+  assert_eq!(dos_time(0, 0), 0);
+}
@@ -0,0 +1,69 @@
+//! Precompressed siblings: `app.js.br` and `app.js.gz` served in place of
+//! `app.js` to a Webview that says it can decode them.
+//!
+//! Nothing is compressed here. A bundler already did it, once, at build time;
+//! the Root only chooses which of the files it finds to send, from the request's
+//! `Accept-Encoding`. A Webview that does not advertise an encoding for its
+//! custom-protocol requests is never sent one, which is what keeps a platform
+//! that hands the bytes to the page undecoded from rendering a `.br` as text.
+
+#[cfg(test)]
+mod tests;
+
+/// One content coding a sibling may be stored in.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Encoding {
+  /// The name `Accept-Encoding` and `Content-Encoding` use.
+  pub name: &'static str,
+  /// The extension the sibling carries after the original's own.
+  pub extension: &'static str,
+}
+
+/// The codings looked for, in the order they are preferred when a request
+/// accepts both equally. Brotli first: it is the smaller of the two for every
+/// bundle a frontend build emits.
+pub const PRECOMPRESSED: [Encoding; 2] = [
+  Encoding {
+    name: "br",
+    extension: "br",
+  },
+  Encoding {
+    name: "gzip",
+    extension: "gz",
+  },
+];
+
+/// The codings a request accepts, best first, out of those a sibling may be
+/// stored in. A coding it gives a quality of zero, or does not name and does
+/// not cover with `*`, is left out.
+pub fn accepted(accept_encoding: &str) -> Vec<&'static Encoding> {
+  let mut accepted: Vec<(&'static Encoding, f32)> = PRECOMPRESSED
+    .iter()
+    .map(|encoding| (encoding, quality(accept_encoding, encoding.name)))
+    .filter(|(_, quality)| *quality > 0.0)
+    .collect();
+  // Stable, so a tie keeps the order of preference above.
+  accepted.sort_by(|a, b| b.1.total_cmp(&a.1));
+  accepted.into_iter().map(|(encoding, _)| encoding).collect()
+}
+
+/// The quality `Accept-Encoding` gives one coding: its own entry if it has
+/// one, otherwise `*`'s, otherwise none at all.
+fn quality(accept_encoding: &str, coding: &str) -> f32 {
+  let mut wildcard = 0.0;
+  for entry in accept_encoding.split(',') {
+    let mut parameters = entry.split(';').map(str::trim);
+    let name = parameters.next().unwrap_or_default();
+    let quality = parameters
+      .find_map(|parameter| parameter.strip_prefix("q="))
+      .map_or(Some(1.0), |value| value.parse::<f32>().ok())
+      .unwrap_or(0.0);
+    if name.eq_ignore_ascii_case(coding) {
+      return quality;
+    }
+    if name == "*" {
+      wildcard = quality;
+    }
+  }
+  wildcard
+}
@@ -0,0 +1,41 @@
+//! Tests for choosing a precompressed sibling from a request's
+//! `Accept-Encoding`.
+
+use super::*;
+
+fn names(accept_encoding: &str) -> Vec<&'static str> {
+  accepted(accept_encoding)
+    .into_iter()
+    .map(|encoding| encoding.name)
+    .collect()
+}
+
+#[test]
+fn brotli_is_preferred_when_both_are_accepted_equally() {
+  assert_eq!(names("gzip, deflate, br"), ["br", "gzip"]);
+  assert_eq!(names("br, gzip"), ["br", "gzip"]);
+}
+
+#[test]
+fn a_higher_quality_wins_over_the_preference() {
+  assert_eq!(names("br;q=0.5, gzip"), ["gzip", "br"]);
+}
+
+#[test]
+fn a_coding_refused_or_not_named_is_left_out() {
+  assert_eq!(names("gzip"), ["gzip"]);
+  assert_eq!(names("br;q=0, gzip"), ["gzip"]);
+  assert_eq!(names("identity"), Vec::<&str>::new());
+  assert_eq!(names(""), Vec::<&str>::new());
+}
+
+#[test]
+fn a_wildcard_covers_what_is_not_named() {
+  assert_eq!(names("*"), ["br", "gzip"]);
+  assert_eq!(names("gzip;q=0, *"), ["br"]);
+}
+
+#[test]
+fn names_are_read_without_regard_to_case() {
+  assert_eq!(names("GZIP"), ["gzip"]);
+}
@@ -0,0 +1,80 @@
+//! The patterns a Root's settings name files by: `*.html`, `assets/**`,
+//! `fonts/*.woff2`.
+//!
+//! The rules are gitignore's, minus negation. `*` matches within one path
+//! segment, `**` across any number of them, `?` one character that is not a
+//! separator. A pattern with no `/` in it names a file wherever it sits, so
+//! `*.map` reaches `assets/app.js.map`; one with a `/` is anchored at the top
+//! of the Root, leading slash or not. A trailing `/` names a directory and
+//! everything beneath it, so `drafts/` is `**/drafts/**` and `docs/old/` is
+//! `docs/old/**`.
+
+#[cfg(test)]
+mod tests;
+
+/// One pattern, checked once when the settings are read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+  pattern: String,
+  anchored: bool,
+}
+
+impl Glob {
+  /// Reads a pattern, refusing one that could never name a file.
+  pub fn new(pattern: &str) -> Result<Self, String> {
+    let trimmed = pattern.strip_prefix('/').unwrap_or(pattern);
+    let directory = trimmed.trim_end_matches('/');
+    if directory.is_empty() {
+      return Err(format!("'{pattern}' is not a pattern that names a file."));
+    }
+    if directory.len() < trimmed.len() {
+      // A file is never named with a trailing slash, so as it stands the
+      // pattern would match nothing: it means what is inside the directory.
+      let anywhere = !pattern.starts_with('/') && !directory.contains('/');
+      return Ok(Glob {
+        pattern: if anywhere {
+          format!("**/{directory}/**")
+        } else {
+          format!("{directory}/**")
+        },
+        anchored: true,
+      });
+    }
+    Ok(Glob {
+      pattern: trimmed.to_string(),
+      anchored: pattern.contains('/'),
+    })
+  }
+
+  /// Whether the pattern names a path, written relative to the top of the Root
+  /// with `/` between its segments.
+  pub fn matches(&self, relative: &str) -> bool {
+    let target = if self.anchored {
+      relative
+    } else {
+      relative.rsplit('/').next().unwrap_or(relative)
+    };
+    wildcard(self.pattern.as_bytes(), target.as_bytes())
+  }
+}
+
+fn wildcard(pattern: &[u8], text: &[u8]) -> bool {
+  match pattern {
+    [] => text.is_empty(),
+    [b'*', b'*', b'/', rest @ ..] => (0..=text.len())
+      .filter(|&at| at == 0 || text[at - 1] == b'/')
+      .any(|at| wildcard(rest, &text[at..])),
+    [b'*', b'*', rest @ ..] => (0..=text.len()).any(|at| wildcard(rest, &text[at..])),
+    [b'*', rest @ ..] => {
+      let segment = text
+        .iter()
+        .position(|&byte| byte == b'/')
+        .unwrap_or(text.len());
+      (0..=segment).any(|at| wildcard(rest, &text[at..]))
+    },
+    [b'?', rest @ ..] => {
+      matches!(text.first(), Some(&byte) if byte != b'/') && wildcard(rest, &text[1..])
+    },
+    [literal, rest @ ..] => text.first() == Some(literal) && wildcard(rest, &text[1..]),
+  }
+}
@@ -0,0 +1,77 @@
+//! Tests for the patterns a Root's settings name files by.
+
+use super::*;
+
+fn glob(pattern: &str) -> Glob {
+  Glob::new(pattern).expect("the pattern should be read")
+}
+
+#[test]
+fn a_pattern_without_a_slash_names_a_file_anywhere() {
+  let html = glob("*.html");
+  assert!(html.matches("index.html"));
+  assert!(html.matches("nested/deeper/page.html"));
+  assert!(!html.matches("index.htm"));
+
+  assert!(glob("index.html").matches("nested/index.html"));
+}
+
+#[test]
+fn a_pattern_with_a_slash_is_anchored_at_the_top() {
+  let assets = glob("assets/*.js");
+  assert!(assets.matches("assets/app.js"));
+  assert!(!assets.matches("assets/vendor/app.js"));
+  assert!(!assets.matches("other/assets/app.js"));
+
+  assert!(glob("/index.html").matches("index.html"));
+  assert!(!glob("/index.html").matches("nested/index.html"));
+}
+
+#[test]
+fn a_double_star_crosses_segments() {
+  let everything = glob("assets/**");
+  assert!(everything.matches("assets/app.js"));
+  assert!(everything.matches("assets/vendor/lib/x.css"));
+  assert!(!everything.matches("index.html"));
+
+  let any_depth = glob("assets/**/x.js");
+  assert!(any_depth.matches("assets/x.js"));
+  assert!(any_depth.matches("assets/a/b/x.js"));
+  assert!(!any_depth.matches("assets/a/bx.js"));
+}
+
+#[test]
+fn a_question_mark_is_one_character_of_a_name() {
+  let numbered = glob("page?.html");
+  assert!(numbered.matches("page1.html"));
+  assert!(!numbered.matches("page10.html"));
+  assert!(!glob("a?b").matches("a/b"));
+}
+
+/// A trailing slash names a directory, and so everything inside it: as
+/// gitignore reads it, anywhere when that is the only slash, anchored when
+/// there is another.
+#[test]
+fn a_trailing_slash_names_what_is_inside_the_directory() {
+  let drafts = glob("drafts/");
+  assert!(drafts.matches("drafts/post.html"));
+  assert!(drafts.matches("blog/drafts/2024/post.html"));
+  assert!(!drafts.matches("drafts.html"));
+  assert!(!drafts.matches("blog/mydrafts/post.html"));
+
+  let old = glob("docs/old/");
+  assert!(old.matches("docs/old/a.html"));
+  assert!(old.matches("docs/old/deeper/a.html"));
+  assert!(!old.matches("site/docs/old/a.html"));
+
+  assert!(glob("/drafts/").matches("drafts/post.html"));
+  assert!(!glob("/drafts/").matches("blog/drafts/post.html"));
+  assert_eq!(glob("assets//"), glob("assets/"));
+}
+
+#[test]
+fn an_empty_pattern_is_refused() {
+  assert!(Glob::new("").is_err());
+  assert!(Glob::new("/").is_err());
+  assert!(Glob::new("//").is_err());
+}
@@ -0,0 +1,133 @@
+//! Checking what a Root serves against a manifest of what was shipped.
+//!
+//! A frontend installed beside the Python code sits on a disk its users
+//! control, where an update can stop halfway and a file can be edited in
+//! place. The manifest is the SHA-256 of every file the build produced,
+//! written by `dry.write_manifest`, and a Root that has one serves nothing
+//! that does not match it: a page running half of last version's JavaScript
+//! fails loudly instead of strangely.
+//!
+//! A file of up to `REHASH_LIMIT` bytes, which is nearly every file a build
+//! produces, is hashed whole each time it is served. A larger one is hashed
+//! the first time, and not again until its validators change, so a video
+//! costs one read over the session rather than one per range.
+//!
+//! That leaves a gap for the larger files: the validators are the length and
+//! the modification time, and a rewrite that keeps both — same size, clock
+//! set back, or `touch -r` afterwards — is taken on trust until the Webview
+//! is started again. The manifest catches a broken install, not someone
+//! working to hide a change.
+
+use std::{
+  collections::HashMap,
+  io::{self, Seek, SeekFrom},
+  sync::Mutex,
+};
+
+use super::{sha256, source::Opened};
+use crate::logs;
+
+#[cfg(test)]
+mod tests;
+
+/// The largest file hashed again each time it is served. Past it, one that
+/// matched is trusted while its length and modification time stand.
+const REHASH_LIMIT: u64 = 1024 * 1024;
+
+/// The digests a Root's files must have, by the name the Root gives them.
+pub struct Manifest {
+  digests: HashMap<String, [u8; 32]>,
+  /// The tag each file past `REHASH_LIMIT` had when it last matched, so one
+  /// nobody has touched since is not hashed again.
+  verified: Mutex<HashMap<String, String>>,
+}
+
+impl Manifest {
+  /// Reads `name`, hex digest pairs, refusing a digest that is not 64 hex
+  /// digits, in either case, as `dry.read_manifest` reads them. A name may
+  /// start with `/`, as the page would ask for it.
+  pub fn new(digests: Vec<(String, String)>) -> Result<Self, String> {
+    let digests = digests
+      .into_iter()
+      .map(|(name, digest)| {
+        let parsed = parse_hex(&digest).ok_or_else(|| {
+          format!("The digest of '{name}' is not 64 hex digits: '{digest}'.")
+        })?;
+        let name = name.strip_prefix('/').unwrap_or(&name).to_string();
+        Ok((name, parsed))
+      })
+      .collect::<Result<_, String>>()?;
+    Ok(Manifest {
+      digests,
+      verified: Mutex::new(HashMap::new()),
+    })
+  }
+
+  /// Whether a file just opened is the file that was shipped, as an error
+  /// when it is not: one the manifest does not name, or one whose content no
+  /// longer hashes to what it names. Either is logged, since it means the
+  /// install is broken and someone should hear of it.
+  pub fn check(&self, name: &str, opened: &mut Opened) -> io::Result<()> {
+    let Some(expected) = self.digests.get(name) else {
+      return Err(refused(name, "is not in the integrity manifest"));
+    };
+
+    let tag = opened
+      .validators()
+      .filter(|_| opened.length() > REHASH_LIMIT)
+      .map(|validators| validators.etag.clone());
+    if let Some(tag) = &tag
+      && self
+        .verified
+        .lock()
+        .is_ok_and(|verified| verified.get(name) == Some(tag))
+    {
+      return Ok(());
+    }
+
+    if digest(opened)? != *expected {
+      return Err(refused(
+        name,
+        "does not match its digest in the integrity manifest",
+      ));
+    }
+
+    if let Some(tag) = tag
+      && let Ok(mut verified) = self.verified.lock()
+    {
+      verified.insert(name.to_string(), tag);
+    }
+    Ok(())
+  }
+}
+
+/// The digest of an opened file's content, leaving it to be read again from
+/// the start.
+fn digest(opened: &mut Opened) -> io::Result<[u8; 32]> {
+  match opened {
+    Opened::File { file, .. } => {
+      let digest = sha256::digest(&mut *file)?;
+      file.seek(SeekFrom::Start(0))?;
+      Ok(digest)
+    },
+    Opened::Entry { content, .. } => sha256::digest(&content[..]),
+  }
+}
+
+fn refused(name: &str, reason: &str) -> io::Error {
+  let message = format!("'{name}' {reason}, and was not served.");
+  logs::error(logs::WEBVIEW, &message);
+  io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn parse_hex(digest: &str) -> Option<[u8; 32]> {
+  if digest.len() != 64 || !digest.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+    return None;
+  }
+  let mut parsed = [0; 32];
+  for (byte, pair) in parsed.iter_mut().zip(digest.as_bytes().chunks_exact(2)) {
+    let pair = std::str::from_utf8(pair).ok()?;
+    *byte = u8::from_str_radix(pair, 16).ok()?;
+  }
+  Some(parsed)
+}
@@ -0,0 +1,110 @@
+//! Tests for checking files against an integrity manifest. Serving a checked
+//! Root is tested in `root/tests.rs`.
+
+use super::*;
+use crate::root::validators::Validators;
+
+/// The SHA-256 of `abc`.
+const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+fn manifest(digests: &[(&str, &str)]) -> Manifest {
+  Manifest::new(
+    digests
+      .iter()
+      .map(|(name, digest)| (name.to_string(), digest.to_string()))
+      .collect(),
+  )
+  .expect("the manifest should be read")
+}
+
+fn entry(content: &[u8], crc: u32) -> Opened {
+  Opened::Entry {
+    content: content.to_vec(),
+    validators: Validators::of_entry(content.len() as u64, crc, 0),
+  }
+}
+
+#[test]
+fn a_file_as_shipped_passes() {
+  let manifest = manifest(&[("/assets/app.js", ABC)]);
+  assert!(
+    manifest
+      .check("assets/app.js", &mut entry(b"abc", 1))
+      .is_ok()
+  );
+}
+
+#[test]
+fn a_file_changed_since_it_shipped_is_refused() {
+  let manifest = manifest(&[("assets/app.js", ABC)]);
+  let refused = manifest
+    .check("assets/app.js", &mut entry(b"abd", 1))
+    .expect_err("a changed file should be refused");
+
+  assert_eq!(refused.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn a_file_the_manifest_does_not_name_is_refused() {
+  let manifest = manifest(&[("assets/app.js", ABC)]);
+  assert!(
+    manifest
+      .check("assets/old.js", &mut entry(b"abc", 1))
+      .is_err()
+  );
+}
+
+/// A small file is hashed every time, so a change that keeps its tag is
+/// still found out.
+#[test]
+fn a_small_file_is_hashed_each_time_it_is_served() {
+  let manifest = manifest(&[("app.js", ABC)]);
+  assert!(manifest.check("app.js", &mut entry(b"abc", 1)).is_ok());
+  assert!(manifest.check("app.js", &mut entry(b"abd", 1)).is_err());
+}
+
+/// A large file that matched is not hashed again while its tag stands, and
+/// is the moment the tag changes.
+#[test]
+fn a_large_file_is_hashed_again_only_once_it_changes() {
+  let shipped = vec![b'a'; REHASH_LIMIT as usize + 1];
+  let mut changed = shipped.clone();
+  changed[0] = b'b';
+  let digest = sha256::digest(&shipped[..]).expect("the content should be hashed");
+  let hex = digest
+    .iter()
+    .map(|byte| format!("{byte:02x}"))
+    .collect::<String>();
+  let manifest = manifest(&[("video.mp4", &hex)]);
+
+  assert!(manifest.check("video.mp4", &mut entry(&shipped, 1)).is_ok());
+  // Same tag: taken on trust, whatever the content now.
+  assert!(manifest.check("video.mp4", &mut entry(&changed, 1)).is_ok());
+  // New tag: hashed, and found out.
+  assert!(
+    manifest
+      .check("video.mp4", &mut entry(&changed, 2))
+      .is_err()
+  );
+}
+
+#[test]
+fn a_digest_is_read_in_either_case() {
+  let manifest = manifest(&[("app.js", &ABC.to_uppercase())]);
+  assert!(manifest.check("app.js", &mut entry(b"abc", 1)).is_ok());
+}
+
+#[test]
+fn a_digest_that_is_not_one_is_refused_up_front() {
+  for digest in [
+    "abc",
+    &ABC[..63],
+    &format!("{}g", &ABC[..63]),
+    &format!("+{}", &ABC[1..]),
+  ] {
+    assert!(
+      Manifest::new(vec![("app.js".into(), digest.to_string())]).is_err(),
+      "{digest} should be refused"
+    );
+  }
+}
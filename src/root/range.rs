@@ -0,0 +1,218 @@
+//! `Range` requests: which bytes of a file a request asked for, and how a
+//! response carrying more than one run of them is written.
+//!
+//! Only the `bytes` unit exists, as in every server a browser talks to. A
+//! header this cannot read is ignored and the whole file is sent, which is
+//! what RFC 9110 asks of a server that does not understand a range — a
+//! `<video>` that gets the whole file still plays, where one that gets a `400`
+//! does not.
+
+#[cfg(test)]
+mod tests;
+
+/// How many ranges one request may ask for before it is answered with the
+/// whole file instead. A media element asks for one; a request for hundreds of
+/// tiny overlapping runs is asking the Root to do a great deal of work to send
+/// the same bytes several times over.
+const MAX_RANGES: usize = 32;
+
+/// What separates the parts of a `multipart/byteranges` body. Fixed, and long
+/// and unlikely enough that no media file will contain it on a line of its own.
+const BOUNDARY: &str = "dry-byteranges-7f3a9c1e5b2d4086";
+
+/// One run of bytes, first to last, both inclusive, as `Content-Range` counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+  pub start: u64,
+  pub end: u64,
+}
+
+impl ByteRange {
+  pub fn len(&self) -> u64 {
+    self.end - self.start + 1
+  }
+
+  /// The first `limit` bytes of this run, or all of it if it is no longer.
+  pub fn at_most(self, limit: u64) -> ByteRange {
+    ByteRange {
+      start: self.start,
+      end: self.end.min(self.start + limit.max(1) - 1),
+    }
+  }
+
+  /// The `Content-Range` value announcing this run of a file that long.
+  pub fn content_range(&self, length: u64) -> String {
+    format!("bytes {}-{}/{length}", self.start, self.end)
+  }
+}
+
+/// What a `Range` header comes to, against a file of a known length.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Ranges {
+  /// No usable range: send the whole file, as though none had been asked for.
+  Whole,
+  /// A well-formed request of which no range overlaps the file: a `416`.
+  Unsatisfiable,
+  /// The runs to send, in the order they were asked for, clipped to the file,
+  /// those that overlap or touch merged into one.
+  Satisfiable(Vec<ByteRange>),
+}
+
+/// Reads a `Range` header against a file `length` bytes long.
+///
+/// `first-last`, `first-` and `-suffix` are understood, as a list. A range
+/// running past the end is clipped to it; one starting past the end is
+/// unsatisfiable, and is dropped unless every range is. Anything malformed —
+/// another unit, a reversed range, a number that is not one — is the header
+/// not being understood, which is `Whole`, not an error.
+///
+/// Ranges that overlap or touch are sent as one, as RFC 9110 allows, so no
+/// byte is read or sent twice. A request whose ranges add up to more than the
+/// file, `bytes=0-,0-` and the like, is `Whole`: the file itself is less to
+/// send than what was asked for.
+pub fn parse(header: &str, length: u64) -> Ranges {
+  let Some((unit, list)) = header.split_once('=') else {
+    return Ranges::Whole;
+  };
+  if !unit.trim().eq_ignore_ascii_case("bytes") {
+    return Ranges::Whole;
+  }
+
+  let mut ranges = Vec::new();
+  let mut requested = 0;
+
+  for spec in list
+    .split(',')
+    .map(str::trim)
+    .filter(|spec| !spec.is_empty())
+  {
+    requested += 1;
+    if requested > MAX_RANGES {
+      return Ranges::Whole;
+    }
+
+    let Some((first, last)) = spec.split_once('-') else {
+      return Ranges::Whole;
+    };
+    let (first, last) = (first.trim(), last.trim());
+
+    let range = if first.is_empty() {
+      let Some(suffix) = number(last) else {
+        return Ranges::Whole;
+      };
+      (suffix > 0 && length > 0).then(|| ByteRange {
+        start: length - suffix.min(length),
+        end: length - 1,
+      })
+    } else {
+      let Some(start) = number(first) else {
+        return Ranges::Whole;
+      };
+      let end = if last.is_empty() {
+        u64::MAX
+      } else {
+        match number(last) {
+          Some(end) if end >= start => end,
+          _ => return Ranges::Whole,
+        }
+      };
+      (start < length).then(|| ByteRange {
+        start,
+        end: end.min(length - 1),
+      })
+    };
+
+    ranges.extend(range);
+  }
+
+  if requested == 0 {
+    return Ranges::Whole;
+  }
+  if ranges.is_empty() {
+    return Ranges::Unsatisfiable;
+  }
+  let asked = ranges
+    .iter()
+    .fold(0u64, |total, range| total.saturating_add(range.len()));
+  if asked > length {
+    return Ranges::Whole;
+  }
+  Ranges::Satisfiable(coalesce(ranges))
+}
+
+/// Merges every run into any other it overlaps or touches, keeping each merged
+/// run where the first of its parts was asked for.
+fn coalesce(ranges: Vec<ByteRange>) -> Vec<ByteRange> {
+  let mut runs: Vec<ByteRange> = Vec::with_capacity(ranges.len());
+  for mut range in ranges {
+    let mut at = runs.len();
+    let mut index = 0;
+    while index < runs.len() {
+      let run = runs[index];
+      if run.start <= range.end.saturating_add(1)
+        && range.start <= run.end.saturating_add(1)
+      {
+        range = ByteRange {
+          start: run.start.min(range.start),
+          end: run.end.max(range.end),
+        };
+        runs.remove(index);
+        at = at.min(index);
+      } else {
+        index += 1;
+      }
+    }
+    runs.insert(at.min(runs.len()), range);
+  }
+  runs
+}
+
+/// A run of ASCII digits as a number. `str::parse` alone would also take a
+/// leading `+`, which a range does not allow.
+fn number(text: &str) -> Option<u64> {
+  if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+    return None;
+  }
+  text.parse().ok()
+}
+
+/// The content type of a response carrying several ranges.
+pub fn multipart_type() -> String {
+  format!("multipart/byteranges; boundary={BOUNDARY}")
+}
+
+/// Writes several runs of one file as a single `multipart/byteranges` body,
+/// each part announcing the file's own content type and where it came from.
+pub fn multipart_body(
+  content_type: &str, length: u64, parts: &[(ByteRange, Vec<u8>)],
+) -> Vec<u8> {
+  let mut body = Vec::new();
+  for (range, content) in parts {
+    body.extend_from_slice(part_head(content_type, length, range).as_bytes());
+    body.extend_from_slice(content);
+    body.extend_from_slice(b"\r\n");
+  }
+  body.extend_from_slice(closing().as_bytes());
+  body
+}
+
+/// How long `multipart_body` would be for these runs, counted without reading
+/// them, for a `HEAD` that is told the length and sent nothing.
+pub fn multipart_length(content_type: &str, length: u64, ranges: &[ByteRange]) -> u64 {
+  let parts: u64 = ranges
+    .iter()
+    .map(|range| part_head(content_type, length, range).len() as u64 + range.len() + 2)
+    .sum();
+  parts + closing().len() as u64
+}
+
+fn part_head(content_type: &str, length: u64, range: &ByteRange) -> String {
+  format!(
+    "--{BOUNDARY}\r\nContent-Type: {content_type}\r\nContent-Range: {}\r\n\r\n",
+    range.content_range(length)
+  )
+}
+
+fn closing() -> String {
+  format!("--{BOUNDARY}--\r\n")
+}
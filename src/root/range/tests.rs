@@ -0,0 +1,155 @@
+//! Tests for reading a `Range` header against a file of a known length, and
+//! for the body several ranges are sent in.
+
+use super::*;
+
+fn satisfiable(ranges: &[(u64, u64)]) -> Ranges {
+  Ranges::Satisfiable(
+    ranges
+      .iter()
+      .map(|&(start, end)| ByteRange { start, end })
+      .collect(),
+  )
+}
+
+#[test]
+fn the_three_forms_are_read() {
+  assert_eq!(parse("bytes=0-499", 1000), satisfiable(&[(0, 499)]));
+  assert_eq!(parse("bytes=500-", 1000), satisfiable(&[(500, 999)]));
+  assert_eq!(parse("bytes=-200", 1000), satisfiable(&[(800, 999)]));
+}
+
+#[test]
+fn a_range_running_past_the_end_is_clipped() {
+  assert_eq!(parse("bytes=900-5000", 1000), satisfiable(&[(900, 999)]));
+  assert_eq!(parse("bytes=-5000", 1000), satisfiable(&[(0, 999)]));
+}
+
+#[test]
+fn a_list_keeps_its_order_and_its_whitespace_is_ignored() {
+  assert_eq!(
+    parse("bytes= 500-599 , 0-99,", 1000),
+    satisfiable(&[(500, 599), (0, 99)])
+  );
+}
+
+/// Bytes asked for twice are read and sent once: overlapping and touching
+/// runs become one, kept where the first of them was asked for.
+#[test]
+fn overlapping_and_touching_ranges_are_merged() {
+  assert_eq!(parse("bytes=0-4, 3-9", 100), satisfiable(&[(0, 9)]));
+  assert_eq!(parse("bytes=0-4, 5-9", 100), satisfiable(&[(0, 9)]));
+  assert_eq!(
+    parse("bytes=50-59, 0-4, 55-64, 3-9", 100),
+    satisfiable(&[(50, 64), (0, 9)])
+  );
+  assert_eq!(
+    parse("bytes=0-9, 20-29, 8-21", 100),
+    satisfiable(&[(0, 29)])
+  );
+}
+
+/// Thirty-two `0-` ranges would otherwise read the whole file thirty-two
+/// times; asking for more than the file holds gets the file.
+#[test]
+fn ranges_adding_up_to_more_than_the_file_ask_for_the_whole_file() {
+  let many = vec!["0-"; MAX_RANGES].join(",");
+  assert_eq!(parse(&format!("bytes={many}"), 1000), Ranges::Whole);
+  assert_eq!(parse("bytes=0-599, 400-999", 1000), Ranges::Whole);
+  assert_eq!(
+    parse("bytes=0-499, 500-999", 1000),
+    satisfiable(&[(0, 999)])
+  );
+}
+
+#[test]
+fn the_unit_is_read_without_regard_to_case() {
+  assert_eq!(parse("Bytes=0-0", 10), satisfiable(&[(0, 0)]));
+}
+
+/// One range past the end among others that are not is dropped; only a
+/// request of which no range overlaps the file is refused outright.
+#[test]
+fn only_a_request_no_range_of_which_fits_is_unsatisfiable() {
+  assert_eq!(parse("bytes=0-9, 50-60", 20), satisfiable(&[(0, 9)]));
+  assert_eq!(parse("bytes=50-60", 20), Ranges::Unsatisfiable);
+  assert_eq!(parse("bytes=-0", 20), Ranges::Unsatisfiable);
+  assert_eq!(parse("bytes=0-", 0), Ranges::Unsatisfiable);
+}
+
+#[test]
+fn a_header_that_cannot_be_read_asks_for_the_whole_file() {
+  for header in [
+    "bytes=9-1",
+    "bytes=+1-2",
+    "bytes=1",
+    "bytes=x-",
+    "bytes=",
+    "lines=0-9",
+    "0-9",
+  ] {
+    assert_eq!(parse(header, 1000), Ranges::Whole, "{header}");
+  }
+}
+
+#[test]
+fn too_many_ranges_ask_for_the_whole_file() {
+  let many = (0..=MAX_RANGES)
+    .map(|at| format!("{at}-{at}"))
+    .collect::<Vec<_>>()
+    .join(",");
+  assert_eq!(parse(&format!("bytes={many}"), 1000), Ranges::Whole);
+}
+
+#[test]
+fn a_run_is_cut_to_a_limit_from_its_start() {
+  let run = ByteRange {
+    start: 100,
+    end: 999,
+  };
+  assert_eq!(
+    run.at_most(50),
+    ByteRange {
+      start: 100,
+      end: 149
+    }
+  );
+  assert_eq!(run.at_most(5000), run);
+}
+
+#[test]
+fn a_multipart_body_announces_each_part_and_closes() {
+  let parts = [
+    (ByteRange { start: 0, end: 1 }, b"ab".to_vec()),
+    (ByteRange { start: 4, end: 4 }, b"e".to_vec()),
+  ];
+
+  let body = multipart_body("text/plain", 5, &parts);
+
+  assert_eq!(
+    String::from_utf8(body).expect("the body is text here"),
+    format!(
+      "--{BOUNDARY}\r\nContent-Type: text/plain\r\nContent-Range: bytes 0-1/5\r\n\r\nab\r\n\
+       --{BOUNDARY}\r\nContent-Type: text/plain\r\nContent-Range: bytes 4-4/5\r\n\r\ne\r\n\
+       --{BOUNDARY}--\r\n"
+    )
+  );
+  assert_eq!(
+    multipart_type(),
+    format!("multipart/byteranges; boundary={BOUNDARY}")
+  );
+}
+
+#[test]
+fn a_multipart_body_is_as_long_as_counted_without_it() {
+  let parts = [
+    (ByteRange { start: 0, end: 1 }, b"ab".to_vec()),
+    (ByteRange { start: 4, end: 4 }, b"e".to_vec()),
+  ];
+  let ranges: Vec<ByteRange> = parts.iter().map(|(range, _)| *range).collect();
+
+  assert_eq!(
+    multipart_length("text/plain", 5, &ranges),
+    multipart_body("text/plain", 5, &parts).len() as u64
+  );
+}
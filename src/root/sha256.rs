@@ -0,0 +1,149 @@
+//! SHA-256, as FIPS 180-4 defines it, for checking a Root against its
+//! integrity manifest.
+//!
+//! Written here rather than pulled in from a crate, as the archive reader and
+//! the HTTP dates are: it is one function over a fixed table, and nothing else
+//! in Dry needs a hash that holds up against someone choosing the input.
+
+use std::io::{self, Read};
+
+#[cfg(test)]
+mod tests;
+
+/// The first 32 bits of the fractional parts of the cube roots of the first 64
+/// primes.
+#[rustfmt::skip]
+const K: [u32; 64] = [
+  0x428a_2f98, 0x7137_4491, 0xb5c0_fbcf, 0xe9b5_dba5, 0x3956_c25b, 0x59f1_11f1,
+  0x923f_82a4, 0xab1c_5ed5, 0xd807_aa98, 0x1283_5b01, 0x2431_85be, 0x550c_7dc3,
+  0x72be_5d74, 0x80de_b1fe, 0x9bdc_06a7, 0xc19b_f174, 0xe49b_69c1, 0xefbe_4786,
+  0x0fc1_9dc6, 0x240c_a1cc, 0x2de9_2c6f, 0x4a74_84aa, 0x5cb0_a9dc, 0x76f9_88da,
+  0x983e_5152, 0xa831_c66d, 0xb003_27c8, 0xbf59_7fc7, 0xc6e0_0bf3, 0xd5a7_9147,
+  0x06ca_6351, 0x1429_2967, 0x27b7_0a85, 0x2e1b_2138, 0x4d2c_6dfc, 0x5338_0d13,
+  0x650a_7354, 0x766a_0abb, 0x81c2_c92e, 0x9272_2c85, 0xa2bf_e8a1, 0xa81a_664b,
+  0xc24b_8b70, 0xc76c_51a3, 0xd192_e819, 0xd699_0624, 0xf40e_3585, 0x106a_a070,
+  0x19a4_c116, 0x1e37_6c08, 0x2748_774c, 0x34b0_bcb5, 0x391c_0cb3, 0x4ed8_aa4a,
+  0x5b9c_ca4f, 0x682e_6ff3, 0x748f_82ee, 0x78a5_636f, 0x84c8_7814, 0x8cc7_0208,
+  0x90be_fffa, 0xa450_6ceb, 0xbef9_a3f7, 0xc671_78f2,
+];
+
+/// The first 32 bits of the fractional parts of the square roots of the first
+/// eight primes.
+#[rustfmt::skip]
+const INITIAL: [u32; 8] = [
+  0x6a09_e667, 0xbb67_ae85, 0x3c6e_f372, 0xa54f_f53a,
+  0x510e_527f, 0x9b05_688c, 0x1f83_d9ab, 0x5be0_cd19,
+];
+
+/// A digest, fed a block at a time.
+pub struct Sha256 {
+  state: [u32; 8],
+  /// Bytes fed that do not yet fill a block.
+  pending: Vec<u8>,
+  length: u64,
+}
+
+impl Sha256 {
+  pub fn new() -> Self {
+    Sha256 {
+      state: INITIAL,
+      pending: Vec::with_capacity(64),
+      length: 0,
+    }
+  }
+
+  pub fn update(&mut self, mut bytes: &[u8]) {
+    self.length += bytes.len() as u64;
+    if !self.pending.is_empty() {
+      let wanted = (64 - self.pending.len()).min(bytes.len());
+      self.pending.extend_from_slice(&bytes[..wanted]);
+      bytes = &bytes[wanted..];
+      if self.pending.len() < 64 {
+        return;
+      }
+      let block: [u8; 64] = self.pending[..].try_into().expect("a block is 64 bytes");
+      self.compress(&block);
+      self.pending.clear();
+    }
+    let mut blocks = bytes.chunks_exact(64);
+    for block in &mut blocks {
+      self.compress(block.try_into().expect("a block is 64 bytes"));
+    }
+    self.pending.extend_from_slice(blocks.remainder());
+  }
+
+  pub fn finish(mut self) -> [u8; 32] {
+    let bits = self.length.wrapping_mul(8);
+    let mut padding = vec![0x80];
+    padding.resize((119 - self.pending.len()) % 64 + 1, 0);
+    padding.extend_from_slice(&bits.to_be_bytes());
+    // The padding is not message, so the length it was taken at stands.
+    let length = self.length;
+    self.update(&padding);
+    self.length = length;
+
+    let mut digest = [0; 32];
+    for (word, bytes) in self.state.iter().zip(digest.chunks_exact_mut(4)) {
+      bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+  }
+
+  fn compress(&mut self, block: &[u8; 64]) {
+    let mut schedule = [0u32; 64];
+    for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+      *word = u32::from_be_bytes(bytes.try_into().expect("a word is 4 bytes"));
+    }
+    for i in 16..64 {
+      let s0 = schedule[i - 15].rotate_right(7)
+        ^ schedule[i - 15].rotate_right(18)
+        ^ (schedule[i - 15] >> 3);
+      let s1 = schedule[i - 2].rotate_right(17)
+        ^ schedule[i - 2].rotate_right(19)
+        ^ (schedule[i - 2] >> 10);
+      schedule[i] = schedule[i - 16]
+        .wrapping_add(s0)
+        .wrapping_add(schedule[i - 7])
+        .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+    for (k, w) in K.iter().zip(schedule) {
+      let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+      let choice = (e & f) ^ (!e & g);
+      let t1 = h
+        .wrapping_add(s1)
+        .wrapping_add(choice)
+        .wrapping_add(*k)
+        .wrapping_add(w);
+      let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+      let majority = (a & b) ^ (a & c) ^ (b & c);
+      let t2 = s0.wrapping_add(majority);
+      h = g;
+      g = f;
+      f = e;
+      e = d.wrapping_add(t1);
+      d = c;
+      c = b;
+      b = a;
+      a = t1.wrapping_add(t2);
+    }
+
+    for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+      *word = word.wrapping_add(value);
+    }
+  }
+}
+
+/// The digest of everything a reader holds, read a buffer at a time so a
+/// large file is never held whole.
+pub fn digest(mut reader: impl Read) -> io::Result<[u8; 32]> {
+  let mut hasher = Sha256::new();
+  let mut buffer = vec![0; 64 * 1024];
+  loop {
+    match reader.read(&mut buffer)? {
+      0 => return Ok(hasher.finish()),
+      read => hasher.update(&buffer[..read]),
+    }
+  }
+}
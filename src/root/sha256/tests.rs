@@ -0,0 +1,53 @@
+//! Tests for SHA-256, against the examples FIPS 180-4 publishes.
+
+use super::*;
+
+fn hex(digest: [u8; 32]) -> String {
+  digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn of(bytes: &[u8]) -> String {
+  let mut hasher = Sha256::new();
+  hasher.update(bytes);
+  hex(hasher.finish())
+}
+
+#[test]
+fn the_published_examples_digest_as_published() {
+  assert_eq!(
+    of(b""),
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+  );
+  assert_eq!(
+    of(b"abc"),
+    "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+  );
+  assert_eq!(
+    of(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+    "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+  );
+}
+
+#[test]
+fn a_million_bytes_digest_as_published() {
+  assert_eq!(
+    hex(digest(&vec![b'a'; 1_000_000][..]).expect("a slice should read")),
+    "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+  );
+}
+
+/// However the bytes arrive, a block at a time or one at a time, the digest
+/// is the same.
+#[test]
+fn the_digest_does_not_depend_on_how_the_bytes_were_fed() {
+  let bytes: Vec<u8> = (0..=255).cycle().take(1000).collect();
+  let whole = of(&bytes);
+
+  for size in [1, 7, 63, 64, 65, 200] {
+    let mut hasher = Sha256::new();
+    for chunk in bytes.chunks(size) {
+      hasher.update(chunk);
+    }
+    assert_eq!(hex(hasher.finish()), whole, "fed {size} at a time");
+  }
+}
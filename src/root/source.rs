@@ -0,0 +1,176 @@
+//! Where a Root's files are read from: a directory on disk, or a zip archive.
+//!
+//! Both are asked the same two things — which file a request path names, and
+//! what is in it — and both answer with a name relative to the top of the
+//! Root, `/` between its segments. Everything above, from ranges to the cache
+//! policy, is written once against that name.
+
+use std::{
+  fs::File,
+  io::{self, Read, Seek, SeekFrom},
+  path::{Path, PathBuf},
+};
+
+use super::{
+  ROOT_INDEX, Rejection, archive::Archive, range::ByteRange, validators::Validators,
+};
+
+/// What a Root serves its files from.
+pub enum Source {
+  /// A directory on disk, canonicalised once so every resolved path can be
+  /// tested against it without walking symlinks again on each request.
+  Directory(PathBuf),
+  /// A zip archive, its index read once when the Root is made.
+  Archive(Archive),
+}
+
+/// A file about to be answered with: open, measured, and not yet read.
+pub enum Opened {
+  /// A file on disk, read only as far as a response needs.
+  File {
+    file: File,
+    length: u64,
+    validators: Option<Validators>,
+  },
+  /// An archive entry, which has to be inflated whole to be read at all.
+  Entry {
+    content: Vec<u8>,
+    validators: Validators,
+  },
+}
+
+impl Source {
+  /// A directory on disk.
+  pub fn directory(directory: PathBuf) -> Self {
+    let directory = directory.canonicalize().unwrap_or(directory);
+    Source::Directory(directory)
+  }
+
+  /// A zip archive, or one directory inside it, its index read now.
+  pub fn archive(archive: &Path, within: &str) -> Result<Self, String> {
+    Archive::open(archive, within).map(Source::Archive)
+  }
+
+  /// The name of the file the components of a request path reach, with a
+  /// directory standing for its index.
+  ///
+  /// The components have already been refused anything that would climb out
+  /// or be read as a separator or a drive. A directory then also requires the
+  /// canonical result to sit beneath it, which catches a symlink pointing
+  /// out; an archive has no symlinks and no names outside itself to reach.
+  pub fn resolve(&self, components: &[&str]) -> Result<String, Rejection> {
+    match self {
+      Source::Directory(directory) => {
+        let mut candidate = directory.clone();
+        candidate.extend(components);
+        if candidate.is_dir() {
+          candidate.push(ROOT_INDEX);
+        }
+
+        let resolved = candidate.canonicalize().map_err(|_| Rejection::NotFound)?;
+
+        if !resolved.starts_with(directory) {
+          return Err(Rejection::Outside);
+        }
+
+        if !resolved.is_file() {
+          return Err(Rejection::NotFound);
+        }
+
+        Ok(relative(directory, &resolved))
+      },
+      Source::Archive(archive) => {
+        let mut name = components.join("/");
+        if archive.is_dir(&name) {
+          name = match name.as_str() {
+            "" => ROOT_INDEX.to_string(),
+            directory => format!("{directory}/{ROOT_INDEX}"),
+          };
+        }
+        if !archive.is_file(&name) {
+          return Err(Rejection::NotFound);
+        }
+        Ok(name)
+      },
+    }
+  }
+
+  /// Opens a file `resolve` named.
+  pub fn open(&self, name: &str) -> io::Result<Opened> {
+    match self {
+      Source::Directory(directory) => {
+        let file = File::open(directory.join(name))?;
+        let metadata = file.metadata()?;
+        Ok(Opened::File {
+          length: metadata.len(),
+          validators: Validators::of(&metadata),
+          file,
+        })
+      },
+      Source::Archive(archive) => {
+        let (content, validators) = archive.read(name)?;
+        Ok(Opened::Entry {
+          content,
+          validators,
+        })
+      },
+    }
+  }
+}
+
+impl Opened {
+  pub fn length(&self) -> u64 {
+    match self {
+      Opened::File { length, .. } => *length,
+      Opened::Entry { content, .. } => content.len() as u64,
+    }
+  }
+
+  pub fn validators(&self) -> Option<&Validators> {
+    match self {
+      Opened::File { validators, .. } => validators.as_ref(),
+      Opened::Entry { validators, .. } => Some(validators),
+    }
+  }
+
+  /// Every byte of the file.
+  pub fn read_all(self) -> io::Result<Vec<u8>> {
+    match self {
+      Opened::File {
+        mut file, length, ..
+      } => {
+        let mut content = Vec::with_capacity(length as usize);
+        file.read_to_end(&mut content)?;
+        Ok(content)
+      },
+      Opened::Entry { content, .. } => Ok(content),
+    }
+  }
+
+  /// Exactly the bytes one range names, and no others.
+  pub fn read_range(&mut self, range: &ByteRange) -> io::Result<Vec<u8>> {
+    match self {
+      Opened::File { file, .. } => {
+        let mut content = vec![0; range.len() as usize];
+        file.seek(SeekFrom::Start(range.start))?;
+        file.read_exact(&mut content)?;
+        Ok(content)
+      },
+      Opened::Entry { content, .. } => {
+        Ok(content[range.start as usize..=range.end as usize].to_vec())
+      },
+    }
+  }
+}
+
+/// A resolved path as a Root names it: relative to the top of the directory,
+/// with `/` between its segments on every platform.
+fn relative(directory: &Path, path: &Path) -> String {
+  path
+    .strip_prefix(directory)
+    .unwrap_or(path)
+    .components()
+    .map(|component| component.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/")
+}
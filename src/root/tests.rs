@@ -0,0 +1,1535 @@
+//! Tests for serving a Root: which file a request reaches, which requests are
+//! refused, the content type a file is served with, how much of it a `Range`
+//! request gets back, when a reload is told its copy is still good, and when a
+//! precompressed sibling is sent in the file's place.
+//!
+//! Everything here runs against a temporary directory on disk, or a zip archive
+//! written into one. Nothing opens a window or runs an event loop.
+
+use super::*;
+use std::{
+  fs,
+  path::PathBuf,
+  sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+  },
+  thread,
+  time::{SystemTime, UNIX_EPOCH},
+};
+use wry::http::header::{
+  ACCEPT_ENCODING, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_RANGE, RANGE,
+};
+
+/// A throwaway directory tree, removed when the test drops it.
+struct Fixture {
+  directory: PathBuf,
+}
+
+impl Fixture {
+  fn new() -> Self {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let unique = format!(
+      "dry-root-{}-{}",
+      SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("the clock should be after the epoch")
+        .as_nanos(),
+      COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let directory = std::env::temp_dir().join(unique);
+    fs::create_dir_all(&directory).expect("the fixture directory should be created");
+    Fixture { directory }
+  }
+
+  fn write(&self, relative: &str, contents: &str) {
+    let path = self.directory.join(relative);
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).expect("the parent directory should be created");
+    }
+    fs::write(path, contents).expect("the fixture file should be written");
+  }
+
+  fn root(&self) -> Root {
+    served(Source::directory(self.directory.clone()))
+  }
+
+  /// Writes an archive of the given files into the fixture, after whatever
+  /// bytes are to precede it, and returns its path.
+  fn archive(&self, name: &str, preceding: &[u8], entries: &[(&str, &str)]) -> PathBuf {
+    let path = self.directory.join(name);
+    let mut contents = preceding.to_vec();
+    contents.extend(zip(entries, true));
+    fs::write(&path, contents).expect("the archive should be written");
+    path
+  }
+}
+
+/// A Root serving one source at its top.
+fn served(source: Source) -> Root {
+  Root::mounting(vec![("/".to_string(), source)]).expect("'/' should be mounted")
+}
+
+impl Drop for Fixture {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.directory);
+  }
+}
+
+/// A zip archive holding the given files, each deflated or each stored, with
+/// the offsets it records counted from its own first byte.
+pub(super) fn zip(entries: &[(&str, &str)], deflate: bool) -> Vec<u8> {
+  const TIME: u16 = 0;
+  // 2024-01-01.
+  const DATE: u16 = (44 << 9) | (1 << 5) | 1;
+  let method: u16 = if deflate { 8 } else { 0 };
+
+  let mut archive = Vec::new();
+  let mut directory = Vec::new();
+  for (name, content) in entries {
+    let stored = if deflate {
+      miniz_oxide::deflate::compress_to_vec(content.as_bytes(), 6)
+    } else {
+      content.as_bytes().to_vec()
+    };
+    let offset = archive.len() as u32;
+    let shared = [
+      method.to_le_bytes().as_slice(),
+      &TIME.to_le_bytes(),
+      &DATE.to_le_bytes(),
+      &crc32(content.as_bytes()).to_le_bytes(),
+      &(stored.len() as u32).to_le_bytes(),
+      &(content.len() as u32).to_le_bytes(),
+      &(name.len() as u16).to_le_bytes(),
+      &0u16.to_le_bytes(),
+    ]
+    .concat();
+
+    archive.extend(0x0403_4b50u32.to_le_bytes());
+    archive.extend(20u16.to_le_bytes());
+    archive.extend(0u16.to_le_bytes());
+    archive.extend(&shared);
+    archive.extend(name.as_bytes());
+    archive.extend(&stored);
+
+    directory.extend(0x0201_4b50u32.to_le_bytes());
+    directory.extend(20u16.to_le_bytes());
+    directory.extend(20u16.to_le_bytes());
+    directory.extend(0u16.to_le_bytes());
+    directory.extend(&shared);
+    // Comment length, disk, internal and external attributes.
+    directory.extend([0; 10]);
+    directory.extend(offset.to_le_bytes());
+    directory.extend(name.as_bytes());
+  }
+
+  let directory_offset = archive.len() as u32;
+  archive.extend(&directory);
+  archive.extend(0x0605_4b50u32.to_le_bytes());
+  archive.extend([0; 4]);
+  archive.extend((entries.len() as u16).to_le_bytes());
+  archive.extend((entries.len() as u16).to_le_bytes());
+  archive.extend((directory.len() as u32).to_le_bytes());
+  archive.extend(directory_offset.to_le_bytes());
+  archive.extend(0u16.to_le_bytes());
+  archive
+}
+
+/// The CRC-32 a zip records for its entries, computed the slow way.
+fn crc32(bytes: &[u8]) -> u32 {
+  let mut crc = !0u32;
+  for &byte in bytes {
+    crc ^= u32::from(byte);
+    for _ in 0..8 {
+      crc = if crc & 1 == 1 {
+        (crc >> 1) ^ 0xEDB8_8320
+      } else {
+        crc >> 1
+      };
+    }
+  }
+  !crc
+}
+
+/// A request for a path, with whatever headers the test adds to it.
+fn request(path: &str, headers: &[(HeaderName, &str)]) -> Request<Vec<u8>> {
+  let mut request = Request::get(path)
+    .body(Vec::new())
+    .expect("the request should build");
+  for (name, value) in headers {
+    request.headers_mut().insert(
+      name.clone(),
+      HeaderValue::from_str(value).expect("the header should be text"),
+    );
+  }
+  request
+}
+
+/// What the Root answers a plain request for a path with.
+fn get(root: &Root, path: &str) -> Response<Cow<'static, [u8]>> {
+  root.serve(&request(path, &[]))
+}
+
+/// The body a request came back with, as text.
+fn body_of(response: &Response<Cow<'static, [u8]>>) -> String {
+  String::from_utf8_lossy(response.body()).into_owned()
+}
+
+/// A response header as text, if the response carries it.
+fn header_of(
+  response: &Response<Cow<'static, [u8]>>, name: HeaderName,
+) -> Option<String> {
+  response.headers().get(name).map(|value| {
+    value
+      .to_str()
+      .expect("the header should be text")
+      .to_owned()
+  })
+}
+
+/// The content type a request came back with.
+fn content_type_of(response: &Response<Cow<'static, [u8]>>) -> String {
+  header_of(response, CONTENT_TYPE).expect("every response should carry a content type")
+}
+
+#[test]
+fn a_root_serves_a_file_beneath_it() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("assets/app.js", "export const x = 1;");
+
+  let root = fixture.root();
+
+  let page = get(&root, "/index.html");
+  assert_eq!(page.status(), 200);
+  assert_eq!(body_of(&page), "<h1>Root</h1>");
+
+  let script = get(&root, "/assets/app.js");
+  assert_eq!(script.status(), 200);
+  assert_eq!(body_of(&script), "export const x = 1;");
+}
+
+#[test]
+fn a_request_for_a_directory_serves_its_index() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("nested/index.html", "<h1>Nested</h1>");
+
+  let root = fixture.root();
+
+  assert_eq!(body_of(&get(&root, "/")), "<h1>Root</h1>");
+  assert_eq!(body_of(&get(&root, "/nested")), "<h1>Nested</h1>");
+  assert_eq!(body_of(&get(&root, "/nested/")), "<h1>Nested</h1>");
+}
+
+#[test]
+fn a_percent_escaped_name_reaches_its_file() {
+  let fixture = Fixture::new();
+  fixture.write("a file.css", "body { color: red; }");
+
+  let response = get(&fixture.root(), "/a%20file.css");
+
+  assert_eq!(response.status(), 200);
+  assert_eq!(body_of(&response), "body { color: red; }");
+}
+
+#[test]
+fn a_missing_file_is_a_404() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+
+  let response = get(&fixture.root(), "/nowhere.js");
+
+  assert_eq!(response.status(), 404);
+  assert!(body_of(&response).contains("nowhere.js"));
+}
+
+#[test]
+fn a_404_page_at_the_top_of_the_root_answers_a_miss() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("404.html", "<h1>Nothing here</h1>");
+  fixture.write("403.html", "<h1>Not yours</h1>");
+
+  let root = fixture
+    .root()
+    .with_headers(
+      Vec::new(),
+      vec![("404.html".into(), vec![("X-Page".into(), "missing".into())])],
+    )
+    .expect("the headers should be read");
+
+  let missing = get(&root, "/nested/nowhere.js");
+  assert_eq!(missing.status(), 404);
+  assert_eq!(body_of(&missing), "<h1>Nothing here</h1>");
+  assert_eq!(content_type_of(&missing), "text/html; charset=utf-8");
+  assert_eq!(missing.headers()["x-page"], "missing");
+
+  let outside = get(&root, "/../secret.txt");
+  assert_eq!(outside.status(), 403);
+  assert_eq!(body_of(&outside), "<h1>Not yours</h1>");
+}
+
+#[test]
+fn without_an_error_page_the_error_is_text() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("404.html", "<h1>Nothing here</h1>");
+
+  let outside = get(&fixture.root(), "/../secret.txt");
+  assert_eq!(outside.status(), 403);
+  assert_eq!(content_type_of(&outside), TEXT);
+}
+
+#[test]
+fn a_dotfile_is_not_served_unless_asked_for() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write(".env", "SECRET=1");
+  fixture.write(".git/config", "[core]");
+  fixture.write(".well-known/security.txt", "Contact: me");
+
+  let root = fixture.root();
+  assert_eq!(root.resolve("/.env"), Err(Rejection::NotFound));
+  assert_eq!(root.resolve("/.git/config"), Err(Rejection::NotFound));
+  assert_eq!(get(&root, "/.env").status(), 404);
+
+  let root = fixture.root().with_dotfiles(true);
+  assert_eq!(
+    root.resolve("/.well-known/security.txt").as_deref(),
+    Ok(".well-known/security.txt")
+  );
+}
+
+/// Live reload names only what the Root would serve: a change to `.env` or to
+/// an excluded file tells the page nothing, not even that the file is there.
+#[test]
+fn the_watcher_names_only_what_is_served() {
+  let fixture = Fixture::new();
+  let root = fixture
+    .root()
+    .with_filters(Vec::new(), vec!["*.map".to_string()])
+    .expect("the filters should be read");
+  let mut watcher = root.watcher();
+
+  fixture.write("app.js", "1");
+  fixture.write("app.js.map", "{}");
+  fixture.write(".env", "SECRET=1");
+  fixture.write(".git/HEAD", "ref: main");
+
+  assert_eq!(watcher.changes(), ["/app.js"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn a_symlink_to_a_dotfile_is_as_hidden_as_the_dotfile() {
+  let fixture = Fixture::new();
+  fixture.write(".env", "SECRET=1");
+  std::os::unix::fs::symlink(
+    fixture.directory.join(".env"),
+    fixture.directory.join("config.txt"),
+  )
+  .expect("the symlink should be created");
+
+  assert_eq!(
+    fixture.root().resolve("/config.txt"),
+    Err(Rejection::NotFound)
+  );
+}
+
+#[test]
+fn only_what_the_filters_let_through_is_served() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("assets/app.js", "export {};");
+  fixture.write("assets/app.js.map", "{}");
+  fixture.write("notes/todo.md", "- ship");
+
+  let root = fixture
+    .root()
+    .with_filters(
+      vec!["index.html".into(), "assets/**".into()],
+      vec!["*.map".into()],
+    )
+    .expect("the filters should be read");
+
+  assert_eq!(root.resolve("/").as_deref(), Ok("index.html"));
+  assert_eq!(
+    root.resolve("/assets/app.js").as_deref(),
+    Ok("assets/app.js")
+  );
+  assert_eq!(root.resolve("/assets/app.js.map"), Err(Rejection::NotFound));
+  assert_eq!(root.resolve("/notes/todo.md"), Err(Rejection::NotFound));
+}
+
+#[test]
+fn a_filter_that_names_nothing_is_refused_up_front() {
+  let fixture = Fixture::new();
+  assert!(
+    fixture
+      .root()
+      .with_filters(Vec::new(), vec!["".into()])
+      .is_err()
+  );
+}
+
+/// The manifest entry for some content, as `dry.write_manifest` writes it.
+fn shipped(name: &str, content: &str) -> (String, String) {
+  let digest = sha256::digest(content.as_bytes()).expect("a slice should read");
+  let hex = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+  (name.to_string(), hex)
+}
+
+#[test]
+fn a_root_with_a_manifest_serves_only_what_was_shipped() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("assets/app.js", "export {};");
+  fixture.write("assets/stale.js", "export const old = 1;");
+
+  let root = fixture
+    .root()
+    .with_integrity(Some(vec![
+      shipped("index.html", "<h1>Root</h1>"),
+      shipped("assets/app.js", "export const v = 2;"),
+    ]))
+    .expect("the manifest should be read");
+
+  // Hashed, and still read whole afterwards.
+  let page = get(&root, "/");
+  assert_eq!(page.status(), 200);
+  assert_eq!(body_of(&page), "<h1>Root</h1>");
+
+  let changed = get(&root, "/assets/app.js");
+  assert_eq!(changed.status(), 500);
+  assert_eq!(
+    body_of(&changed),
+    "Not the file that was shipped: /assets/app.js"
+  );
+  assert_eq!(get(&root, "/assets/stale.js").status(), 500);
+  assert_eq!(get(&root, "/assets/missing.js").status(), 404);
+}
+
+/// A precompressed sibling is its own file, with its own digest, and one the
+/// manifest does not vouch for is never sent in the original's place.
+#[test]
+fn a_precompressed_sibling_is_checked_as_itself() {
+  let fixture = Fixture::new();
+  fixture.write("app.js", "export {};");
+  fixture.write("app.js.gz", "not really gzip");
+
+  let root = fixture
+    .root()
+    .with_integrity(Some(vec![shipped("app.js", "export {};")]))
+    .expect("the manifest should be read");
+  let response = root.serve(&request("/app.js", &[(ACCEPT_ENCODING, "gzip")]));
+
+  assert_eq!(response.status(), 500);
+  assert_eq!(get(&root, "/app.js").status(), 200);
+}
+
+#[test]
+fn a_path_climbing_out_of_the_root_is_refused() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+
+  let root = fixture.root();
+
+  for path in [
+    "/../secret.txt",
+    "/%2e%2e/secret.txt",
+    "/assets/../../out.txt",
+  ] {
+    let response = get(&root, path);
+    assert_eq!(response.status(), 403, "{path} should be refused");
+  }
+
+  assert_eq!(root.resolve("/../secret.txt"), Err(Rejection::Outside));
+}
+
+#[test]
+fn an_absolute_or_separator_bearing_path_is_refused() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+
+  let root = fixture.root();
+
+  assert_eq!(root.resolve("/C:/Windows/win.ini"), Err(Rejection::Outside));
+  assert_eq!(root.resolve("/..\\secret.txt"), Err(Rejection::Outside));
+}
+
+#[test]
+fn content_types_follow_the_extension() {
+  assert_eq!(
+    content_type(Path::new("a/index.html")),
+    "text/html; charset=utf-8"
+  );
+  assert_eq!(
+    content_type(Path::new("a/app.js")),
+    "text/javascript; charset=utf-8"
+  );
+  assert_eq!(
+    content_type(Path::new("a/app.mjs")),
+    "text/javascript; charset=utf-8"
+  );
+  assert_eq!(
+    content_type(Path::new("a/site.css")),
+    "text/css; charset=utf-8"
+  );
+  assert_eq!(content_type(Path::new("a/logo.png")), "image/png");
+  assert_eq!(content_type(Path::new("a/logo.SVG")), "image/svg+xml");
+  assert_eq!(content_type(Path::new("a/photo.jpeg")), "image/jpeg");
+  assert_eq!(content_type(Path::new("a/font.woff2")), "font/woff2");
+}
+
+/// The old handler answered every image with `image/`, which is not a media
+/// type at all. Nothing may answer with it again.
+#[test]
+fn no_extension_answers_with_a_bare_image_type() {
+  for name in ["a.png", "a.jpg", "a.jpeg", "a.gif", "a.svg"] {
+    assert_ne!(content_type(Path::new(name)), "image/");
+  }
+}
+
+#[test]
+fn an_unknown_extension_gets_a_safe_default() {
+  assert_eq!(
+    content_type(Path::new("a/thing.xyz")),
+    "application/octet-stream"
+  );
+  assert_eq!(
+    content_type(Path::new("a/LICENSE")),
+    "application/octet-stream"
+  );
+}
+
+#[test]
+fn the_formats_a_frontend_ships_have_their_types() {
+  assert_eq!(content_type(Path::new("scene.glb")), "model/gltf-binary");
+  assert_eq!(
+    content_type(Path::new("site.webmanifest")),
+    "application/manifest+json; charset=utf-8"
+  );
+  assert_eq!(
+    content_type(Path::new("data.jsonld")),
+    "application/ld+json; charset=utf-8"
+  );
+  assert_eq!(
+    content_type(Path::new("src/main.ts")),
+    "text/typescript; charset=utf-8"
+  );
+  assert_eq!(content_type(Path::new("clip.mov")), "video/quicktime");
+}
+
+#[test]
+fn the_settings_types_come_before_the_built_in_ones() {
+  let fixture = Fixture::new();
+  fixture.write("scene.glb", "glTF");
+  fixture.write("segment.ts", "G");
+  fixture.write("types/index.d.ts", "export {};");
+  fixture.write("logo.png", "png");
+
+  let root = fixture
+    .root()
+    .with_mime_types(vec![
+      (".TS".into(), "video/mp2t".into()),
+      (".d.ts".into(), "text/plain".into()),
+      ("glb".into(), "model/x-custom".into()),
+    ])
+    .expect("the types should be read");
+
+  assert_eq!(content_type_of(&get(&root, "/segment.ts")), "video/mp2t");
+  assert_eq!(
+    content_type_of(&get(&root, "/types/index.d.ts")),
+    "text/plain"
+  );
+  assert_eq!(content_type_of(&get(&root, "/scene.glb")), "model/x-custom");
+  assert_eq!(content_type_of(&get(&root, "/logo.png")), "image/png");
+}
+
+#[test]
+fn a_type_that_cannot_be_set_is_refused_up_front() {
+  let fixture = Fixture::new();
+  let types = |extension: &str, content_type: &str| {
+    fixture
+      .root()
+      .with_mime_types(vec![(extension.into(), content_type.into())])
+  };
+
+  assert!(types(".", "text/plain").is_err());
+  assert!(types(".a/b", "text/plain").is_err());
+  assert!(types(".glb", "").is_err());
+  assert!(types(".glb", "model/gltf\nbinary").is_err());
+}
+
+#[test]
+fn a_served_file_carries_its_content_type() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("style.css", "body {}");
+
+  let root = fixture.root();
+
+  assert_eq!(
+    content_type_of(&get(&root, "/")),
+    "text/html; charset=utf-8"
+  );
+  assert_eq!(
+    content_type_of(&get(&root, "/style.css")),
+    "text/css; charset=utf-8"
+  );
+}
+
+#[test]
+fn percent_decoding_leaves_a_broken_escape_alone() {
+  assert_eq!(percent_decode("/a%20b.css"), "/a b.css");
+  assert_eq!(percent_decode("/100%.css"), "/100%.css");
+  assert_eq!(percent_decode("/a%zz.css"), "/a%zz.css");
+  assert_eq!(percent_decode("/a%2"), "/a%2");
+}
+
+/// A media element decides whether it can seek from this header, on the very
+/// first response, before it has asked for a range at all.
+#[test]
+fn every_file_says_it_accepts_ranges() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let response = get(&fixture.root(), "/clip.mp4");
+
+  assert_eq!(response.status(), 200);
+  assert_eq!(
+    header_of(&response, ACCEPT_RANGES).as_deref(),
+    Some("bytes")
+  );
+  assert!(header_of(&response, ETAG).is_some());
+  assert!(header_of(&response, LAST_MODIFIED).is_some());
+}
+
+#[test]
+fn a_single_range_is_a_206_with_only_those_bytes() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let response = fixture
+    .root()
+    .serve(&request("/clip.mp4", &[(RANGE, "bytes=2-5")]));
+
+  assert_eq!(response.status(), 206);
+  assert_eq!(body_of(&response), "2345");
+  assert_eq!(
+    header_of(&response, CONTENT_RANGE).as_deref(),
+    Some("bytes 2-5/10")
+  );
+  assert_eq!(content_type_of(&response), "video/mp4");
+}
+
+/// The two forms a seeking `<video>` actually sends: from a point to the end,
+/// and the last few bytes, where an mp4 may keep its index.
+#[test]
+fn open_ended_and_suffix_ranges_reach_the_end_of_the_file() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let root = fixture.root();
+
+  let from = root.serve(&request("/clip.mp4", &[(RANGE, "bytes=7-")]));
+  assert_eq!(body_of(&from), "789");
+  assert_eq!(
+    header_of(&from, CONTENT_RANGE).as_deref(),
+    Some("bytes 7-9/10")
+  );
+
+  let suffix = root.serve(&request("/clip.mp4", &[(RANGE, "bytes=-4")]));
+  assert_eq!(body_of(&suffix), "6789");
+  assert_eq!(
+    header_of(&suffix, CONTENT_RANGE).as_deref(),
+    Some("bytes 6-9/10")
+  );
+}
+
+#[test]
+fn several_ranges_are_one_multipart_response() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let response = fixture
+    .root()
+    .serve(&request("/clip.mp4", &[(RANGE, "bytes=0-1, 8-9")]));
+
+  assert_eq!(response.status(), 206);
+  assert!(content_type_of(&response).starts_with("multipart/byteranges; boundary="));
+  assert_eq!(header_of(&response, CONTENT_RANGE), None);
+
+  let body = body_of(&response);
+  assert!(
+    body.contains("Content-Range: bytes 0-1/10\r\n\r\n01\r\n"),
+    "{body}"
+  );
+  assert!(
+    body.contains("Content-Range: bytes 8-9/10\r\n\r\n89\r\n"),
+    "{body}"
+  );
+  assert!(body.contains("Content-Type: video/mp4\r\n"), "{body}");
+}
+
+#[test]
+fn overlapping_ranges_are_sent_once() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let root = fixture.root();
+
+  let response = root.serve(&request("/clip.mp4", &[(RANGE, "bytes=0-2, 1-3")]));
+  assert_eq!(response.status(), 206);
+  assert_eq!(
+    header_of(&response, CONTENT_RANGE).as_deref(),
+    Some("bytes 0-3/10")
+  );
+  assert_eq!(body_of(&response), "0123");
+
+  let response = root.serve(&request("/clip.mp4", &[(RANGE, "bytes=0-, 0-, 0-")]));
+  assert_eq!(response.status(), 200);
+  assert_eq!(body_of(&response), "0123456789");
+}
+
+#[test]
+fn a_range_past_the_end_is_a_416_naming_the_length() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let response = fixture
+    .root()
+    .serve(&request("/clip.mp4", &[(RANGE, "bytes=10-20")]));
+
+  assert_eq!(response.status(), 416);
+  assert_eq!(
+    header_of(&response, CONTENT_RANGE).as_deref(),
+    Some("bytes */10")
+  );
+}
+
+/// A header that cannot be read is a header not understood, and the whole
+/// file is the answer to that — never an error the media element cannot use.
+#[test]
+fn an_unreadable_range_serves_the_whole_file() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let root = fixture.root();
+
+  for range in ["bytes=5-2", "items=0-1", "bytes=a-b", "bytes"] {
+    let response = root.serve(&request("/clip.mp4", &[(RANGE, range)]));
+    assert_eq!(response.status(), 200, "{range} should be ignored");
+    assert_eq!(body_of(&response), "0123456789");
+  }
+}
+
+#[test]
+fn a_matching_if_range_keeps_the_range() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let root = fixture.root();
+  let first = get(&root, "/clip.mp4");
+
+  for validator in [ETAG, LAST_MODIFIED] {
+    let validator = header_of(&first, validator).expect("the file has validators");
+    let response = root.serve(&request(
+      "/clip.mp4",
+      &[(RANGE, "bytes=0-3"), (IF_RANGE, &validator)],
+    ));
+    assert_eq!(response.status(), 206, "If-Range: {validator}");
+    assert_eq!(body_of(&response), "0123");
+  }
+}
+
+/// The client's copy is of a file that has since changed, so a range of the
+/// new one stitched onto it would be corrupt. It gets the whole new file.
+#[test]
+fn a_stale_if_range_sends_the_whole_file() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+
+  let root = fixture.root();
+
+  for stale in [
+    "\"a-stale-tag\"",
+    "W/\"a-weak-tag\"",
+    "Thu, 01 Jan 1970 00:00:00 GMT",
+    "not a validator",
+  ] {
+    let response = root.serve(&request(
+      "/clip.mp4",
+      &[(RANGE, "bytes=0-3"), (IF_RANGE, stale)],
+    ));
+    assert_eq!(response.status(), 200, "If-Range: {stale}");
+    assert_eq!(body_of(&response), "0123456789");
+  }
+}
+
+/// A reload of a bundle the Webview already holds is answered without reading
+/// it: the tag it sent back still names the file, so the answer is a `304`.
+#[test]
+fn a_matching_tag_is_a_304_with_no_body() {
+  let fixture = Fixture::new();
+  fixture.write("assets/app.js", "export const x = 1;");
+
+  let root = fixture.root();
+  let first = get(&root, "/assets/app.js");
+  let etag = header_of(&first, ETAG).expect("the file has a tag");
+
+  for tags in [
+    etag.clone(),
+    format!("W/{etag}"),
+    format!("\"other\", {etag}"),
+    "*".into(),
+  ] {
+    let response = root.serve(&request("/assets/app.js", &[(IF_NONE_MATCH, &tags)]));
+    assert_eq!(response.status(), 304, "If-None-Match: {tags}");
+    assert!(response.body().is_empty());
+    assert_eq!(header_of(&response, ETAG).as_deref(), Some(etag.as_str()));
+    assert!(header_of(&response, CACHE_CONTROL).is_some());
+  }
+}
+
+#[test]
+fn a_tag_that_no_longer_matches_is_the_whole_file() {
+  let fixture = Fixture::new();
+  fixture.write("assets/app.js", "export const x = 2;");
+
+  let response = fixture.root().serve(&request(
+    "/assets/app.js",
+    &[(IF_NONE_MATCH, "\"an-old-build\"")],
+  ));
+
+  assert_eq!(response.status(), 200);
+  assert_eq!(body_of(&response), "export const x = 2;");
+}
+
+#[test]
+fn a_date_no_older_than_the_file_is_a_304() {
+  let fixture = Fixture::new();
+  fixture.write("style.css", "body {}");
+
+  let root = fixture.root();
+  let first = get(&root, "/style.css");
+  let modified = header_of(&first, LAST_MODIFIED).expect("the file has a date");
+
+  let same = root.serve(&request("/style.css", &[(IF_MODIFIED_SINCE, &modified)]));
+  assert_eq!(same.status(), 304);
+
+  let older = root.serve(&request(
+    "/style.css",
+    &[(IF_MODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:00 GMT")],
+  ));
+  assert_eq!(older.status(), 200);
+}
+
+/// `If-None-Match` speaks alone when it is there. A date that would have said
+/// "unchanged" does not overrule a tag that says otherwise.
+#[test]
+fn a_tag_overrules_a_date() {
+  let fixture = Fixture::new();
+  fixture.write("style.css", "body {}");
+
+  let root = fixture.root();
+  let modified =
+    header_of(&get(&root, "/style.css"), LAST_MODIFIED).expect("the file has a date");
+
+  let response = root.serve(&request(
+    "/style.css",
+    &[(IF_NONE_MATCH, "\"stale\""), (IF_MODIFIED_SINCE, &modified)],
+  ));
+
+  assert_eq!(response.status(), 200);
+}
+
+#[test]
+fn a_file_no_rule_names_must_be_revalidated() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+
+  let response = get(&fixture.root(), "/");
+
+  assert_eq!(
+    header_of(&response, CACHE_CONTROL).as_deref(),
+    Some("no-cache")
+  );
+}
+
+#[test]
+fn the_first_rule_naming_a_file_decides_its_cache_policy() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("assets/app.3f9a2c.js", "export {};");
+  fixture.write("assets/logo.svg", "<svg/>");
+
+  let root = fixture
+    .root()
+    .with_cache_control(vec![
+      ("assets/*.js".into(), "immutable".into()),
+      ("assets/**".into(), "max-age=3600".into()),
+      ("*.js".into(), "no-store".into()),
+    ])
+    .expect("the policy should be read");
+
+  assert_eq!(
+    header_of(&get(&root, "/assets/app.3f9a2c.js"), CACHE_CONTROL).as_deref(),
+    Some(IMMUTABLE)
+  );
+  assert_eq!(
+    header_of(&get(&root, "/assets/logo.svg"), CACHE_CONTROL).as_deref(),
+    Some("max-age=3600")
+  );
+  assert_eq!(
+    header_of(&get(&root, "/index.html"), CACHE_CONTROL).as_deref(),
+    Some(DEFAULT_CACHE_CONTROL)
+  );
+}
+
+#[test]
+fn a_policy_that_cannot_be_a_header_is_refused_up_front() {
+  let fixture = Fixture::new();
+
+  let refused = fixture
+    .root()
+    .with_cache_control(vec![("*.js".into(), "max-age=1\nx".into())]);
+  assert!(refused.is_err());
+
+  let empty = fixture
+    .root()
+    .with_cache_control(vec![("".into(), "no-cache".into())]);
+  assert!(empty.is_err());
+}
+
+#[test]
+fn default_headers_are_on_every_response() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+
+  let root = fixture
+    .root()
+    .with_headers(
+      vec![
+        (
+          "Content-Security-Policy".into(),
+          "default-src 'self'".into(),
+        ),
+        ("X-Content-Type-Options".into(), "nosniff".into()),
+      ],
+      Vec::new(),
+    )
+    .expect("the headers should be read");
+
+  for path in ["/", "/missing.js", "/../outside"] {
+    let response = get(&root, path);
+    assert_eq!(
+      response.headers()["content-security-policy"],
+      "default-src 'self'"
+    );
+    assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+  }
+}
+
+#[test]
+fn the_first_override_naming_a_file_decides_each_header() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  fixture.write("worker/app.js", "export {};");
+
+  let root = fixture
+    .root()
+    .with_headers(
+      vec![
+        ("Cross-Origin-Opener-Policy".into(), "unsafe-none".into()),
+        ("X-Frame-Options".into(), "DENY".into()),
+      ],
+      vec![
+        (
+          "worker/*".into(),
+          vec![("Cross-Origin-Opener-Policy".into(), "same-origin".into())],
+        ),
+        (
+          "*.js".into(),
+          vec![
+            (
+              "Cross-Origin-Opener-Policy".into(),
+              "same-origin-allow-popups".into(),
+            ),
+            ("Cross-Origin-Embedder-Policy".into(), "require-corp".into()),
+          ],
+        ),
+        ("*.html".into(), vec![("X-Frame-Options".into(), "".into())]),
+      ],
+    )
+    .expect("the headers should be read");
+
+  let script = get(&root, "/worker/app.js");
+  assert_eq!(
+    script.headers()["cross-origin-opener-policy"],
+    "same-origin"
+  );
+  assert_eq!(
+    script.headers()["cross-origin-embedder-policy"],
+    "require-corp"
+  );
+  assert_eq!(script.headers()["x-frame-options"], "DENY");
+
+  let page = get(&root, "/");
+  assert_eq!(page.headers()["cross-origin-opener-policy"], "unsafe-none");
+  assert!(page.headers().get("x-frame-options").is_none());
+}
+
+#[test]
+fn a_route_falling_back_carries_the_index_headers() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+
+  let root = fixture
+    .root()
+    .with_spa_fallback(true)
+    .with_headers(
+      Vec::new(),
+      vec![(
+        "index.html".into(),
+        vec![("X-Served".into(), "index".into())],
+      )],
+    )
+    .expect("the headers should be read");
+
+  assert_eq!(get(&root, "/settings").headers()["x-served"], "index");
+}
+
+#[test]
+fn a_header_that_cannot_be_set_is_refused_up_front() {
+  let fixture = Fixture::new();
+  let defaults = |name: &str, value: &str| {
+    fixture
+      .root()
+      .with_headers(vec![(name.into(), value.into())], Vec::new())
+  };
+
+  assert!(defaults("Bad Name", "x").is_err());
+  assert!(defaults("X-Ok", "line\nbreak").is_err());
+  assert!(defaults("Content-Type", "text/plain").is_err());
+  assert!(defaults("cache-control", "no-store").is_err());
+
+  let override_pattern = fixture
+    .root()
+    .with_headers(Vec::new(), vec![("".into(), Vec::new())]);
+  assert!(override_pattern.is_err());
+}
+
+/// A fixture holding `app.js` and whichever of its precompressed siblings the
+/// test names, each with contents that say which one it is.
+fn compressed(siblings: &[&str]) -> Fixture {
+  let fixture = Fixture::new();
+  fixture.write("app.js", "raw");
+  for extension in siblings {
+    fixture.write(&format!("app.js.{extension}"), extension);
+  }
+  fixture
+}
+
+#[test]
+fn brotli_is_sent_when_the_webview_accepts_it() {
+  let fixture = compressed(&["br", "gz"]);
+  let response = fixture.root().serve(&request(
+    "/app.js",
+    &[(ACCEPT_ENCODING, "gzip, deflate, br")],
+  ));
+
+  assert_eq!(response.status(), 200);
+  assert_eq!(body_of(&response), "br");
+  assert_eq!(
+    header_of(&response, CONTENT_ENCODING).as_deref(),
+    Some("br")
+  );
+  assert_eq!(content_type_of(&response), "text/javascript; charset=utf-8");
+  assert_eq!(
+    header_of(&response, VARY).as_deref(),
+    Some("Accept-Encoding")
+  );
+}
+
+#[test]
+fn gzip_is_sent_when_brotli_is_missing_or_refused() {
+  let only_gzip = compressed(&["gz"]);
+  let response = only_gzip
+    .root()
+    .serve(&request("/app.js", &[(ACCEPT_ENCODING, "gzip, br")]));
+  assert_eq!(body_of(&response), "gz");
+  assert_eq!(
+    header_of(&response, CONTENT_ENCODING).as_deref(),
+    Some("gzip")
+  );
+
+  let both = compressed(&["br", "gz"]);
+  let response = both
+    .root()
+    .serve(&request("/app.js", &[(ACCEPT_ENCODING, "gzip, br;q=0")]));
+  assert_eq!(body_of(&response), "gz");
+}
+
+#[test]
+fn the_file_itself_is_sent_when_no_encoding_is_accepted() {
+  let fixture = compressed(&["br", "gz"]);
+  let root = fixture.root();
+
+  let response = get(&root, "/app.js");
+  assert_eq!(body_of(&response), "raw");
+  assert_eq!(header_of(&response, CONTENT_ENCODING), None);
+  // The answer still depends on the header, even when it was absent.
+  assert_eq!(
+    header_of(&response, VARY).as_deref(),
+    Some("Accept-Encoding")
+  );
+
+  let identity = root.serve(&request("/app.js", &[(ACCEPT_ENCODING, "identity")]));
+  assert_eq!(body_of(&identity), "raw");
+}
+
+#[test]
+fn a_file_without_siblings_says_nothing_about_encoding() {
+  let fixture = compressed(&[]);
+  let response = fixture
+    .root()
+    .serve(&request("/app.js", &[(ACCEPT_ENCODING, "br, gzip")]));
+
+  assert_eq!(body_of(&response), "raw");
+  assert_eq!(header_of(&response, CONTENT_ENCODING), None);
+  assert_eq!(header_of(&response, VARY), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn a_sibling_is_not_reachable_past_the_root() {
+  let outside = Fixture::new();
+  outside.write("app.js.br", "escaped");
+  let fixture = compressed(&[]);
+  std::os::unix::fs::symlink(
+    outside.directory.join("app.js.br"),
+    fixture.directory.join("app.js.br"),
+  )
+  .expect("the symlink should be created");
+
+  let response = fixture
+    .root()
+    .serve(&request("/app.js", &[(ACCEPT_ENCODING, "br")]));
+  assert_eq!(body_of(&response), "raw");
+  assert_eq!(header_of(&response, VARY), None);
+}
+
+#[test]
+fn a_range_of_a_compressed_file_counts_the_compressed_bytes() {
+  let fixture = Fixture::new();
+  fixture.write("app.js", "raw contents");
+  fixture.write("app.js.br", "0123456789");
+  let response = fixture.root().serve(&request(
+    "/app.js",
+    &[(ACCEPT_ENCODING, "br"), (RANGE, "bytes=2-4")],
+  ));
+
+  assert_eq!(response.status(), 206);
+  assert_eq!(body_of(&response), "234");
+  assert_eq!(
+    header_of(&response, CONTENT_ENCODING).as_deref(),
+    Some("br")
+  );
+  assert_eq!(
+    header_of(&response, CONTENT_RANGE).as_deref(),
+    Some("bytes 2-4/10")
+  );
+}
+
+#[test]
+fn a_large_file_is_sent_a_chunk_at_a_time() {
+  let fixture = Fixture::new();
+  fixture.write("video.mp4", "0123456789abcdef");
+  let root = fixture.root().with_stream_threshold(4);
+
+  let opening = root.serve(&request("/video.mp4", &[(RANGE, "bytes=0-")]));
+  assert_eq!(opening.status(), 206);
+  assert_eq!(body_of(&opening), "0123");
+  assert_eq!(
+    header_of(&opening, CONTENT_RANGE).as_deref(),
+    Some("bytes 0-3/16")
+  );
+
+  let several = root.serve(&request("/video.mp4", &[(RANGE, "bytes=10-,0-1")]));
+  assert_eq!(several.status(), 206);
+  assert_eq!(body_of(&several), "abcd");
+  assert_eq!(
+    header_of(&several, CONTENT_RANGE).as_deref(),
+    Some("bytes 10-13/16")
+  );
+}
+
+/// A download, a `fetch` or an `<img>` asks for the whole file, and a `206`
+/// is no answer to a request that asked for no part: `fetch` would take the
+/// first chunk for all of it. The threshold is for range requests only.
+#[test]
+fn a_plain_request_for_a_large_file_gets_all_of_it() {
+  let fixture = Fixture::new();
+  fixture.write("video.mp4", "0123456789abcdef");
+  let root = fixture.root().with_stream_threshold(4);
+
+  let response = get(&root, "/video.mp4");
+  assert_eq!(response.status(), 200);
+  assert_eq!(body_of(&response), "0123456789abcdef");
+  assert_eq!(header_of(&response, CONTENT_RANGE), None);
+}
+
+/// A `HEAD` reads nothing, and is told how long each answer to a `GET` would
+/// have been.
+#[test]
+fn a_head_is_told_the_length_without_the_body() {
+  let fixture = Fixture::new();
+  fixture.write("video.mp4", "0123456789abcdef");
+  let root = fixture.root();
+  let head = |headers: &[(HeaderName, &str)]| {
+    let mut request = request("/video.mp4", headers);
+    *request.method_mut() = Method::HEAD;
+    root.serve(&request)
+  };
+
+  let whole = head(&[]);
+  assert_eq!(whole.status(), 200);
+  assert!(whole.body().is_empty());
+  assert_eq!(header_of(&whole, CONTENT_LENGTH).as_deref(), Some("16"));
+
+  let part = head(&[(RANGE, "bytes=2-5")]);
+  assert_eq!(part.status(), 206);
+  assert!(part.body().is_empty());
+  assert_eq!(header_of(&part, CONTENT_LENGTH).as_deref(), Some("4"));
+  assert_eq!(
+    header_of(&part, CONTENT_RANGE).as_deref(),
+    Some("bytes 2-5/16")
+  );
+
+  let several = [(RANGE, "bytes=0-1, 8-9")];
+  let parts = head(&several);
+  assert!(parts.body().is_empty());
+  assert_eq!(
+    header_of(&parts, CONTENT_LENGTH),
+    Some(
+      root
+        .serve(&request("/video.mp4", &several))
+        .body()
+        .len()
+        .to_string()
+    )
+  );
+}
+
+#[test]
+fn a_file_within_the_threshold_is_sent_as_asked() {
+  let fixture = Fixture::new();
+  fixture.write("clip.mp4", "0123456789");
+  let root = fixture.root().with_stream_threshold(10);
+
+  let response = root.serve(&request("/clip.mp4", &[(RANGE, "bytes=0-")]));
+  assert_eq!(body_of(&response), "0123456789");
+}
+
+#[test]
+fn a_root_answers_from_any_thread() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>Root</h1>");
+  let root = Arc::new(fixture.root());
+
+  let answers: Vec<_> = (0..4)
+    .map(|_| {
+      let root = Arc::clone(&root);
+      thread::spawn(move || body_of(&get(&root, "/")))
+    })
+    .collect();
+
+  for answer in answers {
+    assert_eq!(
+      answer.join().expect("the thread should answer"),
+      "<h1>Root</h1>"
+    );
+  }
+}
+
+/// A Root mounting `dist` at `/`, `themes` at `/themes` and `fonts` at
+/// `/shared/fonts`, each a directory of the fixture.
+fn mounted(fixture: &Fixture) -> Root {
+  Root::mounting(
+    [
+      ("/", "dist"),
+      ("/themes", "themes"),
+      ("/shared/fonts/", "fonts"),
+    ]
+    .into_iter()
+    .map(|(prefix, directory)| {
+      (
+        prefix.to_string(),
+        Source::directory(fixture.directory.join(directory)),
+      )
+    })
+    .collect(),
+  )
+  .expect("the mounts should be read")
+}
+
+#[test]
+fn the_longest_prefix_a_path_falls_under_answers_it() {
+  let fixture = Fixture::new();
+  fixture.write("dist/index.html", "<h1>app</h1>");
+  fixture.write("dist/themes/dark.css", "bundled");
+  fixture.write("themes/dark.css", "installed");
+  fixture.write("themes/index.html", "<h1>themes</h1>");
+  fixture.write("fonts/inter.woff2", "font");
+  let root = mounted(&fixture);
+
+  assert_eq!(body_of(&get(&root, "/")), "<h1>app</h1>");
+  assert_eq!(body_of(&get(&root, "/themes/dark.css")), "installed");
+  assert_eq!(body_of(&get(&root, "/themes")), "<h1>themes</h1>");
+  assert_eq!(body_of(&get(&root, "/shared/fonts/inter.woff2")), "font");
+  assert_eq!(
+    root.resolve("/themes/dark.css").as_deref(),
+    Ok("themes/dark.css")
+  );
+}
+
+#[test]
+fn a_file_missing_from_a_mount_is_not_looked_for_beneath_another() {
+  let fixture = Fixture::new();
+  fixture.write("dist/themes/light.css", "bundled");
+  fixture.write("themes/dark.css", "installed");
+  fixture.write("fonts/inter.woff2", "font");
+  let root = mounted(&fixture);
+
+  assert_eq!(get(&root, "/themes/light.css").status(), 404);
+  assert_eq!(get(&root, "/shared/other.txt").status(), 404);
+}
+
+#[test]
+fn each_mount_keeps_its_own_containment() {
+  let fixture = Fixture::new();
+  fixture.write("dist/index.html", "<h1>app</h1>");
+  fixture.write("themes/dark.css", "installed");
+  fixture.write("fonts/inter.woff2", "font");
+  let root = mounted(&fixture);
+
+  assert_eq!(
+    root.resolve("/themes/../../dist/index.html"),
+    Err(Rejection::Outside)
+  );
+  assert_eq!(
+    root.resolve("/themes/..%2F..%2Fdist"),
+    Err(Rejection::Outside)
+  );
+}
+
+#[test]
+fn rules_see_the_path_the_page_asked_for() {
+  let fixture = Fixture::new();
+  fixture.write("dist/index.html", "<h1>app</h1>");
+  fixture.write("themes/dark.css", "installed");
+  fixture.write("fonts/inter.woff2", "font");
+  let root = mounted(&fixture)
+    .with_cache_control(vec![("themes/*".into(), "no-store".into())])
+    .expect("the policy should be read");
+
+  assert_eq!(
+    header_of(&get(&root, "/themes/dark.css"), CACHE_CONTROL).as_deref(),
+    Some("no-store")
+  );
+}
+
+#[test]
+fn a_prefix_that_cannot_be_mounted_is_refused() {
+  let fixture = Fixture::new();
+  let mount = |prefixes: &[&str]| {
+    Root::mounting(
+      prefixes
+        .iter()
+        .map(|prefix| {
+          (
+            prefix.to_string(),
+            Source::directory(fixture.directory.clone()),
+          )
+        })
+        .collect(),
+    )
+  };
+
+  assert!(mount(&[]).is_err());
+  assert!(mount(&["themes"]).is_err());
+  assert!(mount(&["/a/../b"]).is_err());
+  assert!(mount(&["/themes", "/themes/"]).is_err());
+  assert!(mount(&["/", "/themes"]).is_ok());
+}
+
+#[test]
+fn a_route_is_a_404_unless_the_fallback_is_on() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>app</h1>");
+
+  let response = get(&fixture.root(), "/settings/profile");
+  assert_eq!(response.status(), 404);
+}
+
+#[test]
+fn a_route_falls_back_to_the_index() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>app</h1>");
+  let root = fixture.root().with_spa_fallback(true);
+
+  for route in ["/settings/profile", "/settings/", "/users/42"] {
+    let response = get(&root, route);
+    assert_eq!(response.status(), 200, "{route}");
+    assert_eq!(body_of(&response), "<h1>app</h1>");
+    assert_eq!(content_type_of(&response), "text/html; charset=utf-8");
+  }
+}
+
+#[test]
+fn a_missing_asset_is_still_a_404_with_the_fallback_on() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>app</h1>");
+  let root = fixture.root().with_spa_fallback(true);
+
+  for asset in ["/assets/app.js", "/theme.css", "/settings/logo.svg"] {
+    assert_eq!(get(&root, asset).status(), 404, "{asset}");
+  }
+}
+
+#[test]
+fn the_fallback_neither_hides_files_nor_escapes_the_root() {
+  let fixture = Fixture::new();
+  fixture.write("index.html", "<h1>app</h1>");
+  fixture.write("about", "plain");
+  let root = fixture.root().with_spa_fallback(true);
+
+  assert_eq!(body_of(&get(&root, "/about")), "plain");
+  assert_eq!(get(&root, "/../outside").status(), 403);
+}
+
+#[test]
+fn the_fallback_is_a_404_without_an_index() {
+  let fixture = Fixture::new();
+  fixture.write("other.html", "elsewhere");
+  let root = fixture.root().with_spa_fallback(true);
+
+  assert_eq!(get(&root, "/settings").status(), 404);
+}
+
+#[test]
+fn an_archive_serves_its_entries_like_a_directory() {
+  let fixture = Fixture::new();
+  let path = fixture.archive(
+    "app.zip",
+    &[],
+    &[
+      ("index.html", "<h1>Zipped</h1>"),
+      ("assets/app.js", "export const x = 1;"),
+      ("docs/index.html", "<h1>Docs</h1>"),
+    ],
+  );
+  let root = Source::archive(&path, "")
+    .map(served)
+    .expect("the archive should be read");
+
+  let index = get(&root, "/");
+  assert_eq!(index.status(), 200);
+  assert_eq!(body_of(&index), "<h1>Zipped</h1>");
+  assert_eq!(content_type_of(&index), "text/html; charset=utf-8");
+
+  let script = get(&root, "/assets/app.js");
+  assert_eq!(body_of(&script), "export const x = 1;");
+  assert_eq!(content_type_of(&script), "text/javascript; charset=utf-8");
+
+  assert_eq!(body_of(&get(&root, "/docs")), "<h1>Docs</h1>");
+  assert_eq!(body_of(&get(&root, "/docs/")), "<h1>Docs</h1>");
+}
+
+#[test]
+fn an_archive_refuses_what_a_directory_refuses() {
+  let fixture = Fixture::new();
+  let path = fixture.archive("app.zip", &[], &[("index.html", "<h1>Zipped</h1>")]);
+  let root = Source::archive(&path, "")
+    .map(served)
+    .expect("the archive should be read");
+
+  assert_eq!(get(&root, "/missing.js").status(), 404);
+  assert_eq!(get(&root, "/assets").status(), 404);
+  assert_eq!(root.resolve("/../app.zip"), Err(Rejection::Outside));
+  assert_eq!(root.resolve("/C:/index.html"), Err(Rejection::Outside));
+}
+
+#[test]
+fn an_archive_appended_to_another_file_is_found() {
+  let fixture = Fixture::new();
+  let executable = b"\x7fELF pretend this is a program".repeat(64);
+  let path =
+    fixture.archive("app.bin", &executable, &[("index.html", "<h1>Tail</h1>")]);
+  let root = Source::archive(&path, "")
+    .map(served)
+    .expect("the appended archive should be read");
+
+  assert_eq!(body_of(&get(&root, "/")), "<h1>Tail</h1>");
+}
+
+#[test]
+fn an_archive_entry_is_revalidated_and_ranged() {
+  let fixture = Fixture::new();
+  let path = fixture.archive("app.zip", &[], &[("clip.mp4", "0123456789")]);
+  let root = Source::archive(&path, "")
+    .map(served)
+    .expect("the archive should be read");
+
+  let whole = get(&root, "/clip.mp4");
+  let etag = header_of(&whole, ETAG).expect("an entry should carry an ETag");
+  assert_eq!(
+    header_of(&whole, LAST_MODIFIED).as_deref(),
+    Some("Mon, 01 Jan 2024 00:00:00 GMT")
+  );
+
+  let again = root.serve(&request("/clip.mp4", &[(IF_NONE_MATCH, &etag)]));
+  assert_eq!(again.status(), 304);
+
+  let part = root.serve(&request("/clip.mp4", &[(RANGE, "bytes=3-5")]));
+  assert_eq!(part.status(), 206);
+  assert_eq!(body_of(&part), "345");
+}
+
+#[test]
+fn a_file_that_is_not_an_archive_is_refused() {
+  let fixture = Fixture::new();
+  fixture.write("notes.txt", "not a zip at all");
+
+  let refused = Source::archive(&fixture.directory.join("notes.txt"), "").map(served);
+  assert!(refused.is_err());
+}
+
+#[test]
+fn a_directory_inside_an_archive_is_the_top_of_the_root() {
+  let fixture = Fixture::new();
+  let path = fixture.archive(
+    "site-packages.zip",
+    &[],
+    &[
+      ("myapp/__init__.py", "secret = 1"),
+      ("myapp/frontend/dist/index.html", "<h1>Packaged</h1>"),
+      ("myapp/frontend/dist/assets/app.js", "export {};"),
+    ],
+  );
+  let root = Source::archive(&path, "myapp/frontend/dist")
+    .map(served)
+    .expect("the directory should be read")
+    .with_cache_control(vec![("assets/*".into(), "immutable".into())])
+    .expect("the policy should be read");
+
+  assert_eq!(body_of(&get(&root, "/")), "<h1>Packaged</h1>");
+  assert_eq!(
+    header_of(&get(&root, "/assets/app.js"), CACHE_CONTROL).as_deref(),
+    Some(IMMUTABLE)
+  );
+  assert_eq!(get(&root, "/__init__.py").status(), 404);
+  assert_eq!(root.resolve("/../../__init__.py"), Err(Rejection::Outside));
+}
@@ -0,0 +1,180 @@
+//! What a file served from a Root is identified by between two requests: an
+//! `ETag` and a `Last-Modified` date, both read off its metadata.
+//!
+//! Neither costs a read of the file. The tag is the length and the
+//! modification time, which is what every static file server uses, and it
+//! changes whenever a build rewrites the file — the only change a Root sees.
+//! An entry in an archive has a checksum of its content to hand, so its tag is
+//! the length and that instead.
+//!
+//! A tag is compared weakly for a reload and strongly for a range, the two
+//! comparisons RFC 9110 defines; the tags written here are strong, so the
+//! difference only shows when a client sends a weak one back.
+//!
+//! Dates are HTTP dates, the fixed `Sun, 06 Nov 1994 08:49:37 GMT` form, and
+//! they are written and read here rather than pulled in from a crate: it is
+//! one format, and the arithmetic is a dozen lines.
+
+use std::{fs::Metadata, time::UNIX_EPOCH};
+
+#[cfg(test)]
+mod tests;
+
+const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+
+const MONTHS: [&str; 12] = [
+  "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// The validators of one file, as it stands on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validators {
+  /// A strong entity tag, quotes included, as it goes on the wire.
+  pub etag: String,
+  /// The modification time, in whole seconds since the epoch: an HTTP date
+  /// has no finer resolution, so nothing finer is kept to compare against.
+  pub modified: u64,
+}
+
+impl Validators {
+  /// The validators of a file, or `None` on a file system that does not
+  /// record when a file was modified. A tag made of the length alone would
+  /// claim two different builds of the same size are the same file.
+  pub fn of(metadata: &Metadata) -> Option<Self> {
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(Validators {
+      etag: format!("\"{:x}-{:x}\"", metadata.len(), modified.as_nanos()),
+      modified: modified.as_secs(),
+    })
+  }
+
+  /// The validators of an entry in an archive, from what its central
+  /// directory records: the length, the CRC-32 of the content and the time it
+  /// was stored.
+  pub fn of_entry(length: u64, crc: u32, modified: u64) -> Self {
+    Validators {
+      etag: format!("\"{length:x}-{crc:08x}\""),
+      modified,
+    }
+  }
+
+  /// The `Last-Modified` header value.
+  pub fn last_modified(&self) -> String {
+    http_date(self.modified)
+  }
+
+  /// Whether an `If-None-Match` list names this file, which makes the copy the
+  /// client holds this file. The comparison is weak: `W/"x"` and `"x"` name
+  /// the same thing here, and `*` names any file that exists.
+  pub fn satisfy_if_none_match(&self, tags: &str) -> bool {
+    let own = weak(&self.etag);
+    tags
+      .split(',')
+      .map(str::trim)
+      .any(|tag| tag == "*" || weak(tag) == own)
+  }
+
+  /// Whether the file is no newer than an `If-Modified-Since` date. A date
+  /// that cannot be read says nothing, and the file is sent.
+  pub fn unmodified_since(&self, date: &str) -> bool {
+    parse_http_date(date).is_some_and(|since| self.modified <= since)
+  }
+
+  /// Whether an `If-Range` condition still describes this file.
+  ///
+  /// `If-Range` asks for a strong match: a weak tag never satisfies it, and a
+  /// date satisfies it only when it is the file's own `Last-Modified`, to the
+  /// second. Anything unreadable is a condition that does not hold.
+  pub fn satisfy_if_range(&self, condition: &str) -> bool {
+    let condition = condition.trim();
+    if condition.starts_with('"') {
+      return condition == self.etag;
+    }
+    if condition.starts_with("W/") {
+      return false;
+    }
+    parse_http_date(condition) == Some(self.modified)
+  }
+}
+
+/// A tag with its weakness taken off, for the weak comparison.
+fn weak(tag: &str) -> &str {
+  tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+/// Seconds since the epoch as an HTTP date.
+pub fn http_date(seconds: u64) -> String {
+  let days = seconds / SECONDS_PER_DAY;
+  let of_day = seconds % SECONDS_PER_DAY;
+  let (year, month, day) = civil_from_days(days);
+  format!(
+    "{}, {day:02} {} {year:04} {:02}:{:02}:{:02} GMT",
+    WEEKDAYS[(days % 7) as usize],
+    MONTHS[(month - 1) as usize],
+    of_day / 3600,
+    of_day % 3600 / 60,
+    of_day % 60,
+  )
+}
+
+/// An HTTP date as seconds since the epoch, or `None` for anything that is not
+/// one. Only the fixed form is read, which is the only one a browser sends.
+pub fn parse_http_date(text: &str) -> Option<u64> {
+  let (_weekday, rest) = text.trim().split_once(", ")?;
+  let mut fields = rest.split(' ');
+
+  let day: u64 = fields.next()?.parse().ok()?;
+  let month = fields.next()?;
+  let month = MONTHS.iter().position(|name| *name == month)? as u64 + 1;
+  let year: u64 = fields.next()?.parse().ok()?;
+  let time = fields.next()?;
+  if fields.next()? != "GMT" || fields.next().is_some() {
+    return None;
+  }
+
+  let mut clock = time.split(':').map(|field| field.parse::<u64>().ok());
+  let (hour, minute, second) = (clock.next()??, clock.next()??, clock.next()??);
+  if clock.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+    return None;
+  }
+  if !(1..=31).contains(&day) || year < 1970 {
+    return None;
+  }
+
+  let days = days_from_civil(year, month, day);
+  Some(days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second)
+}
+
+/// Days since the epoch as a year, month and day of the proleptic Gregorian
+/// calendar. Howard Hinnant's `civil_from_days`, for dates after the epoch.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+  let shifted = days + 719_468;
+  let era = shifted / 146_097;
+  let of_era = shifted % 146_097;
+  let year_of_era =
+    (of_era - of_era / 1_460 + of_era / 36_524 - of_era / 146_096) / 365;
+  let day_of_year = of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let month_index = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+  let month = if month_index < 10 {
+    month_index + 3
+  } else {
+    month_index - 9
+  };
+  let year = year_of_era + era * 400 + u64::from(month <= 2);
+  (year, month, day)
+}
+
+/// The inverse of `civil_from_days`.
+pub fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+  let year = if month <= 2 { year - 1 } else { year };
+  let era = year / 400;
+  let year_of_era = year % 400;
+  let month_index = if month > 2 { month - 3 } else { month + 9 };
+  let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+  let day_of_era =
+    year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+  era * 146_097 + day_of_era - 719_468
+}
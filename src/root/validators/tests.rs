@@ -0,0 +1,84 @@
+//! Tests for HTTP dates and for the conditional comparisons, against validators
+//! written down rather than read from a file.
+
+use super::*;
+
+/// RFC 9110's own example date.
+const EXAMPLE: &str = "Sun, 06 Nov 1994 08:49:37 GMT";
+const EXAMPLE_SECONDS: u64 = 784_111_777;
+
+fn validators() -> Validators {
+  Validators {
+    etag: "\"a-1\"".to_string(),
+    modified: EXAMPLE_SECONDS,
+  }
+}
+
+#[test]
+fn a_date_is_written_in_the_fixed_form() {
+  assert_eq!(http_date(EXAMPLE_SECONDS), EXAMPLE);
+  assert_eq!(http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+  assert_eq!(http_date(951_782_400), "Tue, 29 Feb 2000 00:00:00 GMT");
+}
+
+#[test]
+fn a_written_date_reads_back_as_itself() {
+  for seconds in [
+    0,
+    EXAMPLE_SECONDS,
+    951_782_400,
+    1_700_000_000,
+    4_102_444_799,
+  ] {
+    assert_eq!(parse_http_date(&http_date(seconds)), Some(seconds));
+  }
+}
+
+#[test]
+fn anything_but_the_fixed_form_is_not_a_date() {
+  for text in [
+    "Sunday, 06-Nov-94 08:49:37 GMT",
+    "Sun Nov  6 08:49:37 1994",
+    "Sun, 06 Nov 1994 08:49:37 UTC",
+    "Sun, 06 Nov 1994 25:49:37 GMT",
+    "Sun, 06 Foo 1994 08:49:37 GMT",
+    "",
+  ] {
+    assert_eq!(parse_http_date(text), None, "{text}");
+  }
+}
+
+#[test]
+fn if_range_holds_for_the_same_tag_or_the_same_date() {
+  let validators = validators();
+  assert!(validators.satisfy_if_range("\"a-1\""));
+  assert!(validators.satisfy_if_range(EXAMPLE));
+}
+
+#[test]
+fn if_range_fails_for_anything_else() {
+  let validators = validators();
+  assert!(!validators.satisfy_if_range("\"a-2\""));
+  assert!(!validators.satisfy_if_range("W/\"a-1\""));
+  assert!(!validators.satisfy_if_range("Sun, 06 Nov 1994 08:49:38 GMT"));
+  assert!(!validators.satisfy_if_range("yesterday"));
+}
+
+#[test]
+fn if_none_match_compares_weakly_and_takes_a_list() {
+  let validators = validators();
+  assert!(validators.satisfy_if_none_match("\"a-1\""));
+  assert!(validators.satisfy_if_none_match("W/\"a-1\""));
+  assert!(validators.satisfy_if_none_match("\"x\", \"a-1\""));
+  assert!(validators.satisfy_if_none_match("*"));
+  assert!(!validators.satisfy_if_none_match("\"a-2\""));
+}
+
+#[test]
+fn if_modified_since_holds_for_the_same_date_or_a_later_one() {
+  let validators = validators();
+  assert!(validators.unmodified_since(EXAMPLE));
+  assert!(validators.unmodified_since("Mon, 07 Nov 1994 08:49:37 GMT"));
+  assert!(!validators.unmodified_since("Sun, 06 Nov 1994 08:49:36 GMT"));
+  assert!(!validators.unmodified_since("not a date"));
+}
@@ -0,0 +1,154 @@
+//! Noticing that the files under a Root have changed, for live reload.
+//!
+//! By polling: every file beneath each directory the Root serves is stamped
+//! with its length and modification time, and a later scan that stamps one
+//! differently, or finds one added or gone, has found a change. That needs no
+//! platform API and no dependency, costs a directory walk per tick, and is
+//! meant for the development tree of a frontend, not a dataset.
+//!
+//! Only files the Root would serve are watched. A change to `.env`, or to a
+//! file `exclude` holds back, is no more the page's business than the file
+//! itself, and naming it in `window:root-changed` would tell the page it is
+//! there.
+//!
+//! A build rewrites dozens of files over a second or two, and a reload per
+//! file would reload a half-written bundle. `Debounce` holds changes back until
+//! the tree has been quiet for a while, and hands them over as one.
+
+use std::{
+  collections::{BTreeSet, HashMap},
+  fs,
+  path::{Path, PathBuf},
+  time::{Duration, Instant, SystemTime},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// What a file looked like the last time it was scanned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+  length: u64,
+  modified: Option<SystemTime>,
+}
+
+/// The directories a Root serves, and what was in them at the last scan.
+pub struct Watcher {
+  /// Each directory, and the URL prefix its files are named beneath.
+  directories: Vec<(String, PathBuf)>,
+  /// Whether the Root serves a file, by its name beneath the Root.
+  serves: Box<dyn Fn(&str) -> bool + Send>,
+  seen: HashMap<String, Stamp>,
+}
+
+impl Watcher {
+  /// Watches directories, each under a prefix: empty for the top of the Root,
+  /// `themes` for a mount beneath `/themes`. Only a file `serves` lets
+  /// through is watched. The first scan happens here, so only what changes
+  /// after this counts.
+  pub fn new(
+    directories: Vec<(String, PathBuf)>, serves: impl Fn(&str) -> bool + Send + 'static,
+  ) -> Self {
+    let mut watcher = Watcher {
+      directories,
+      serves: Box::new(serves),
+      seen: HashMap::new(),
+    };
+    watcher.seen = watcher.scan();
+    watcher
+  }
+
+  /// Whether there is anything on disk to watch at all.
+  pub fn is_empty(&self) -> bool {
+    self.directories.is_empty()
+  }
+
+  /// The names of every file added, changed or removed since the last call,
+  /// as the page would ask for them: `/`, then the path beneath the Root.
+  pub fn changes(&mut self) -> Vec<String> {
+    let now = self.scan();
+    let mut changed: BTreeSet<&String> = now
+      .iter()
+      .filter(|(name, stamp)| self.seen.get(*name) != Some(stamp))
+      .map(|(name, _)| name)
+      .collect();
+    changed.extend(self.seen.keys().filter(|name| !now.contains_key(*name)));
+    let changed = changed.into_iter().map(|name| format!("/{name}")).collect();
+    self.seen = now;
+    changed
+  }
+
+  fn scan(&self) -> HashMap<String, Stamp> {
+    let mut stamps = HashMap::new();
+    for (prefix, directory) in &self.directories {
+      walk(directory, prefix, &mut stamps);
+    }
+    stamps.retain(|name, _| (self.serves)(name));
+    stamps
+  }
+}
+
+/// Stamps every file beneath a directory. A directory that is a symlink is
+/// not followed, so a link back up the tree cannot loop; one that cannot be
+/// read is skipped, since it may be mid-rewrite and the next scan will see it.
+fn walk(directory: &Path, name: &str, stamps: &mut HashMap<String, Stamp>) {
+  let Ok(entries) = fs::read_dir(directory) else {
+    return;
+  };
+  for entry in entries.flatten() {
+    let Ok(file_type) = entry.file_type() else {
+      continue;
+    };
+    let file_name = entry.file_name().to_string_lossy().into_owned();
+    let child = match name {
+      "" => file_name,
+      name => format!("{name}/{file_name}"),
+    };
+    if file_type.is_dir() {
+      walk(&entry.path(), &child, stamps);
+    } else if let Ok(metadata) = fs::metadata(entry.path())
+      && metadata.is_file()
+    {
+      stamps.insert(
+        child,
+        Stamp {
+          length: metadata.len(),
+          modified: metadata.modified().ok(),
+        },
+      );
+    }
+  }
+}
+
+/// Changes held back until the tree has been quiet for a while.
+pub struct Debounce {
+  quiet: Duration,
+  pending: BTreeSet<String>,
+  last: Option<Instant>,
+}
+
+impl Debounce {
+  pub fn new(quiet: Duration) -> Self {
+    Debounce {
+      quiet,
+      pending: BTreeSet::new(),
+      last: None,
+    }
+  }
+
+  /// Takes what one scan found, at `now`, and hands back every change since
+  /// the last hand-over once nothing more has changed for the quiet period.
+  pub fn feed(&mut self, changes: Vec<String>, now: Instant) -> Option<Vec<String>> {
+    if !changes.is_empty() {
+      self.pending.extend(changes);
+      self.last = Some(now);
+      return None;
+    }
+    let last = self.last?;
+    if now.duration_since(last) < self.quiet {
+      return None;
+    }
+    self.last = None;
+    Some(std::mem::take(&mut self.pending).into_iter().collect())
+  }
+}
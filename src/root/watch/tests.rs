@@ -0,0 +1,117 @@
+//! Tests for noticing changes under a Root, against a temporary directory, and
+//! for holding a burst of them back until it settles.
+
+use super::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A directory the watcher is pointed at, removed when the test drops it.
+struct Tree {
+  directory: PathBuf,
+}
+
+impl Tree {
+  fn new() -> Self {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let directory = std::env::temp_dir().join(format!(
+      "dry-watch-{}-{}",
+      std::process::id(),
+      COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&directory).expect("the tree should be created");
+    Tree { directory }
+  }
+
+  fn write(&self, relative: &str, contents: &str) {
+    let path = self.directory.join(relative);
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).expect("the parent directory should be created");
+    }
+    fs::write(path, contents).expect("the file should be written");
+  }
+
+  fn watcher(&self, prefix: &str) -> Watcher {
+    Watcher::new(vec![(prefix.to_string(), self.directory.clone())], |_| true)
+  }
+}
+
+impl Drop for Tree {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.directory);
+  }
+}
+
+#[test]
+fn nothing_has_changed_until_something_does() {
+  let tree = Tree::new();
+  tree.write("index.html", "<h1>one</h1>");
+  let mut watcher = tree.watcher("");
+
+  assert!(watcher.changes().is_empty());
+}
+
+#[test]
+fn an_added_a_changed_and_a_removed_file_are_each_named_once() {
+  let tree = Tree::new();
+  tree.write("index.html", "<h1>one</h1>");
+  tree.write("assets/old.css", "a {}");
+  let mut watcher = tree.watcher("");
+
+  tree.write("index.html", "<h1>two, longer</h1>");
+  tree.write("assets/new.css", "b {}");
+  fs::remove_file(tree.directory.join("assets/old.css")).expect("the file should go");
+
+  assert_eq!(
+    watcher.changes(),
+    ["/assets/new.css", "/assets/old.css", "/index.html"]
+  );
+  assert!(watcher.changes().is_empty());
+}
+
+#[test]
+fn a_mounted_directory_names_its_files_beneath_its_prefix() {
+  let tree = Tree::new();
+  let mut watcher = tree.watcher("themes");
+
+  tree.write("dark.css", "body {}");
+
+  assert_eq!(watcher.changes(), ["/themes/dark.css"]);
+}
+
+#[test]
+fn a_file_the_root_does_not_serve_is_not_named() {
+  let tree = Tree::new();
+  let mut watcher =
+    Watcher::new(vec![(String::new(), tree.directory.clone())], |name| {
+      !name.ends_with(".map")
+    });
+
+  tree.write("app.js", "1");
+  tree.write("app.js.map", "{}");
+
+  assert_eq!(watcher.changes(), ["/app.js"]);
+}
+
+#[test]
+fn a_burst_is_handed_over_once_it_settles() {
+  let quiet = Duration::from_millis(400);
+  let mut debounce = Debounce::new(quiet);
+  let start = Instant::now();
+
+  assert_eq!(debounce.feed(vec!["/a.js".into()], start), None);
+  assert_eq!(
+    debounce.feed(vec!["/b.js".into()], start + Duration::from_millis(300)),
+    None
+  );
+  assert_eq!(
+    debounce.feed(Vec::new(), start + Duration::from_millis(600)),
+    None
+  );
+  assert_eq!(
+    debounce.feed(Vec::new(), start + Duration::from_millis(700)),
+    Some(vec!["/a.js".to_string(), "/b.js".to_string()])
+  );
+  assert_eq!(
+    debounce.feed(Vec::new(), start + Duration::from_secs(5)),
+    None
+  );
+}
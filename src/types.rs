@@ -2,8 +2,8 @@
 //! frontend, and the conversions that carry them.
 //!
 //! The contract is the JSON data model, with `json.dumps` / `json.loads`
-//! semantics and nothing else. A value outside it raises rather than being
-//! bent into the nearest shape — see ADR-0002.
+//! semantics, and binary values besides. A value outside it raises rather
+//! than being bent into the nearest shape — see ADR-0002.
 //!
 //! Every conversion here is a free function over owned data, so a test can
 //! exercise both directions without opening a Webview or running an event
@@ -14,17 +14,26 @@ use pyo3::{
   Bound, IntoPyObject, Py, PyAny, PyResult, Python,
   exceptions::{PyTypeError, PyValueError},
   types::{
-    PyAnyMethods, PyBool, PyByteArray, PyBytes, PyDict, PyDictMethods, PyFloat,
-    PyFrozenSet, PyInt, PyList, PyListMethods, PySet, PyString, PyTuple, PyTypeMethods,
+    PyAnyMethods, PyBool, PyByteArray, PyByteArrayMethods, PyBytes, PyBytesMethods,
+    PyDict, PyDictMethods, PyFloat, PyFrozenSet, PyInt, PyList, PyListMethods, PySet,
+    PyString, PyTuple, PyType, PyTypeMethods,
   },
 };
 use serde::{
   Deserialize, Deserializer, Serialize, Serializer,
-  de::{MapAccess, SeqAccess, Visitor},
+  de::{DeserializeSeed, MapAccess, SeqAccess, Visitor},
   ser::{SerializeMap, SerializeSeq},
 };
 use serde_json::{Error as JsonError, from_str, to_string};
-use std::{fmt, sync::RwLock};
+use std::{
+  fmt,
+  sync::{
+    RwLock,
+    atomic::{AtomicBool, Ordering},
+  },
+};
+
+use crate::binary;
 
 #[cfg(test)]
 mod tests;
@@ -39,11 +48,128 @@ const INTEGER_LIMIT: i64 = 9_007_199_254_740_992; // 2**53
 /// depths.
 const DEPTH_LIMIT: usize = 128;
 
+/// The key of an integer past ±2**53, written as its decimal digits, with big
+/// integers switched on. Reserved only then: switched off, it is a key like
+/// any other, as it was before big integers existed.
+pub const BIG_INTEGER: &str = "__dry_bigint__";
+
+/// The script that tells the page big integers are switched on, so a `BigInt`
+/// it sends crosses instead of being refused as `JSON.stringify` refuses it.
+pub const BIG_INTEGERS_JS: &str = "Object.defineProperty(window.dry, 'bigIntegers', \
+   { value: true, writable: false, configurable: false, enumerable: false });";
+
 /// The `default=` hook, held for the lifetime of the process because the Api
 /// is registered once. `None` means a value outside the contract raises.
 static DEFAULT_HOOK: RwLock<Option<Py<PyAny>>> = RwLock::new(None);
 
-/// A value of the Bridge contract: exactly the JSON data model.
+/// The `object_hook=` hook, the inbound counterpart to `default=`, held as
+/// that one is. `None` means an object from the frontend arrives as a `dict`.
+static OBJECT_HOOK: RwLock<Option<Py<PyAny>>> = RwLock::new(None);
+
+/// The built-in codecs switched on, held as the hook is. Empty means none is,
+/// and every such value goes to the hook.
+static CODECS: RwLock<Vec<Codec>> = RwLock::new(Vec::new());
+
+/// Whether an integer past ±2**53 crosses as a `BigInt` rather than raising.
+static BIG_INTEGERS: AtomicBool = AtomicBool::new(false);
+
+/// A built-in conversion for a type the standard library has and JSON does
+/// not, for the values nearly every `default=` hook ends up converting.
+/// Each is opt-in: the contract itself still refuses them (ADR-0002).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Codec {
+  /// A `datetime`, `date` or `time`, as its ISO 8601 string.
+  Datetime,
+  /// A `Decimal`, as its string, every digit kept.
+  Decimal,
+  /// A `UUID`, as its hyphenated string.
+  Uuid,
+  /// A `PurePath` or any path beneath it, as its string.
+  Path,
+  /// An `Enum` member, as its value.
+  Enum,
+  /// A dataclass instance, as an object of its fields.
+  Dataclass,
+}
+
+impl Codec {
+  pub const ALL: [Codec; 6] = [
+    Codec::Datetime,
+    Codec::Decimal,
+    Codec::Uuid,
+    Codec::Path,
+    Codec::Enum,
+    Codec::Dataclass,
+  ];
+
+  /// The codec a setting names, or the reason it names none.
+  pub fn from_setting(name: &str) -> Result<Self, String> {
+    match name {
+      "datetime" => Ok(Codec::Datetime),
+      "decimal" => Ok(Codec::Decimal),
+      "uuid" => Ok(Codec::Uuid),
+      "path" => Ok(Codec::Path),
+      "enum" => Ok(Codec::Enum),
+      "dataclass" => Ok(Codec::Dataclass),
+      _ => Err(format!(
+        "'{name}' is not a codec: expected datetime, decimal, uuid, path, enum \
+         or dataclass"
+      )),
+    }
+  }
+
+  /// What a value is checked against to find it this codec's: the classes
+  /// it converts, imported once per value read rather than once per item.
+  fn classes<'py>(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+    let class = |module: &str, name: &str| py.import(module)?.getattr(name);
+    Ok(match self {
+      Codec::Datetime => {
+        PyTuple::new(py, [class("datetime", "date")?, class("datetime", "time")?])?
+          .into_any()
+      },
+      Codec::Decimal => class("decimal", "Decimal")?,
+      Codec::Uuid => class("uuid", "UUID")?,
+      Codec::Path => class("pathlib", "PurePath")?,
+      Codec::Enum => class("enum", "Enum")?,
+      Codec::Dataclass => class("dataclasses", "fields")?,
+    })
+  }
+
+  /// The value this codec turns `value` into, or `None` when it is not one
+  /// this codec converts.
+  fn convert<'py>(
+    self, value: &Bound<'py, PyAny>, classes: &Bound<'py, PyAny>,
+  ) -> PyResult<Option<Bound<'py, PyAny>>> {
+    if self == Codec::Dataclass {
+      // As `dataclasses.is_dataclass` asks it, less the class itself, which
+      // is not an instance to read fields from.
+      if value.is_instance_of::<PyType>()
+        || !value.get_type().hasattr("__dataclass_fields__")?
+      {
+        return Ok(None);
+      }
+      // Field by field, not through `asdict`: that copies deeply, and every
+      // field is read by the contract in turn anyway.
+      let fields = PyDict::new(value.py());
+      for field in classes.call1((value,))?.try_iter()? {
+        let name = field?.getattr("name")?.cast_into::<PyString>()?;
+        fields.set_item(&name, value.getattr(&name)?)?;
+      }
+      return Ok(Some(fields.into_any()));
+    }
+
+    if !value.is_instance(classes)? {
+      return Ok(None);
+    }
+    Ok(Some(match self {
+      Codec::Datetime => value.call_method0("isoformat")?,
+      Codec::Enum => value.getattr("value")?,
+      _ => value.str()?.into_any(),
+    }))
+  }
+}
+
+/// A value of the Bridge contract: the JSON data model, and bytes.
 #[derive(Clone, Debug, PartialEq)]
 pub enum PythonType {
   Null,
@@ -56,6 +182,14 @@ pub enum PythonType {
   /// Keys are already coerced to strings, exactly as `json.dumps` coerces
   /// them, so nothing downstream has to think about key types.
   Object(Vec<(String, PythonType)>),
+  /// `bytes` in Python, a `Uint8Array` in JavaScript. JSON has no such value,
+  /// so on the wire it is an object of a shape `binary` reserves.
+  Bytes(Vec<u8>),
+  /// An `int` past ±2**53 in Python, a `BigInt` in JavaScript, held as its
+  /// decimal digits. Only with big integers switched on; on the wire it is an
+  /// object under `BIG_INTEGER`, since a JSON number that long is read with
+  /// digits missing.
+  BigInteger(String),
 }
 
 impl Serialize for PythonType {
@@ -80,11 +214,40 @@ impl Serialize for PythonType {
         }
         map.end()
       },
+      // Always as base64: which values go aside is for `binary` to settle as
+      // a message is built, so writing a value out has no side effects.
+      PythonType::Bytes(bytes) => {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(binary::INLINE, &binary::encode(bytes))?;
+        map.end()
+      },
+      PythonType::BigInteger(digits) => {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(BIG_INTEGER, digits)?;
+        map.end()
+      },
     }
   }
 }
 
-struct PythonTypeVisitor;
+/// Reads a wire value, with whether big integers cross given explicitly, and
+/// handed down to every value nested inside it.
+#[derive(Clone, Copy)]
+struct Inbound {
+  big_integers: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for Inbound {
+  type Value = PythonType;
+
+  fn deserialize<D: Deserializer<'de>>(
+    self, deserializer: D,
+  ) -> Result<Self::Value, D::Error> {
+    deserializer.deserialize_any(PythonTypeVisitor(self))
+  }
+}
+
+struct PythonTypeVisitor(Inbound);
 
 impl<'de> Visitor<'de> for PythonTypeVisitor {
   type Value = PythonType;
@@ -141,7 +304,7 @@ impl<'de> Visitor<'de> for PythonTypeVisitor {
     self, mut access: A,
   ) -> Result<Self::Value, A::Error> {
     let mut items = Vec::new();
-    while let Some(item) = access.next_element()? {
+    while let Some(item) = access.next_element_seed(self.0)? {
       items.push(item);
     }
     Ok(PythonType::Array(items))
@@ -151,16 +314,71 @@ impl<'de> Visitor<'de> for PythonTypeVisitor {
     self, mut access: A,
   ) -> Result<Self::Value, A::Error> {
     let mut entries = Vec::new();
-    while let Some((key, value)) = access.next_entry::<String, PythonType>()? {
-      entries.push((key, value));
+    while let Some(key) = access.next_key::<String>()? {
+      // A reserved key makes the object a binary value, which holds that key
+      // and nothing else.
+      if key == binary::INLINE || key == binary::ASIDE {
+        let bytes = read_bytes(&key, &access.next_value::<String>()?)?;
+        if !entries.is_empty() || access.next_key::<String>()?.is_some() {
+          return Err(serde::de::Error::custom(format!(
+            "'{key}' is reserved for a binary value, and an object holding it \
+             holds nothing else"
+          )));
+        }
+        return Ok(PythonType::Bytes(bytes));
+      }
+      if key == BIG_INTEGER && self.0.big_integers {
+        let integer = read_big_integer(&access.next_value::<String>()?)?;
+        if !entries.is_empty() || access.next_key::<String>()?.is_some() {
+          return Err(serde::de::Error::custom(format!(
+            "'{key}' is reserved for a big integer, and an object holding it \
+             holds nothing else"
+          )));
+        }
+        return Ok(integer);
+      }
+      entries.push((key, access.next_value_seed(self.0)?));
     }
     Ok(PythonType::Object(entries))
   }
 }
 
+/// The bytes a binary value on the wire stands for: written in as base64, or
+/// held aside under an id.
+fn read_bytes<E: serde::de::Error>(key: &str, value: &str) -> Result<Vec<u8>, E> {
+  if key == binary::INLINE {
+    return binary::decode(value)
+      .ok_or_else(|| E::custom("a binary value is not written in base64"));
+  }
+  binary::take(value).ok_or_else(|| {
+    E::custom(format!(
+      "no binary value is held under '{value}': it was taken already, or \
+       waited too long to be"
+    ))
+  })
+}
+
+/// The integer a big integer on the wire stands for. One that would have fit
+/// in a JSON number is read as one, so `5n` and `5` arrive alike.
+fn read_big_integer<E: serde::de::Error>(digits: &str) -> Result<PythonType, E> {
+  let unsigned = digits.strip_prefix('-').unwrap_or(digits);
+  if unsigned.is_empty() || !unsigned.bytes().all(|digit| digit.is_ascii_digit()) {
+    return Err(E::custom(format!(
+      "'{digits}' is not a big integer: it is written as decimal digits"
+    )));
+  }
+  Ok(match digits.parse::<i64>() {
+    Ok(number) if number.abs() <= INTEGER_LIMIT => PythonType::Integer(number),
+    _ => PythonType::BigInteger(digits.to_string()),
+  })
+}
+
 impl<'de> Deserialize<'de> for PythonType {
   fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-    deserializer.deserialize_any(PythonTypeVisitor)
+    Inbound {
+      big_integers: BIG_INTEGERS.load(Ordering::Relaxed),
+    }
+    .deserialize(deserializer)
   }
 }
 
@@ -191,6 +409,14 @@ impl CallResult {
   }
 }
 
+/// A value a streaming Call yielded, travelling to the frontend ahead of the
+/// reply that ends the Call.
+#[derive(Serialize, Debug)]
+pub struct CallYield {
+  pub call_id: String,
+  pub value: PythonType,
+}
+
 /// Reads a Call off the wire.
 pub fn parse_call(body: &str) -> Result<Call, JsonError> {
   from_str(body)
@@ -201,6 +427,11 @@ pub fn call_result_script(result: &CallResult) -> Result<String, JsonError> {
   Ok(format!("window.dry.resolveCall({})", to_string(result)?))
 }
 
+/// Writes the JavaScript that hands one yielded value to the frontend.
+pub fn call_yield_script(item: &CallYield) -> Result<String, JsonError> {
+  Ok(format!("window.dry.yieldCall({})", to_string(item)?))
+}
+
 /// Installs the `default=` hook, the one escape hatch the contract offers.
 /// It has the signature `json.dumps` gives it: called with the value that is
 /// outside the contract, it returns one that is inside, or raises.
@@ -214,29 +445,80 @@ pub fn set_default_hook(hook: Option<Py<PyAny>>) {
   }
 }
 
+/// Switches on the built-in codecs, alongside whatever `default=` hook is
+/// installed. An empty list switches them all off.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn set_codecs(codecs: Vec<Codec>) {
+  if let Ok(mut slot) = CODECS.write() {
+    *slot = codecs;
+  }
+}
+
+/// Switches big integers on or off: whether an `int` past ±2**53 crosses as a
+/// `BigInt`, or raises as the contract has it.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn set_big_integers(on: bool) {
+  BIG_INTEGERS.store(on, Ordering::Relaxed);
+}
+
 /// Reads a Python value into the Bridge contract, through the installed
-/// `default=` hook.
+/// codecs and `default=` hook, and with big integers as they are switched.
 pub fn from_python(value: &Bound<'_, PyAny>) -> PyResult<PythonType> {
   let hook = DEFAULT_HOOK
     .read()
     .ok()
     .and_then(|slot| slot.as_ref().map(|hook| hook.clone_ref(value.py())));
+  let codecs = CODECS.read().map(|slot| slot.clone()).unwrap_or_default();
+  let big_integers = BIG_INTEGERS.load(Ordering::Relaxed);
   match &hook {
-    Some(hook) => from_python_with(value, Some(hook.bind(value.py()))),
-    None => from_python_with(value, None),
+    Some(hook) => {
+      from_python_using(value, Some(hook.bind(value.py())), &codecs, big_integers)
+    },
+    None => from_python_using(value, None, &codecs, big_integers),
   }
 }
 
 /// Reads a Python value into the Bridge contract, with the `default=` hook
-/// given explicitly rather than taken from the installed one.
+/// given explicitly rather than taken from the installed one, no codecs, and
+/// no big integers.
 pub fn from_python_with<'py>(
   value: &Bound<'py, PyAny>, default: Option<&Bound<'py, PyAny>>,
 ) -> PyResult<PythonType> {
-  read(value, default, 0)
+  from_python_using(value, default, &[], false)
+}
+
+/// Reads a Python value into the Bridge contract, with the codecs, the
+/// `default=` hook and whether big integers cross all given explicitly.
+pub fn from_python_using<'py>(
+  value: &Bound<'py, PyAny>, default: Option<&Bound<'py, PyAny>>, codecs: &[Codec],
+  big_integers: bool,
+) -> PyResult<PythonType> {
+  let codecs = codecs
+    .iter()
+    .map(|codec| Ok((*codec, codec.classes(value.py())?)))
+    .collect::<PyResult<Vec<_>>>()?;
+  read(
+    value,
+    &Outward {
+      default,
+      codecs: &codecs,
+      big_integers,
+    },
+    0,
+  )
+}
+
+/// What a value outside the contract may still be converted by, on its way
+/// out: the codecs switched on, each with the classes it checks against, and
+/// then the hook. And whether an integer past ±2**53 crosses at all.
+struct Outward<'a, 'py> {
+  default: Option<&'a Bound<'py, PyAny>>,
+  codecs: &'a [(Codec, Bound<'py, PyAny>)],
+  big_integers: bool,
 }
 
 fn read<'py>(
-  value: &Bound<'py, PyAny>, default: Option<&Bound<'py, PyAny>>, depth: usize,
+  value: &Bound<'py, PyAny>, outward: &Outward<'_, 'py>, depth: usize,
 ) -> PyResult<PythonType> {
   if depth > DEPTH_LIMIT {
     return Err(PyValueError::new_err(format!(
@@ -256,7 +538,7 @@ fn read<'py>(
   }
 
   if value.is_instance_of::<PyInt>() {
-    return read_integer(value);
+    return read_integer(value, outward.big_integers);
   }
 
   if value.is_instance_of::<PyFloat>() {
@@ -279,7 +561,7 @@ fn read<'py>(
   if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
     let mut items = Vec::new();
     for item in value.try_iter()? {
-      items.push(read(&item?, default, depth + 1)?);
+      items.push(read(&item?, outward, depth + 1)?);
     }
     return Ok(PythonType::Array(items));
   }
@@ -288,7 +570,21 @@ fn read<'py>(
     let dictionary = value.cast::<PyDict>()?;
     let mut entries = Vec::with_capacity(dictionary.len());
     for (key, item) in dictionary.iter() {
-      entries.push((read_key(&key)?, read(&item, default, depth + 1)?));
+      let key = read_key(&key)?;
+      if key == binary::INLINE || key == binary::ASIDE {
+        return Err(PyValueError::new_err(format!(
+          "The dictionary key '{key}' is reserved: the Bridge writes a binary \
+           value under it, and the frontend would read this dictionary as one."
+        )));
+      }
+      if key == BIG_INTEGER && outward.big_integers {
+        return Err(PyValueError::new_err(format!(
+          "The dictionary key '{key}' is reserved: with big_integers on, the \
+           Bridge writes a big integer under it, and the frontend would read \
+           this dictionary as one."
+        )));
+      }
+      entries.push((key, read(&item, outward, depth + 1)?));
     }
     return Ok(PythonType::Object(entries));
   }
@@ -301,18 +597,27 @@ fn read<'py>(
     )));
   }
 
-  if value.is_instance_of::<PyBytes>() || value.is_instance_of::<PyByteArray>() {
-    return Err(PyTypeError::new_err(format!(
-      "{} is outside the Bridge contract: JSON has no bytes. Decode it to a \
-       str, or encode it with base64 and pass the str.",
-      value.get_type().name()?
-    )));
+  if let Ok(bytes) = value.cast::<PyBytes>() {
+    return Ok(PythonType::Bytes(bytes.as_bytes().to_vec()));
+  }
+
+  if let Ok(bytes) = value.cast::<PyByteArray>() {
+    return Ok(PythonType::Bytes(bytes.to_vec()));
+  }
+
+  // The codecs switched on, before the hook, so a hook that is still given
+  // sees only what they leave. What a codec returns is read in turn, which
+  // carries a dataclass of dates, or an Enum whose value is a UUID.
+  for (codec, classes) in outward.codecs {
+    if let Some(replacement) = codec.convert(value, classes)? {
+      return read(&replacement, outward, depth + 1);
+    }
   }
 
   // The one escape hatch, with the signature `json.dumps` gives it.
-  if let Some(default) = default {
+  if let Some(default) = outward.default {
     let replacement = default.call1((value,))?;
-    return read(&replacement, Some(default), depth + 1);
+    return read(&replacement, outward, depth + 1);
   }
 
   Err(PyTypeError::new_err(format!(
@@ -322,16 +627,25 @@ fn read<'py>(
   )))
 }
 
-fn read_integer(value: &Bound<'_, PyAny>) -> PyResult<PythonType> {
+fn read_integer(value: &Bound<'_, PyAny>, big_integers: bool) -> PyResult<PythonType> {
   if let Ok(number) = value.extract::<i64>()
     && number.abs() <= INTEGER_LIMIT
   {
     return Ok(PythonType::Integer(number));
   }
+  // Through `int.__repr__`, since a subclass of `int` may write itself as
+  // something other than its digits.
+  if big_integers {
+    let digits = value
+      .py()
+      .get_type::<PyInt>()
+      .call_method1("__repr__", (value,))?;
+    return Ok(PythonType::BigInteger(digits.extract()?));
+  }
   Err(PyValueError::new_err(format!(
     "{} is outside the Bridge contract: a JSON number carries whole numbers \
      only up to ±2**53, and the frontend would read this one with digits \
-     missing.",
+     missing. Switch big_integers on to send it as a BigInt.",
     value.repr()?
   )))
 }
@@ -378,9 +692,47 @@ fn read_key(key: &Bound<'_, PyAny>) -> PyResult<String> {
   )))
 }
 
+/// Installs the `object_hook=` hook, with the signature `json.loads` gives
+/// it: called with every object the frontend sent, innermost first, once it
+/// is a `dict`, and whatever it returns stands in that object's place.
+///
+/// Passing `None` takes the hook away.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn set_object_hook(hook: Option<Py<PyAny>>) {
+  if let Ok(mut slot) = OBJECT_HOOK.write() {
+    *slot = hook;
+  }
+}
+
+/// Writes a value the frontend sent into a Python object, through the
+/// installed `object_hook=` hook. A value Dry made itself, such as a window
+/// event's, is written by `to_python` and never reaches the hook.
+pub fn decode<'py>(py: Python<'py>, value: &PythonType) -> PyResult<Bound<'py, PyAny>> {
+  let hook = OBJECT_HOOK
+    .read()
+    .ok()
+    .and_then(|slot| slot.as_ref().map(|hook| hook.clone_ref(py)));
+  decode_with(py, value, hook.as_ref().map(|hook| hook.bind(py)))
+}
+
+/// Writes a value the frontend sent into a Python object, with the
+/// `object_hook=` hook given explicitly rather than taken from the installed
+/// one.
+pub fn decode_with<'py>(
+  py: Python<'py>, value: &PythonType, object_hook: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+  write(py, value, object_hook)
+}
+
 /// Writes a value of the Bridge contract back into a Python object.
 pub fn to_python<'py>(
   py: Python<'py>, value: &PythonType,
+) -> PyResult<Bound<'py, PyAny>> {
+  write(py, value, None)
+}
+
+fn write<'py>(
+  py: Python<'py>, value: &PythonType, object_hook: Option<&Bound<'py, PyAny>>,
 ) -> PyResult<Bound<'py, PyAny>> {
   Ok(match value {
     PythonType::Null => py.None().into_bound(py),
@@ -391,27 +743,33 @@ pub fn to_python<'py>(
     PythonType::Array(items) => {
       let list = PyList::empty(py);
       for item in items {
-        list.append(to_python(py, item)?)?;
+        list.append(write(py, item, object_hook)?)?;
       }
       list.into_any()
     },
     PythonType::Object(entries) => {
       let dictionary = PyDict::new(py);
       for (key, item) in entries {
-        dictionary.set_item(key, to_python(py, item)?)?;
+        dictionary.set_item(key, write(py, item, object_hook)?)?;
+      }
+      match object_hook {
+        Some(object_hook) => object_hook.call1((dictionary,))?,
+        None => dictionary.into_any(),
       }
-      dictionary.into_any()
     },
+    PythonType::Bytes(bytes) => PyBytes::new(py, bytes).into_any(),
+    PythonType::BigInteger(digits) => py.get_type::<PyInt>().call1((digits,))?,
   })
 }
 
-/// Writes the arguments of a Call into the tuple a Python callable expects.
+/// Writes the arguments of a Call into the tuple a Python callable expects,
+/// through the installed `object_hook=` hook.
 pub fn arguments_to_python<'py>(
   py: Python<'py>, arguments: &[PythonType],
 ) -> PyResult<Bound<'py, PyTuple>> {
   let py_arguments = arguments
     .iter()
-    .map(|argument| to_python(py, argument))
+    .map(|argument| decode(py, argument))
     .collect::<PyResult<Vec<_>>>()?;
   PyTuple::new(py, py_arguments)
 }
@@ -431,3 +789,15 @@ pub fn to_json(value: &PythonType) -> Result<String, JsonError> {
 pub fn from_json(json: &str) -> Result<PythonType, JsonError> {
   from_str(json)
 }
+
+/// Reads a value of the Bridge contract off the wire, with whether big
+/// integers cross given explicitly rather than taken from the switch.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn from_json_using(
+  json: &str, big_integers: bool,
+) -> Result<PythonType, JsonError> {
+  let mut deserializer = serde_json::Deserializer::from_str(json);
+  let value = Inbound { big_integers }.deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok(value)
+}
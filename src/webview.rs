@@ -1,9 +1,8 @@
 use std::{
-  borrow::Cow,
   collections::HashMap,
   ffi::CStr,
-  fs,
-  path::{Path, PathBuf},
+  io,
+  path::PathBuf,
   sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
@@ -17,19 +16,25 @@ use pyo3::{
   types::{PyAnyMethods, PyDict, PyDictMethods},
 };
 use tao::{event_loop::EventLoopProxy, window::Window};
+#[cfg(windows)]
+use wry::WebViewBuilderExtWindows;
 use wry::{
   Error as WryError, PageLoadEvent, WebContext, WebView, WebViewBuilder,
-  http::{Request, header::CONTENT_TYPE, response::Response},
+  http::{Request, Uri},
 };
 
 use crate::{
-  api::{API_JS, handle_api_requests},
+  api::{API_JS, CANCEL_PREFIX, handle_api_requests, handle_cancel_request},
+  binary::{self, BINARY_JS},
   errors::WebviewError,
   events::{AppEvent, EVENT_PREFIX, EVENTS_JS, handle_event_request},
-  logs,
+  logs, loopback, protocols,
+  root::Root,
+  types::BIG_INTEGERS_JS,
   window::{
     WINDOW_BORDERS_JS, WINDOW_EVENTS_JS, WINDOW_FUNCTIONS_JS, handle_window_requests,
   },
+  workers::{WORKERS, Workers},
 };
 
 #[cfg(test)]
@@ -37,30 +42,153 @@ mod tests;
 
 pub const NAMESPACE_JS: &str = include_str!("js/namespace.js");
 
-/// The scheme of the internal protocol a Root is served over.
-const ROOT_SCHEME: &str = "localfile";
+/// Schemes the Webview answers itself, which a Root cannot be served over.
+const RESERVED_SCHEMES: [&str; 10] = [
+  "about",
+  "blob",
+  "data",
+  "file",
+  "ftp",
+  "http",
+  "https",
+  "javascript",
+  "ws",
+  "wss",
+];
+
+/// Where a Root is served from: the scheme of the internal protocol, and the
+/// host the Webview starts at. Every relative asset a page requests resolves
+/// against this origin and lands in the Root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootOrigin {
+  scheme: String,
+  host: String,
+  /// Read on Windows only; see `build_webview`.
+  #[cfg_attr(not(windows), allow(dead_code))]
+  secure: bool,
+}
+
+impl RootOrigin {
+  /// Reads `scheme://host`, or says why it is not one a Root can be served
+  /// under. The scheme becomes part of a host name on Windows, where the
+  /// webview serves `scheme://host/` as `http://scheme.host/`, so both halves
+  /// are held to what a host label may hold.
+  pub fn parse(origin: &str, secure: bool) -> Result<Self, String> {
+    let (scheme, host) = origin
+      .split_once("://")
+      .ok_or_else(|| format!("'{origin}' is not a scheme and a host."))?;
+    let starts_with_letter = scheme.starts_with(|c: char| c.is_ascii_lowercase());
+    if !starts_with_letter || !is_label(scheme) {
+      return Err(format!(
+        "'{scheme}' cannot be the Root's scheme: a lowercase letter, then \
+         lowercase letters, digits or '-'."
+      ));
+    }
+    if RESERVED_SCHEMES.contains(&scheme) {
+      return Err(format!(
+        "The scheme '{scheme}' is answered by the Webview already and cannot \
+         serve the Root."
+      ));
+    }
+    if !host.split('.').all(is_label) {
+      return Err(format!(
+        "'{host}' cannot be the Root's host: dot-separated labels of \
+         lowercase letters, digits and '-'."
+      ));
+    }
+    Ok(RootOrigin {
+      scheme: scheme.to_string(),
+      host: host.to_string(),
+      secure,
+    })
+  }
 
-/// The prefix Python uses to hand a Root down as a URL. Everything after it is
-/// the absolute path of the directory to serve.
-const ROOT_URL_PREFIX: &str = "localfile://";
+  pub fn scheme(&self) -> &str {
+    &self.scheme
+  }
+
+  /// The address the Webview opens, the top of the Root.
+  fn url(&self) -> String {
+    format!("{}://{}/", self.scheme, self.host)
+  }
+
+  /// Where a page of the Root finds itself, as `location` reports it: on
+  /// Windows, the `http` or `https` address the scheme is served as.
+  fn page_origin(&self) -> String {
+    #[cfg(windows)]
+    {
+      let scheme = if self.secure { "https" } else { "http" };
+      format!("{scheme}://{}.{}", self.scheme, self.host)
+    }
+    #[cfg(not(windows))]
+    {
+      format!("{}://{}", self.scheme, self.host)
+    }
+  }
+}
 
-/// Where the Webview starts when the Content is a Root. Every relative asset a
-/// page requests resolves against this origin and lands in the Root.
-const ROOT_ORIGIN: &str = "localfile://localhost/";
+/// How a Root reaches the Webview.
+pub enum Transport {
+  /// Over an internal protocol, under an origin of its own.
+  Protocol(RootOrigin),
+  /// Over HTTP, from a server on the loopback interface.
+  Loopback,
+}
 
-/// The file a request for a directory is served from.
-const ROOT_INDEX: &str = "index.html";
+impl Transport {
+  /// Reads the `root_transport` setting: `'protocol'` or `'loopback'`, the
+  /// former served under `origin`.
+  pub fn from_setting(setting: &str, origin: RootOrigin) -> Result<Self, String> {
+    match setting {
+      "protocol" => Ok(Transport::Protocol(origin)),
+      "loopback" => Ok(Transport::Loopback),
+      other => Err(format!(
+        "root_transport must be 'protocol' or 'loopback', not '{other}'."
+      )),
+    }
+  }
+
+  /// Starts serving a Root this way. Only the loopback server can fail to
+  /// start, when the system has no port to give it.
+  pub fn serve(self, root: Root) -> io::Result<Served> {
+    match self {
+      Transport::Protocol(origin) => Ok(Served::Protocol(root, origin)),
+      Transport::Loopback => loopback::start(root).map(Served::Loopback),
+    }
+  }
+}
+
+/// A Root on its way to the Webview.
+pub enum Served {
+  /// To be answered over the internal protocol, under this origin.
+  Protocol(Root, RootOrigin),
+  /// Already answered by the loopback server, whose entry address this is.
+  Loopback(String),
+}
+
+/// Whether a piece of a host name is one: lowercase letters, digits and `-`,
+/// neither first nor last.
+fn is_label(label: &str) -> bool {
+  !label.is_empty()
+    && !label.starts_with('-')
+    && !label.ends_with('-')
+    && label
+      .chars()
+      .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
 
 pub fn build_webview(
   window: &Window, ipc_handler: impl Fn(Request<String>) + 'static,
-  html: Option<String>, url: Option<String>, decorations: bool, api: bool,
-  dev_tools: bool, udf: String,
+  html: Option<String>, url: Option<String>, root: Option<Served>,
+  protocols: HashMap<String, Py<PyAny>>, decorations: bool, api: bool, dev_tools: bool,
+  big_integers: bool, udf: String,
 ) -> Result<WebView, WryError> {
   let data_directory = PathBuf::from(udf);
   let mut web_context = WebContext::new(Some(data_directory));
 
   let mut builder = WebViewBuilder::new_with_web_context(&mut web_context)
     .with_initialization_script(NAMESPACE_JS)
+    .with_initialization_script(BINARY_JS)
     .with_initialization_script(WINDOW_FUNCTIONS_JS)
     .with_initialization_script(WINDOW_EVENTS_JS)
     // Always, Api or no Api: an Event needs no Api to cross, and the window
@@ -77,36 +205,85 @@ pub fn build_webview(
     builder = builder.with_initialization_script(WINDOW_BORDERS_JS);
   }
 
-  let webview = match (html, url) {
-    (Some(html), _) => builder.with_html(html).build(window)?,
-    (None, Some(url)) => match url.strip_prefix(ROOT_URL_PREFIX) {
-      Some(directory) => {
-        let root = Root::new(PathBuf::from(directory));
-
-        builder = builder
-          .with_custom_protocol(ROOT_SCHEME.into(), move |_webview_id, request| {
-            root.serve(request.uri().path())
-          })
-          .with_url(ROOT_ORIGIN);
+  if big_integers {
+    builder = builder.with_initialization_script(BIG_INTEGERS_JS);
+  }
 
-        builder.build(window)?
-      },
-      None => {
-        let arrived = Arc::new(AtomicBool::new(false));
-        let watched = Arc::clone(&arrived);
-
-        builder = builder.with_on_page_load_handler(move |event, at| {
-          if matches!(event, PageLoadEvent::Finished) && at != BLANK_PAGE {
-            watched.store(true, Ordering::Relaxed);
-          }
-        });
-
-        let webview = builder.with_url(&url).build(window)?;
-        watch_navigation(url, arrived);
-        webview
+  // Asynchronous, so the thread drawing the window hands each request to the
+  // portal and carries on; the handler answers whenever it is done.
+  for (scheme, handler) in protocols {
+    let name = scheme.clone();
+    builder = builder.with_asynchronous_custom_protocol(
+      name,
+      move |_webview_id, request, responder| {
+        protocols::serve(&scheme, &handler, request, responder)
       },
+    );
+  }
+
+  let webview = match (html, url, root) {
+    (Some(html), _, _) => builder.with_html(html).build(window)?,
+    (None, _, Some(Served::Loopback(entry))) => {
+      let origin = entry
+        .parse::<Uri>()
+        .ok()
+        .and_then(|uri| {
+          uri
+            .authority()
+            .map(|authority| format!("http://{authority}"))
+        })
+        .unwrap_or_default();
+      builder
+        .with_initialization_script(binary::offer(&origin))
+        .with_url(entry)
+        .build(window)?
     },
-    (None, None) => panic!("No content provided."),
+    (None, _, Some(Served::Protocol(root, origin))) => {
+      // Only Windows maps a custom scheme onto `http`, so only there is there
+      // an `https` to choose. WebKitGTK registers the scheme as secure itself.
+      #[cfg(windows)]
+      {
+        builder = builder.with_https_scheme(origin.secure);
+      }
+      // Each request is answered off the window's thread, by one of a few
+      // workers, so a large file read from a slow disk holds up that request
+      // and not the window drawing, and a burst of requests waits its turn
+      // rather than starting a thread apiece. The Bridge's side channel shares
+      // the origin, beneath a path of its own.
+      let root = Arc::new(root);
+      let workers = Workers::new("dry-root", WORKERS);
+      builder = builder
+        .with_initialization_script(binary::offer(&origin.page_origin()))
+        .with_asynchronous_custom_protocol(
+          origin.scheme.clone(),
+          move |_webview_id, request, responder| {
+            let root = Arc::clone(&root);
+            workers.run(move || {
+              let response =
+                binary::answer(&request).unwrap_or_else(|| root.serve(&request));
+              responder.respond(response)
+            });
+          },
+        )
+        .with_url(origin.url());
+
+      builder.build(window)?
+    },
+    (None, Some(url), None) => {
+      let arrived = Arc::new(AtomicBool::new(false));
+      let watched = Arc::clone(&arrived);
+
+      builder = builder.with_on_page_load_handler(move |event, at| {
+        if matches!(event, PageLoadEvent::Finished) && at != BLANK_PAGE {
+          watched.store(true, Ordering::Relaxed);
+        }
+      });
+
+      let webview = builder.with_url(&url).build(window)?;
+      watch_navigation(url, arrived);
+      webview
+    },
+    (None, None, None) => panic!("No content provided."),
   };
 
   Ok(webview)
@@ -285,176 +462,6 @@ fn probe(py: Python<'_>, url: &str) -> PyResult<(String, String)> {
     .extract::<(String, String)>()
 }
 
-/// A Root: a local directory served to the Webview, one file per request.
-///
-/// The directory is canonicalised once, up front, so every resolved path can be
-/// tested against it without walking symlinks again on each request.
-struct Root {
-  directory: PathBuf,
-}
-
-/// Why a request did not reach a file inside the Root.
-#[derive(Debug, PartialEq, Eq)]
-enum Rejection {
-  /// The request resolved outside the Root, or tried to.
-  Outside,
-  /// The request resolved inside the Root but there is no file there.
-  NotFound,
-}
-
-impl Root {
-  fn new(directory: PathBuf) -> Self {
-    let directory = directory.canonicalize().unwrap_or(directory);
-    Root { directory }
-  }
-
-  /// Answers one request for a path beneath the Root.
-  fn serve(&self, request_path: &str) -> Response<Cow<'static, [u8]>> {
-    match self.resolve(request_path) {
-      Ok(path) => match fs::read(&path) {
-        Ok(content) => respond(200, content_type(&path), Cow::Owned(content)),
-        Err(_) => respond(404, "text/plain; charset=utf-8", not_found(request_path)),
-      },
-      Err(Rejection::NotFound) => {
-        respond(404, "text/plain; charset=utf-8", not_found(request_path))
-      },
-      Err(Rejection::Outside) => respond(
-        403,
-        "text/plain; charset=utf-8",
-        Cow::Owned(format!("Outside the root: {request_path}").into_bytes()),
-      ),
-    }
-  }
-
-  /// Turns a requested path into the file it names inside the Root.
-  ///
-  /// A path escaping the Root is refused twice over: `..` and anything that
-  /// would be read as a separator or a drive are rejected before the join, and
-  /// the canonical result is then required to sit beneath the Root, which
-  /// catches a symlink pointing out of it.
-  fn resolve(&self, request_path: &str) -> Result<PathBuf, Rejection> {
-    let decoded = percent_decode(request_path);
-
-    let mut candidate = self.directory.clone();
-    for component in decoded.split('/') {
-      if component.is_empty() || component == "." {
-        continue;
-      }
-      if component == ".."
-        || component.contains('\\')
-        || component.contains(':')
-        || component.contains('\0')
-      {
-        return Err(Rejection::Outside);
-      }
-      candidate.push(component);
-    }
-
-    if candidate.is_dir() {
-      candidate.push(ROOT_INDEX);
-    }
-
-    let resolved = candidate.canonicalize().map_err(|_| Rejection::NotFound)?;
-
-    if !resolved.starts_with(&self.directory) {
-      return Err(Rejection::Outside);
-    }
-
-    if !resolved.is_file() {
-      return Err(Rejection::NotFound);
-    }
-
-    Ok(resolved)
-  }
-}
-
-fn respond(
-  status: u16, content_type: &str, body: Cow<'static, [u8]>,
-) -> Response<Cow<'static, [u8]>> {
-  Response::builder()
-    .status(status)
-    .header(CONTENT_TYPE, content_type)
-    .body(body)
-    .expect("Failed to build response")
-}
-
-fn not_found(request_path: &str) -> Cow<'static, [u8]> {
-  Cow::Owned(format!("Not found: {request_path}").into_bytes())
-}
-
-/// The content type of a file, read from its extension. An extension we do not
-/// know gets `application/octet-stream` rather than a guess at the bytes.
-fn content_type(path: &Path) -> &'static str {
-  let extension = path
-    .extension()
-    .and_then(|extension| extension.to_str())
-    .unwrap_or_default()
-    .to_ascii_lowercase();
-
-  match extension.as_str() {
-    "html" | "htm" => "text/html; charset=utf-8",
-    "js" | "mjs" => "text/javascript; charset=utf-8",
-    "css" => "text/css; charset=utf-8",
-    "json" | "map" => "application/json; charset=utf-8",
-    "txt" => "text/plain; charset=utf-8",
-    "csv" => "text/csv; charset=utf-8",
-    "xml" => "application/xml; charset=utf-8",
-    "wasm" => "application/wasm",
-    "pdf" => "application/pdf",
-    "svg" => "image/svg+xml",
-    "png" => "image/png",
-    "jpg" | "jpeg" => "image/jpeg",
-    "gif" => "image/gif",
-    "webp" => "image/webp",
-    "avif" => "image/avif",
-    "bmp" => "image/bmp",
-    "ico" => "image/x-icon",
-    "woff" => "font/woff",
-    "woff2" => "font/woff2",
-    "ttf" => "font/ttf",
-    "otf" => "font/otf",
-    "mp3" => "audio/mpeg",
-    "wav" => "audio/wav",
-    "ogg" | "oga" => "audio/ogg",
-    "mp4" => "video/mp4",
-    "webm" => "video/webm",
-    _ => "application/octet-stream",
-  }
-}
-
-/// Reads the percent-escapes out of a request path, so a file whose name holds
-/// a space or a non-ASCII character is found on disk.
-fn percent_decode(input: &str) -> String {
-  fn hex(byte: u8) -> Option<u8> {
-    match byte {
-      b'0'..=b'9' => Some(byte - b'0'),
-      b'a'..=b'f' => Some(byte - b'a' + 10),
-      b'A'..=b'F' => Some(byte - b'A' + 10),
-      _ => None,
-    }
-  }
-
-  let bytes = input.as_bytes();
-  let mut decoded = Vec::with_capacity(bytes.len());
-  let mut index = 0;
-
-  while index < bytes.len() {
-    if bytes[index] == b'%'
-      && let Some(&high) = bytes.get(index + 1)
-      && let Some(&low) = bytes.get(index + 2)
-      && let (Some(high), Some(low)) = (hex(high), hex(low))
-    {
-      decoded.push(high * 16 + low);
-      index += 3;
-      continue;
-    }
-    decoded.push(bytes[index]);
-    index += 1;
-  }
-
-  String::from_utf8_lossy(&decoded).into_owned()
-}
-
 /// The one handler every message from the frontend arrives at, on the thread
 /// that owns the window. Neither branch blocks: a window request is a message
 /// to the event loop, and a Call is handed to the portal and left to finish on
@@ -476,6 +483,14 @@ pub fn build_ipc_handler(
       return;
     }
 
+    // A cancellation only ever follows a Call, so it needs an Api.
+    if let Some(call_id) = request_body.strip_prefix(CANCEL_PREFIX) {
+      if api.is_some() {
+        handle_cancel_request(call_id);
+      }
+      return;
+    }
+
     if let Some(api) = &api
       && let Err(err) = handle_api_requests(request_body, api)
     {
@@ -0,0 +1,70 @@
+//! A fixed set of threads that answers a Root's requests.
+//!
+//! A page that loads hundreds of assets, or a `<video>` seeking as fast as the
+//! user drags, asks for far more at once than the disk can give. A thread per
+//! request would start them all, each holding what it has read. A fixed set
+//! reads for that many at once; the rest of the requests wait their turn in a
+//! queue, where a request costs only its headers.
+//!
+//! A job that panics is caught, logged and dropped, and its thread goes on to
+//! the next: a set that lost a thread to every panic would, after a few, leave
+//! every later request waiting forever.
+
+use std::{
+  panic::{self, AssertUnwindSafe},
+  sync::{
+    Arc, Mutex,
+    mpsc::{self, Sender},
+  },
+  thread,
+};
+
+use crate::logs;
+
+#[cfg(test)]
+mod tests;
+
+/// How many requests a Root reads from disk at once.
+pub const WORKERS: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+pub struct Workers {
+  jobs: Sender<Job>,
+}
+
+impl Workers {
+  /// Starts `count` threads, which live as long as the set does.
+  pub fn new(name: &str, count: usize) -> Self {
+    let (jobs, queue) = mpsc::channel::<Job>();
+    let queue = Arc::new(Mutex::new(queue));
+    for index in 0..count.max(1) {
+      let queue = Arc::clone(&queue);
+      let name = format!("{name}-{index}");
+      let _ = thread::Builder::new().name(name.clone()).spawn(move || {
+        loop {
+          // The lock is held for the taking only, not for the job.
+          let job = match queue.lock() {
+            Ok(queue) => queue.recv(),
+            Err(_) => return,
+          };
+          let Ok(job) = job else {
+            return;
+          };
+          if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+            logs::error(
+              logs::WEBVIEW,
+              format!("A request on {name} panicked, and was not answered."),
+            );
+          }
+        }
+      });
+    }
+    Workers { jobs }
+  }
+
+  /// Queues a job for the next thread that is free.
+  pub fn run(&self, job: impl FnOnce() + Send + 'static) {
+    let _ = self.jobs.send(Box::new(job));
+  }
+}
@@ -0,0 +1,61 @@
+//! Tests for the fixed set of threads a Root's requests are answered on.
+
+use super::*;
+use std::{
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc,
+  },
+  time::Duration,
+};
+
+/// Every job runs, and never more of them at once than there are threads.
+#[test]
+fn every_job_runs_and_no_more_at_once_than_there_are_workers() {
+  let workers = Workers::new("dry-test", 2);
+  let running = Arc::new(AtomicUsize::new(0));
+  let most = Arc::new(AtomicUsize::new(0));
+  let (done, finished) = mpsc::channel();
+
+  for _ in 0..8 {
+    let (running, most, done) = (Arc::clone(&running), Arc::clone(&most), done.clone());
+    workers.run(move || {
+      let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+      most.fetch_max(now, Ordering::SeqCst);
+      thread::sleep(Duration::from_millis(20));
+      running.fetch_sub(1, Ordering::SeqCst);
+      let _ = done.send(());
+    });
+  }
+
+  for _ in 0..8 {
+    finished
+      .recv_timeout(Duration::from_secs(10))
+      .expect("every job should run");
+  }
+  assert!(most.load(Ordering::SeqCst) <= 2);
+}
+
+/// A job that panics takes nothing down with it: more panics than there are
+/// threads, and the jobs queued after them still run.
+#[test]
+fn a_job_that_panics_does_not_stop_the_jobs_after_it() {
+  let workers = Workers::new("dry-test", 2);
+  let (done, finished) = mpsc::channel();
+
+  for _ in 0..4 {
+    workers.run(|| panic!("a job that panics"));
+  }
+  for _ in 0..4 {
+    let done = done.clone();
+    workers.run(move || {
+      let _ = done.send(());
+    });
+  }
+
+  for _ in 0..4 {
+    finished
+      .recv_timeout(Duration::from_secs(10))
+      .expect("every job after the panics should run");
+  }
+}
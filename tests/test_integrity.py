@@ -0,0 +1,159 @@
+"""
+Writing and reading the integrity manifest a Root is checked against.
+
+The manifest names each file as the Root serves it, the URL path without its
+leading slash: `assets/app.js` for `/assets/app.js`, and `themes/dark.css` for
+a file at the top of the location mounted at `/themes`. A name that differs
+from what the Root asks for is a file the Root refuses, so every shape of
+`root=` is written here and its names checked one by one.
+
+Nothing here opens a window. Serving a checked Root is tested in Rust.
+"""
+
+import json
+import os
+import unittest
+from hashlib import sha256
+from pathlib import Path
+from tempfile import TemporaryDirectory
+from zipfile import ZipFile
+
+from dry.integrity import read_manifest, write_manifest
+
+# What the build produced, by the name the Root serves each file under.
+FILES = {
+    'index.html': b'<!doctype html>',
+    'assets/app.js': b'console.log(1)',
+    'assets/app.js.gz': b'\x1f\x8b',
+    '.well-known/security.txt': b'Contact: security@example.com',
+}
+
+
+def digest(content: bytes) -> str:
+    return sha256(content).hexdigest()
+
+
+class Scratch(unittest.TestCase):
+    """A directory of its own for each test, gone once it ends."""
+
+    def setUp(self) -> None:
+        scratch = TemporaryDirectory()
+        self.addCleanup(scratch.cleanup)
+        self.scratch = Path(scratch.name)
+
+    def build(self, name: str, files: dict[str, bytes]) -> Path:
+        directory = self.scratch / name
+        for file, content in files.items():
+            path = directory / file
+            path.parent.mkdir(parents=True, exist_ok=True)
+            path.write_bytes(content)
+        return directory
+
+    def written(self, root: object) -> dict[str, str]:
+        manifest = self.scratch / 'dist.manifest.json'
+        write_manifest(root, manifest)  # pyright: ignore[reportArgumentType]
+        return read_manifest(manifest)
+
+
+class TheRoundTrip(Scratch):
+    def test_a_directory_is_named_as_the_root_serves_it(self):
+        root = self.build('dist', FILES)
+
+        self.assertEqual(
+            self.written(root),
+            {name: digest(content) for name, content in FILES.items()},
+        )
+
+    def test_a_zip_archive_is_named_from_its_top(self):
+        archive = self.scratch / 'dist.zip'
+        with ZipFile(archive, 'w') as zipped:
+            for name, content in FILES.items():
+                zipped.writestr(name, content)
+
+        self.assertEqual(
+            self.written(archive),
+            {name: digest(content) for name, content in FILES.items()},
+        )
+
+    def test_each_mount_is_named_beneath_its_prefix(self):
+        site = self.build('dist', {'index.html': b'site'})
+        themes = self.build('themes', {'dark.css': b'dark', 'fonts/a.woff2': b'a'})
+
+        self.assertEqual(
+            self.written({'/': site, '/themes/': themes}),
+            {
+                'index.html': digest(b'site'),
+                'themes/dark.css': digest(b'dark'),
+                'themes/fonts/a.woff2': digest(b'a'),
+            },
+        )
+
+    def test_the_manifest_is_sorted_json_under_sha256(self):
+        root = self.build('dist', FILES)
+        manifest = write_manifest(root, self.scratch / 'dist.manifest.json')
+
+        content = json.loads(manifest.read_text(encoding='utf-8'))
+
+        self.assertEqual(list(content), ['sha256'])
+        self.assertEqual(list(content['sha256']), sorted(FILES))
+
+    def test_a_location_that_is_neither_is_refused(self):
+        with self.assertRaises(NotADirectoryError):
+            write_manifest(self.scratch / 'missing', self.scratch / 'm.json')
+
+
+@unittest.skipUnless(hasattr(os, 'symlink'), 'symlinks are not available')
+class SymlinkedDirectories(Scratch):
+    def test_a_symlinked_directory_is_not_followed(self):
+        root = self.build('dist', {'index.html': b'site'})
+        elsewhere = self.build('elsewhere', {'secret.txt': b'secret'})
+        try:
+            (root / 'linked').symlink_to(elsewhere, target_is_directory=True)
+            (root / 'loop').symlink_to(root, target_is_directory=True)
+        except OSError as error:
+            self.skipTest(f'symlinks cannot be made here: {error}')
+
+        self.assertEqual(self.written(root), {'index.html': digest(b'site')})
+
+
+class MalformedManifests(Scratch):
+    def refusal(self, text: str) -> str:
+        manifest = self.scratch / 'dist.manifest.json'
+        manifest.write_text(text, encoding='utf-8')
+        with self.assertRaises(ValueError) as raised:
+            read_manifest(manifest)
+        return str(raised.exception)
+
+    def test_text_that_is_not_json_is_refused(self):
+        self.assertIn('is not an integrity manifest', self.refusal('{"sha256": '))
+
+    def test_json_without_a_sha256_object_is_refused(self):
+        for text in ['[]', '{}', '{"sha256": []}', '{"sha512": {}}', '"sha256"']:
+            with self.subTest(text=text):
+                self.assertIn('no "sha256" object', self.refusal(text))
+
+    def test_a_digest_that_is_not_one_is_refused(self):
+        good = digest(b'')
+        for value in [good[:63], f'{good[:63]}g', f'+{good[1:]}', None, 5]:
+            text = json.dumps({'sha256': {'app.js': value}})
+            with self.subTest(value=value):
+                self.assertIn("digest of 'app.js'", self.refusal(text))
+
+    def test_a_digest_in_uppercase_is_read_as_the_root_reads_it(self):
+        good = digest(b'')
+        manifest = self.scratch / 'dist.manifest.json'
+        manifest.write_text(
+            json.dumps({'sha256': {'app.js': good.upper()}}), encoding='utf-8'
+        )
+
+        self.assertEqual(read_manifest(manifest), {'app.js': good})
+
+    def test_an_empty_manifest_is_read_as_one(self):
+        manifest = self.scratch / 'dist.manifest.json'
+        manifest.write_text('{"sha256": {}}', encoding='utf-8')
+
+        self.assertEqual(read_manifest(manifest), {})
+
+
+if __name__ == '__main__':
+    unittest.main()
@@ -56,10 +56,25 @@ class TypesAreChecked(unittest.TestCase):
 
     def test_every_shape_in_the_bridge_contract_is_recognised(self):
         def take(
-            a: None, b: bool, c: int, d: float, e: str, f: list, g: dict
+            a: None, b: bool, c: int, d: float, e: str, f: list, g: dict, h: bytes
         ) -> None: ...
 
-        self.assertIsNone(refusal(take, None, True, 1, 1.5, 'x', [], {}))
+        self.assertIsNone(refusal(take, None, True, 1, 1.5, 'x', [], {}, b''))
+
+    def test_bytes_are_named_as_the_frontend_sent_them(self):
+        def take(data: bytes) -> None: ...
+
+        self.assertEqual(
+            refusal(take, 'QUI='),
+            'call expects bytes for data, received string instead.',
+        )
+
+        def name(label: str) -> None: ...
+
+        self.assertEqual(
+            refusal(name, b'AB'),
+            'call expects str for label, received Uint8Array instead.',
+        )
 
     def test_a_boolean_is_not_an_integer(self):
         def take(count: int) -> None: ...